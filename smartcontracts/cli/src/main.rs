@@ -0,0 +1,231 @@
+//! `zkmltrack`: register tasks, publish model files and follow a registry from
+//! the command line.
+//!
+//! Connection settings come from `--rpc-url`, `--registry` and `--private-key`,
+//! or the `ZKMLTRACK_RPC_URL`, `ZKMLTRACK_REGISTRY` and `ZKMLTRACK_PRIVATE_KEY`
+//! environment variables. The key is only needed for commands that send
+//! transactions.
+
+mod rpc;
+
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use alloy_primitives::{Address, B256, U256};
+use k256::ecdsa::SigningKey;
+use sha2::{Digest, Sha256};
+use zkmltrack_client::{Client, RegistryEvent, Transport, Version};
+
+use rpc::HttpTransport;
+
+const USAGE: &str = "usage: zkmltrack <command> [options]
+
+commands:
+  register-task <task_id> [--fee WEI]
+  publish <task_id> <model_file> [--uri URI] [--fee WEI]
+  get-latest <task_id>
+  history <task_id>
+  watch [--from-block N] [--interval SECONDS]
+
+options:
+  --rpc-url URL         HTTP JSON-RPC endpoint (ZKMLTRACK_RPC_URL)
+  --registry ADDRESS    registry contract (ZKMLTRACK_REGISTRY)
+  --private-key HEX     signing key for transactions (ZKMLTRACK_PRIVATE_KEY)";
+
+/// Versions fetched per `getVersionsRange` call by `history`.
+const HISTORY_PAGE: u64 = 50;
+
+type Result<T> = std::result::Result<T, String>;
+
+struct Args {
+  positional: Vec<String>,
+  options: HashMap<String, String>,
+}
+
+impl Args {
+  fn parse(raw: impl Iterator<Item = String>) -> Result<Self> {
+    let mut positional = Vec::new();
+    let mut options = HashMap::new();
+    let mut raw = raw.peekable();
+    while let Some(arg) = raw.next() {
+      match arg.strip_prefix("--") {
+        Some(name) => {
+          let value = raw.next().ok_or_else(|| format!("--{name} needs a value"))?;
+          options.insert(name.to_string(), value);
+        }
+        None => positional.push(arg),
+      }
+    }
+    Ok(Args { positional, options })
+  }
+
+  fn positional(&self, index: usize, name: &str) -> Result<&str> {
+    self
+      .positional
+      .get(index)
+      .map(String::as_str)
+      .ok_or_else(|| format!("missing <{name}>\n\n{USAGE}"))
+  }
+
+  /// `--name`, falling back to the environment variable `env_var`.
+  fn setting(&self, name: &str, env_var: &str) -> Option<String> {
+    self.options.get(name).cloned().or_else(|| env::var(env_var).ok())
+  }
+
+  fn parsed<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>> {
+    self
+      .options
+      .get(name)
+      .map(|value| value.parse().map_err(|_| format!("invalid --{name} {value:?}")))
+      .transpose()
+  }
+}
+
+fn main() -> ExitCode {
+  match run(Args::parse(env::args().skip(1))) {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(error) => {
+      eprintln!("error: {error}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+fn run(args: Result<Args>) -> Result<()> {
+  let args = args?;
+  let command = args.positional(0, "command")?;
+  let client = connect(&args)?;
+  match command {
+    "register-task" => {
+      let task_id = args.positional(1, "task_id")?;
+      let fee: U256 = match args.parsed("fee")? {
+        Some(fee) => fee,
+        None => client.fees().map_err(|e| e.to_string())?.0,
+      };
+      let tx_hash = client.register_task(task_id, fee).map_err(|e| e.to_string())?;
+      confirm(&client, tx_hash)?;
+      println!("registered {task_id}");
+    }
+    "publish" => {
+      let task_id = args.positional(1, "task_id")?;
+      let path = args.positional(2, "model_file")?;
+      let hash = sha256_file(path).map_err(|e| format!("{path}: {e}"))?;
+      let fee = match args.parsed("fee")? {
+        Some(fee) => fee,
+        None => client.fees().map_err(|e| e.to_string())?.1,
+      };
+      let tx_hash = match args.options.get("uri") {
+        Some(uri) => client.publish_with_uri(task_id, hash, uri, fee),
+        None => client.publish(task_id, hash, fee),
+      }
+      .map_err(|e| e.to_string())?;
+      confirm(&client, tx_hash)?;
+      let latest = client.get_latest(task_id).map_err(|e| e.to_string())?;
+      println!("published {task_id}");
+      print_version(&latest);
+    }
+    "get-latest" => {
+      let task_id = args.positional(1, "task_id")?;
+      print_version(&client.get_latest(task_id).map_err(|e| e.to_string())?);
+    }
+    "history" => {
+      let task_id = args.positional(1, "task_id")?;
+      let count = client.get_version_count(task_id).map_err(|e| e.to_string())?;
+      let mut from_version = 1;
+      while from_version <= count {
+        let page = client
+          .get_versions_range(task_id, from_version, HISTORY_PAGE)
+          .map_err(|e| e.to_string())?;
+        if page.is_empty() {
+          break;
+        }
+        for version in &page {
+          print_version(version);
+        }
+        from_version += page.len() as u64;
+      }
+    }
+    "watch" => {
+      let from_block = match args.parsed("from-block")? {
+        Some(block) => block,
+        None => client.transport().block_number().map_err(|e| e.to_string())?,
+      };
+      let interval = Duration::from_secs(args.parsed("interval")?.unwrap_or(5));
+      let mut events = client.events(from_block);
+      loop {
+        for event in events.poll().map_err(|e| e.to_string())? {
+          print_event(&event);
+        }
+        thread::sleep(interval);
+      }
+    }
+    _ => return Err(format!("unknown command {command:?}\n\n{USAGE}")),
+  }
+  Ok(())
+}
+
+fn connect(args: &Args) -> Result<Client<HttpTransport>> {
+  let url = args
+    .setting("rpc-url", "ZKMLTRACK_RPC_URL")
+    .ok_or("no RPC URL: pass --rpc-url or set ZKMLTRACK_RPC_URL")?;
+  let registry: Address = args
+    .setting("registry", "ZKMLTRACK_REGISTRY")
+    .ok_or("no registry: pass --registry or set ZKMLTRACK_REGISTRY")?
+    .parse()
+    .map_err(|_| "invalid registry address")?;
+  let signer = args
+    .setting("private-key", "ZKMLTRACK_PRIVATE_KEY")
+    .map(|key| {
+      let bytes: B256 = key.parse().map_err(|_| "invalid private key")?;
+      SigningKey::from_slice(bytes.as_slice()).map_err(|_| "invalid private key")
+    })
+    .transpose()?;
+  Ok(Client::new(HttpTransport::new(&url, signer)?, registry))
+}
+
+/// Waits for the transaction to be mined, failing if it reverted.
+fn confirm(client: &Client<HttpTransport>, tx_hash: B256) -> Result<()> {
+  println!("sent {tx_hash}");
+  loop {
+    match client.transport().receipt_status(tx_hash).map_err(|e| e.to_string())? {
+      Some(true) => return Ok(()),
+      Some(false) => return Err(format!("transaction {tx_hash} reverted")),
+      None => thread::sleep(Duration::from_secs(1)),
+    }
+  }
+}
+
+fn sha256_file(path: &str) -> io::Result<B256> {
+  let mut hasher = Sha256::new();
+  io::copy(&mut File::open(path)?, &mut hasher)?;
+  Ok(B256::from_slice(&hasher.finalize()))
+}
+
+fn print_version(version: &Version) {
+  println!(
+    "v{:<4} {}  publisher {}  at {} (block {})",
+    version.version, version.hash, version.publisher, version.timestamp, version.block_number
+  );
+  if !version.artifact_uri.is_empty() {
+    println!("      {}", version.artifact_uri);
+  }
+}
+
+fn print_event(event: &RegistryEvent) {
+  match event {
+    RegistryEvent::TaskRegistered(e) => println!("registered {} by {}", e.task_id, e.registrar),
+    RegistryEvent::VersionPublished(e) => {
+      println!("published  {} v{} {} by {}", e.task_id, e.version, e.hash, e.publisher)
+    }
+    RegistryEvent::VersionYanked(e) => println!("yanked     {} v{}: {}", e.task_id, e.version, e.reason),
+    RegistryEvent::VersionPinned(e) => println!("pinned     {} v{} by {}", e.task_id, e.version, e.consumer),
+    RegistryEvent::PinnedVersionYanked(e) => {
+      println!("pin broken {} v{} ({} pinners)", e.task_id, e.version, e.pin_count)
+    }
+  }
+}
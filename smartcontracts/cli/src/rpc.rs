@@ -0,0 +1,295 @@
+//! Minimal JSON-RPC transport: blocking HTTP(S) requests and locally signed
+//! EIP-1559 transactions.
+
+use std::fmt;
+use std::time::Duration;
+
+use alloy_primitives::{Address, Bytes, Log, B256, U256};
+use alloy_rlp::{Encodable, Header};
+use k256::ecdsa::SigningKey;
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Url;
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+use zkmltrack_client::{RawLog, Transport};
+
+#[derive(Debug)]
+pub enum RpcError {
+  /// The request never got a response: connection, TLS or timeout failures.
+  Transport(reqwest::Error),
+  Http(u16),
+  /// A JSON-RPC error object; `data` holds revert data when the node sends it.
+  Rpc { code: i64, message: String, data: Option<Bytes> },
+  Malformed(String),
+}
+
+impl fmt::Display for RpcError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      RpcError::Transport(error) => write!(f, "{error}"),
+      RpcError::Http(status) => write!(f, "HTTP {status}"),
+      RpcError::Rpc { code, message, .. } => write!(f, "{message} (code {code})"),
+      RpcError::Malformed(what) => write!(f, "malformed response: {what}"),
+    }
+  }
+}
+
+impl From<reqwest::Error> for RpcError {
+  fn from(error: reqwest::Error) -> Self {
+    RpcError::Transport(error)
+  }
+}
+
+/// Widest block range asked of `eth_getLogs` at once; public endpoints
+/// commonly reject anything above 10,000 blocks.
+const LOG_RANGE: u64 = 10_000;
+
+pub struct HttpTransport {
+  client: Client,
+  url: Url,
+  signer: Option<SigningKey>,
+}
+
+impl HttpTransport {
+  /// `url` must be an `http://` or `https://` endpoint.
+  pub fn new(url: &str, signer: Option<SigningKey>) -> Result<Self, String> {
+    let url = Url::parse(url).map_err(|error| format!("invalid RPC URL {url:?}: {error}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+      return Err(format!("unsupported RPC URL {url}: only http:// and https:// are supported"));
+    }
+    let client = Client::builder()
+      .timeout(Duration::from_secs(30))
+      .build()
+      .map_err(|error| error.to_string())?;
+    Ok(HttpTransport { client, url, signer })
+  }
+
+  pub fn request(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string();
+    let response = self
+      .client
+      .post(self.url.clone())
+      .header(CONTENT_TYPE, "application/json")
+      .body(body)
+      .send()?;
+    let status = response.status();
+    if !status.is_success() {
+      return Err(RpcError::Http(status.as_u16()));
+    }
+    let body = response.text()?;
+    let mut reply: Value = serde_json::from_str(&body).map_err(|error| RpcError::Malformed(error.to_string()))?;
+    if let Some(error) = reply.get("error") {
+      return Err(RpcError::Rpc {
+        code: error["code"].as_i64().unwrap_or_default(),
+        message: error["message"].as_str().unwrap_or_default().to_string(),
+        data: error["data"].as_str().and_then(|data| data.parse().ok()),
+      });
+    }
+    Ok(reply["result"].take())
+  }
+
+  pub fn receipt_status(&self, tx_hash: B256) -> Result<Option<bool>, RpcError> {
+    let receipt = self.request("eth_getTransactionReceipt", json!([tx_hash]))?;
+    if receipt.is_null() {
+      return Ok(None);
+    }
+    Ok(Some(quantity(&receipt["status"])? == 1))
+  }
+
+  fn signer(&self) -> Result<&SigningKey, RpcError> {
+    self
+      .signer
+      .as_ref()
+      .ok_or_else(|| RpcError::Malformed(String::from("no signing key configured")))
+  }
+}
+
+impl Transport for HttpTransport {
+  type Error = RpcError;
+
+  fn call(&self, to: Address, input: Bytes) -> Result<Bytes, RpcError> {
+    let output = self.request("eth_call", json!([{ "to": to, "data": input }, "latest"]))?;
+    output
+      .as_str()
+      .and_then(|output| output.parse().ok())
+      .ok_or_else(|| RpcError::Malformed(String::from("eth_call result")))
+  }
+
+  fn send(&self, to: Address, input: Bytes, value: U256) -> Result<B256, RpcError> {
+    let signer = self.signer()?;
+    let from = signer_address(signer);
+    let chain_id = quantity(&self.request("eth_chainId", json!([]))?)?;
+    let nonce = quantity(&self.request("eth_getTransactionCount", json!([from, "pending"]))?)?;
+    let gas_price = quantity(&self.request("eth_gasPrice", json!([]))?)?;
+    let gas = quantity(&self.request(
+      "eth_estimateGas",
+      json!([{ "from": from, "to": to, "data": input, "value": value }]),
+    )?)?;
+
+    let tx = Eip1559 {
+      chain_id,
+      nonce,
+      max_priority_fee_per_gas: 0,
+      max_fee_per_gas: u128::from(gas_price) * 2,
+      gas_limit: gas + gas / 5,
+      to,
+      value,
+      input,
+    };
+    let raw = tx.sign(signer).map_err(RpcError::Malformed)?;
+    let tx_hash = self.request("eth_sendRawTransaction", json!([raw]))?;
+    tx_hash
+      .as_str()
+      .and_then(|tx_hash| tx_hash.parse().ok())
+      .ok_or_else(|| RpcError::Malformed(String::from("transaction hash")))
+  }
+
+  /// Fetches the range in `LOG_RANGE`-block pages, oldest first.
+  fn logs(&self, address: Address, from_block: u64, to_block: u64) -> Result<Vec<RawLog>, RpcError> {
+    let mut logs = Vec::new();
+    let mut start = from_block;
+    while start <= to_block {
+      let end = to_block.min(start.saturating_add(LOG_RANGE - 1));
+      let page = self.request(
+        "eth_getLogs",
+        json!([{
+          "address": address,
+          "fromBlock": format!("{start:#x}"),
+          "toBlock": format!("{end:#x}"),
+        }]),
+      )?;
+      let page = page
+        .as_array()
+        .ok_or_else(|| RpcError::Malformed(String::from("eth_getLogs result")))?;
+      for log in page {
+        logs.push(raw_log(log)?);
+      }
+      if end == u64::MAX {
+        break;
+      }
+      start = end + 1;
+    }
+    Ok(logs)
+  }
+
+  fn block_number(&self) -> Result<u64, RpcError> {
+    quantity(&self.request("eth_blockNumber", json!([]))?)
+  }
+
+  fn revert_data(error: &RpcError) -> Option<&[u8]> {
+    match error {
+      RpcError::Rpc { data: Some(data), .. } => Some(data),
+      _ => None,
+    }
+  }
+
+  fn is_transient(error: &RpcError) -> bool {
+    match error {
+      RpcError::Transport(_) => true,
+      RpcError::Http(status) => *status == 429 || *status >= 500,
+      _ => false,
+    }
+  }
+}
+
+pub fn signer_address(signer: &SigningKey) -> Address {
+  let point = signer.verifying_key().to_encoded_point(false);
+  Address::from_slice(&keccak256(&point.as_bytes()[1..])[12..])
+}
+
+/// alloy's `keccak256` is bound to the Stylus host function in this workspace,
+/// so hashing on the host goes through `sha3`.
+fn keccak256(data: &[u8]) -> B256 {
+  B256::from_slice(&Keccak256::digest(data))
+}
+
+fn quantity(value: &Value) -> Result<u64, RpcError> {
+  value
+    .as_str()
+    .and_then(|value| u64::from_str_radix(value.trim_start_matches("0x"), 16).ok())
+    .ok_or_else(|| RpcError::Malformed(format!("expected a quantity, got {value}")))
+}
+
+fn raw_log(log: &Value) -> Result<RawLog, RpcError> {
+  let malformed = || RpcError::Malformed(String::from("log entry"));
+  let address = log["address"].as_str().and_then(|a| a.parse().ok()).ok_or_else(malformed)?;
+  let topics = log["topics"]
+    .as_array()
+    .ok_or_else(malformed)?
+    .iter()
+    .map(|topic| topic.as_str().and_then(|t| t.parse().ok()).ok_or_else(malformed))
+    .collect::<Result<Vec<B256>, _>>()?;
+  let data: Bytes = log["data"].as_str().and_then(|d| d.parse().ok()).ok_or_else(malformed)?;
+  Ok(RawLog {
+    block_number: quantity(&log["blockNumber"])?,
+    log: Log::new_unchecked(address, topics, data),
+  })
+}
+
+/// An EIP-1559 (type 2) transaction with an empty access list.
+struct Eip1559 {
+  chain_id: u64,
+  nonce: u64,
+  max_priority_fee_per_gas: u128,
+  max_fee_per_gas: u128,
+  gas_limit: u64,
+  to: Address,
+  value: U256,
+  input: Bytes,
+}
+
+/// RLP encoding of an empty list, for the access list.
+struct EmptyList;
+
+impl Encodable for EmptyList {
+  fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+    Header {
+      list: true,
+      payload_length: 0,
+    }
+    .encode(out);
+  }
+}
+
+impl Eip1559 {
+  /// Returns the signed transaction as `0x02 || rlp(...)`.
+  fn sign(&self, signer: &SigningKey) -> Result<Bytes, String> {
+    let digest = keccak256(&self.encode(None));
+    let (signature, recovery_id) = signer
+      .sign_prehash_recoverable(digest.as_slice())
+      .map_err(|error| error.to_string())?;
+    let r = U256::from_be_slice(&signature.r().to_bytes());
+    let s = U256::from_be_slice(&signature.s().to_bytes());
+    Ok(Bytes::from(self.encode(Some((recovery_id.is_y_odd(), r, s)))))
+  }
+
+  fn encode(&self, signature: Option<(bool, U256, U256)>) -> Vec<u8> {
+    let mut fields: Vec<&dyn Encodable> = vec![
+      &self.chain_id,
+      &self.nonce,
+      &self.max_priority_fee_per_gas,
+      &self.max_fee_per_gas,
+      &self.gas_limit,
+      &self.to,
+      &self.value,
+      &self.input,
+      &EmptyList,
+    ];
+    if let Some((y_parity, r, s)) = &signature {
+      fields.extend([y_parity as &dyn Encodable, r, s]);
+    }
+
+    let payload_length = fields.iter().map(|field| field.length()).sum();
+    let mut out = vec![0x02];
+    Header {
+      list: true,
+      payload_length,
+    }
+    .encode(&mut out);
+    for field in fields {
+      field.encode(&mut out);
+    }
+    out
+  }
+}
@@ -0,0 +1,861 @@
+/**
+ * This file was automatically generated by Stylus and represents a Rust program.
+ * For more information, please see [The Stylus SDK](https://github.com/OffchainLabs/stylus-sdk-rs).
+ */
+
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.23;
+
+interface IRegistry  {
+    function init() external;
+
+    function owner() external view returns (address);
+
+    function pendingOwner() external view returns (address);
+
+    function transferOwnership(address new_owner) external;
+
+    function acceptOwnership() external;
+
+    function paused() external view returns (bool);
+
+    function pause() external;
+
+    function unpause() external;
+
+    function guardian() external view returns (address);
+
+    function setGuardian(address guardian) external;
+
+    function getRecoveryPolicy() external view returns (uint32, uint64, uint32);
+
+    function setRecoveryGuardian(address guardian, bool enabled) external;
+
+    function setRecoveryPolicy(uint32 threshold, uint64 delay) external;
+
+    function getRecovery() external view returns (address, uint32, uint64);
+
+    function proposeRecovery(address new_owner) external;
+
+    function approveRecovery() external;
+
+    function executeRecovery() external;
+
+    function cancelRecovery() external;
+
+    function registerTask(string calldata task_id) external payable;
+
+    function registerTaskWithCategories(string calldata task_id, string[] memory categories) external payable;
+
+    function registerTasks(string[] memory task_ids) external payable;
+
+    function registerTasksWithHashes((string,bytes32)[] memory entries) external payable;
+
+    function taskCount() external view returns (uint64);
+
+    function taskIds(uint64 offset, uint64 limit) external view returns (string[] memory);
+
+    function allTaskIds(uint64 offset, uint64 limit) external view returns (string[] memory);
+
+    function publishNewVersion(string calldata task_id, bytes32 hash) external payable;
+
+    function publishNewVersionWithAuthorSig(string calldata task_id, bytes32 hash, bytes calldata author_signature) external payable;
+
+    function getReleaseDigest(string calldata task_id, bytes32 hash) external view returns (bytes32);
+
+    function getAuthor(string calldata task_id, uint64 version) external view returns (address);
+
+    function publishNewVersionWithDelta(string calldata task_id, bytes32 hash, bytes32 delta_hash) external payable;
+
+    function getPrevRecordHash(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function getRecordHash(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function getDeltaHash(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function getDeltaChain(string calldata task_id, uint64 version) external view returns (uint64, bytes32[] memory);
+
+    function publishNewVersionWithUri(string calldata task_id, bytes32 hash, string calldata artifact_uri) external payable;
+
+    function publishNewVersionWithAlgorithm(string calldata task_id, bytes32 hash, uint8 hash_algorithm, string calldata artifact_uri) external payable;
+
+    function publishNewVersionWithMetadata(string calldata task_id, bytes32 hash, bytes32 config_hash, string calldata parent_task_id, uint64 parent_version, (string,uint64)[] memory datasets) external payable;
+
+    function publishNewVersionWithLicense(string calldata task_id, bytes32 hash, string calldata license, bool commercial_use, bool redistribution, bool fine_tuning) external payable;
+
+    function publishNewVersionWithDependencies(string calldata task_id, bytes32 hash, (string,uint64)[] memory dependencies) external payable;
+
+    function publishNewVersionSemver(string calldata task_id, bytes32 hash, uint32 major, uint32 minor, uint32 patch) external payable;
+
+    function publishNewVersions((string,bytes32)[] memory entries) external payable;
+
+    function commitVersion(string calldata task_id, bytes32 commitment) external;
+
+    function revealVersion(string calldata task_id, bytes32 hash, bytes32 salt) external payable;
+
+    function taskAdmin(string calldata task_id) external view returns (address);
+
+    function isPublisher(string calldata task_id, address account) external view returns (bool);
+
+    function setTaskAdmin(string calldata task_id, address new_admin) external;
+
+    function grantPublisher(string calldata task_id, address publisher) external;
+
+    function revokePublisher(string calldata task_id, address publisher) external;
+
+    function getLatest(string calldata task_id) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getLatestFresh(string calldata task_id) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getLatestWithStaleness(string calldata task_id) external view returns ((uint64, bytes32, uint64, address, uint64, string, uint8, bytes32), bool);
+
+    function getLatestMany(string[] memory task_ids) external view returns ((uint64,bytes32,uint64,string)[] memory);
+
+    function getVersion(string calldata task_id, uint64 version) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getVersionsRange(string calldata task_id, uint64 from_version, uint64 limit) external view returns ((uint64,bytes32,uint64,address,uint64,string,uint8,bytes32)[] memory);
+
+    function lookupHash(bytes32 hash) external view returns (string memory, uint64);
+
+    function getVersionCount(string calldata task_id) external view returns (uint64);
+
+    function yankVersion(string calldata task_id, uint64 version, string calldata reason, uint64 successor_version) external;
+
+    function getYankStatus(string calldata task_id, uint64 version) external view returns (bool, string memory, uint64);
+
+    function deprecateVersion(string calldata task_id, uint64 version, bytes32 reason_hash, uint64 sunset_at) external;
+
+    function getDeprecation(string calldata task_id, uint64 version) external view returns (bool, bytes32, uint64);
+
+    function getLatestUnyanked(string calldata task_id) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getActivationDelay(string calldata task_id) external view returns (uint64);
+
+    function setActivationDelay(string calldata task_id, uint64 activation_delay) external;
+
+    function getActivationStatus(string calldata task_id, uint64 version) external view returns (uint64, bool);
+
+    function cancelPendingVersion(string calldata task_id, uint64 version) external;
+
+    function rollbackLatest(string calldata task_id, uint64 to_version) external;
+
+    function setTag(string calldata task_id, string calldata tag, uint64 version) external;
+
+    function getByTag(string calldata task_id, string calldata tag) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function isFrozen(string calldata task_id) external view returns (bool);
+
+    function freezeTask(string calldata task_id) external;
+
+    function getArchiveStatus(string calldata task_id) external view returns (bool, string memory);
+
+    function archiveTask(string calldata task_id, string calldata reason) external;
+
+    function unarchiveTask(string calldata task_id) external;
+
+    function fees() external view returns (uint256, uint256);
+
+    function collectedFees() external view returns (uint256);
+
+    function setFee(uint256 registration_fee, uint256 publish_fee) external;
+
+    function withdrawFees(address to) external;
+
+    function getGating(string calldata task_id) external view returns (bool, uint256);
+
+    function setGating(string calldata task_id, bool gated, uint256 price_per_second) external;
+
+    function subscribe(string calldata task_id, uint64 duration) external payable;
+
+    function subscriptionExpiry(string calldata task_id, address account) external view returns (uint64);
+
+    function isSubscriber(string calldata task_id, address account) external view returns (bool);
+
+    function issueCapability(string calldata task_id, address consumer, uint64 expires_at) external;
+
+    function revokeCapability(string calldata task_id, address consumer) external;
+
+    function hasCapability(string calldata task_id, address consumer) external view returns (bool);
+
+    function capabilityExpiry(string calldata task_id, address consumer) external view returns (uint64);
+
+    function subscriptionRevenue(string calldata task_id) external view returns (uint256);
+
+    function withdrawSubscriptionRevenue(string calldata task_id, address to) external;
+
+    function publishNonce(string calldata task_id) external view returns (uint256);
+
+    function publishWithSig(string calldata task_id, bytes32 hash, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external payable;
+
+    function publishWithSignerSig(string calldata task_id, bytes32 hash, address signer, uint256 deadline, bytes calldata signature) external payable;
+
+    function setGroth16Key(string calldata task_id, uint64 version, bytes calldata vk) external;
+
+    function getGroth16Key(string calldata task_id, uint64 version) external view returns (bytes memory);
+
+    function verifyInference(string calldata task_id, uint64 version, bytes calldata proof, uint256[] memory public_inputs) external returns (bool);
+
+    function getExternalVerifier(string calldata task_id) external view returns (address);
+
+    function setExternalVerifier(string calldata task_id, address verifier) external;
+
+    function verifyCommittedInference(string calldata task_id, uint64 version, bytes calldata proof, uint256[] memory public_inputs) external returns (bool);
+
+    function poseidonCommitment(uint256[] memory inputs) external view returns (bytes32);
+
+    function isInferenceVerified(string calldata task_id, uint64 version, bytes32 inference_id) external view returns (bool);
+
+    function verifiedInferenceCount(string calldata task_id, uint64 version) external view returns (uint64);
+
+    function setVerificationKey(string calldata task_id, uint64 version, bytes32 vk_hash, string calldata vk_uri) external;
+
+    function getVerificationKey(string calldata task_id, uint64 version) external view returns (bytes32, string memory);
+
+    function attestationCount(string calldata task_id, uint64 version) external view returns (uint64);
+
+    function getAttestations(string calldata task_id, uint64 version, uint64 offset, uint64 limit) external view returns ((bytes32,bytes32,address,uint64)[] memory);
+
+    function verifierRouter() external view returns (address);
+
+    function setVerifierRouter(address router) external;
+
+    function trustedForwarder() external view returns (address);
+
+    function isTrustedForwarder(address forwarder) external view returns (bool);
+
+    function setTrustedForwarder(address forwarder) external;
+
+    function setImageId(string calldata task_id, uint64 version, bytes32 image_id) external;
+
+    function getImageId(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function verifyReceipt(string calldata task_id, uint64 version, bytes32 image_id, bytes calldata journal, bytes calldata seal) external returns (bool);
+
+    function isJournalVerified(string calldata task_id, uint64 version, bytes32 journal_digest) external view returns (bool);
+
+    function setAggregationKey(string calldata task_id, uint64 version, bytes calldata vk) external;
+
+    function verifyBatch(string calldata task_id, uint64 version, bytes calldata proof, bytes32 batch_root, uint64 batch_size) external returns (bool);
+
+    function batchSize(string calldata task_id, uint64 version, bytes32 batch_root) external view returns (uint64);
+
+    function isBatchMember(string calldata task_id, uint64 version, bytes32 batch_root, bytes32 input_commitment, bytes32 output_commitment, bytes32[] memory proof) external view returns (bool);
+
+    function getChallengePeriod(string calldata task_id) external view returns (uint64);
+
+    function setChallengePeriod(string calldata task_id, uint64 challenge_period) external;
+
+    function challengeBond() external view returns (uint256);
+
+    function setChallengeBond(uint256 challenge_bond) external;
+
+    function arbiter() external view returns (address);
+
+    function setArbiter(address arbiter) external;
+
+    function challengeVersion(string calldata task_id, uint64 version, bytes32 evidence_hash) external payable;
+
+    function resolveChallenge(string calldata task_id, uint64 version, bool upheld) external;
+
+    function getRefund(string calldata task_id, uint64 version, address consumer) external view returns (uint256, uint256);
+
+    function claimRefund(string calldata task_id, uint64 version) external returns (uint256);
+
+    function getChallenge(string calldata task_id, uint64 version) external view returns (uint8, address, uint256, bytes32, uint64);
+
+    function staking() external view returns (uint256, uint64);
+
+    function setStaking(uint256 min_stake, uint64 unstake_cooldown) external;
+
+    function getStake(address account) external view returns (uint256, uint64);
+
+    function stake() external payable;
+
+    function requestUnstake() external;
+
+    function unstake() external;
+
+    function isAuditor(address account) external view returns (bool);
+
+    function grantAuditor(address auditor) external;
+
+    function revokeAuditor(address auditor) external;
+
+    function attest(string calldata task_id, uint64 version, string calldata attestation_type, bytes32 report_hash) external;
+
+    function getAudits(string calldata task_id, uint64 version) external view returns ((address,string,bytes32,uint64)[] memory);
+
+    function reputationOf(address account) external view returns (uint64, uint64, uint64, uint64, uint64);
+
+    function setProfile(bytes32 display_name_hash, string calldata did, bytes32 website_hash) external;
+
+    function clearProfile() external;
+
+    function getProfile(address account) external view returns (bytes32, string memory, bytes32, uint64);
+
+    function getPublisherProfile(string calldata task_id, uint64 version) external view returns (address, bytes32, string memory, bytes32);
+
+    function getLineage(string calldata task_id, uint64 version) external view returns ((string,uint64)[] memory);
+
+    function commitDataset(string calldata dataset_id, bytes32 hash) external returns (uint64);
+
+    function getDatasetInfo(string calldata dataset_id) external view returns (address, uint64);
+
+    function getDatasetHash(string calldata dataset_id, uint64 revision) external view returns (bytes32);
+
+    function getTrainingInputs(string calldata task_id, uint64 version) external view returns ((string,uint64,bytes32)[] memory);
+
+    function isEvaluator(string calldata task_id, address account) external view returns (bool);
+
+    function grantEvaluator(string calldata task_id, address evaluator) external;
+
+    function revokeEvaluator(string calldata task_id, address evaluator) external;
+
+    function recordMetrics(string calldata task_id, uint64 version, string[] memory metric_keys, uint256[] memory metric_values) external;
+
+    function getMetric(string calldata task_id, uint64 version, string calldata key) external view returns (uint256);
+
+    function getMetrics(string calldata task_id, uint64 version) external view returns ((string,uint256)[] memory);
+
+    function setModelCard(string calldata task_id, uint64 version, uint8 framework, uint64 parameter_count, bytes32 io_schema_hash, uint16 license_code) external;
+
+    function getModelCard(string calldata task_id, uint64 version) external view returns (uint8, uint64, bytes32, uint16);
+
+    function ownerOfTask(string calldata task_id) external view returns (address);
+
+    function approveTask(string calldata task_id, address approved) external;
+
+    function getApprovedTask(string calldata task_id) external view returns (address);
+
+    function setApprovalForAll(address operator, bool approved) external;
+
+    function isApprovedForAll(address owner, address operator) external view returns (bool);
+
+    function transferTask(string calldata task_id, address new_owner) external;
+
+    function tokenFees() external view returns (address, uint256, uint256);
+
+    function setPaymentToken(address token, uint256 registration_fee, uint256 publish_fee) external;
+
+    function collectedTokenFees(address token) external view returns (uint256);
+
+    function withdrawTokenFees(address token, address to) external;
+
+    function getTokenSubscriptionPrice(string calldata task_id) external view returns (uint256);
+
+    function setTokenSubscriptionPrice(string calldata task_id, uint256 price_per_second) external;
+
+    function tokenSubscriptionRevenue(string calldata task_id, address token) external view returns (uint256);
+
+    function withdrawTokenSubscriptionRevenue(string calldata task_id, address token, address to) external;
+
+    function getRoyalty(string calldata task_id) external view returns (string memory, uint16);
+
+    function getRoyaltyOffer(string calldata task_id) external view returns (string memory, uint16);
+
+    function maxRoyaltyBps() external view returns (uint16);
+
+    function setMaxRoyaltyBps(uint16 max_royalty_bps) external;
+
+    function setRoyalty(string calldata task_id, uint64 version, uint16 royalty_bps) external;
+
+    function acceptRoyalty(string calldata task_id, uint16 royalty_bps) external;
+
+    function jobTimeout() external view returns (uint64);
+
+    function setJobTimeout(uint64 job_timeout) external;
+
+    function getInferencePrice(string calldata task_id, uint64 version) external view returns (uint256);
+
+    function setInferencePrice(string calldata task_id, uint64 version, uint256 price) external;
+
+    function requestInference(string calldata task_id, uint64 version, bytes32 input_commitment) external payable returns (uint64);
+
+    function fulfillInference(uint64 job_id, bytes32 output_commitment, bytes calldata proof, uint256[] memory public_inputs) external returns (bool);
+
+    function refundInference(uint64 job_id) external;
+
+    function jobCount() external view returns (uint64);
+
+    function getJob(uint64 job_id) external view returns (string memory, uint64, address, bytes32, uint256, uint64, uint8, address, bytes32);
+
+    function isOracle(address account) external view returns (bool);
+
+    function grantOracle(address oracle) external;
+
+    function revokeOracle(address oracle) external;
+
+    function requestEvaluation(string calldata task_id, uint64 version, bytes32 suite_hash) external returns (uint64);
+
+    function fulfillEvaluation(string calldata task_id, uint64 version, uint64 request_id, bytes32 results_hash) external;
+
+    function evaluationCount() external view returns (uint64);
+
+    function getEvaluation(uint64 request_id) external view returns (string memory, uint64, address, bytes32, bool, address, bytes32, uint64);
+
+    function versionRoot() external view returns (bytes32);
+
+    function l1CheckpointTarget() external view returns (address);
+
+    function setL1CheckpointTarget(address target) external;
+
+    function checkpointToL1() external returns (bytes32);
+
+    function lastCheckpoint() external view returns (bytes32, uint64);
+
+    function l1Controller() external view returns (address);
+
+    function setL1Controller(address controller) external;
+
+    function registerTaskFromL1(string calldata task_id) external;
+
+    function publishFromL1(string calldata task_id, bytes32 hash, string calldata artifact_uri) external;
+
+    function multicall(bytes[] memory calls) external returns (bytes[] memory);
+
+    function taskIdOf(string calldata task_id) external view returns (bytes32);
+
+    function taskNameOf(bytes32 id) external view returns (string memory);
+
+    function publishNewVersionById(bytes32 id, bytes32 hash) external payable;
+
+    function getLatestById(bytes32 id) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getVersionById(bytes32 id, uint64 version) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getVersionCountById(bytes32 id) external view returns (uint64);
+
+    function namespaceAdmin(string calldata namespace) external view returns (address);
+
+    function setNamespaceAdmin(string calldata namespace, address admin) external;
+
+    function tasksInNamespace(string calldata namespace, uint64 offset, uint64 limit) external view returns (string[] memory);
+
+    function addAlias(string calldata _alias, string calldata task_id) external;
+
+    function resolveTaskId(string calldata task_id) external view returns (string memory);
+
+    function isStrictMode(string calldata task_id) external view returns (bool);
+
+    function setStrictMode(string calldata task_id, bool enabled) external;
+
+    function getVersionAt(string calldata task_id, uint64 timestamp) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function totalTasks() external view returns (uint64);
+
+    function totalVersions() external view returns (uint64);
+
+    function versionsOf(string calldata task_id) external view returns (uint64);
+
+    function lastActivity(string calldata task_id) external view returns (uint64);
+
+    function getSemver(string calldata task_id, uint64 version) external view returns (uint32, uint32, uint32);
+
+    function getLatestInMajor(string calldata task_id, uint32 major) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getMaxAge(string calldata task_id) external view returns (uint64);
+
+    function setMaxAge(string calldata task_id, uint64 max_age) external;
+
+    function getPublishCooldown(string calldata task_id) external view returns (uint64);
+
+    function setPublishCooldown(string calldata task_id, uint64 cooldown) external;
+
+    function pin(string calldata task_id, uint64 version) external;
+
+    function unpin(string calldata task_id) external;
+
+    function getPin(string calldata task_id, address consumer) external view returns (uint64);
+
+    function getPinCount(string calldata task_id, uint64 version) external view returns (uint64);
+
+    function getPinners(string calldata task_id, uint64 version, uint64 offset, uint64 limit) external view returns (address[] memory);
+
+    function setEncryptedArtifact(string calldata task_id, uint64 version, string calldata encrypted_uri, bytes32 key_commitment) external;
+
+    function getEncryptedArtifact(string calldata task_id, uint64 version) external view returns (string memory, bytes32);
+
+    function grantAccess(string calldata task_id, uint64 version, address consumer, bytes32 wrapped_key_hash) external;
+
+    function getAccessGrant(string calldata task_id, uint64 version, address consumer) external view returns (bytes32);
+
+    function contractVersion() external view returns (string memory);
+
+    function supportsInterface(bytes4 interface_id) external view returns (bool);
+
+    function exportState(uint64 cursor, uint64 limit) external view returns (bytes memory, uint64);
+
+    function importState(bytes calldata chunk) external;
+
+    function closeImport() external;
+
+    function isApprover(string calldata task_id, address account) external view returns (bool);
+
+    function grantApprover(string calldata task_id, address approver) external;
+
+    function revokeApprover(string calldata task_id, address approver) external;
+
+    function getApprovalPolicy(string calldata task_id) external view returns (uint32, uint64, uint32);
+
+    function setApprovalPolicy(string calldata task_id, uint32 threshold, uint64 proposal_ttl) external;
+
+    function proposeVersion(string calldata task_id, bytes32 hash, string calldata artifact_uri) external payable returns (uint64);
+
+    function approveVersion(string calldata task_id, uint64 proposal_id) external;
+
+    function getProposal(string calldata task_id, uint64 proposal_id) external view returns (address, bytes32, uint64, uint32, uint64);
+
+    function nonceOf(string calldata task_id, address account) external view returns (uint64);
+
+    function getVersionTreeRoot(string calldata task_id) external view returns (bytes32);
+
+    function verifyVersionProof(string calldata task_id, uint64 version, bytes32 hash, bytes32[] memory proof) external view returns (bool);
+
+    function stateRoot() external view returns (bytes32);
+
+    function recordCheckpoint() external returns (uint64);
+
+    function checkpointCount() external view returns (uint64);
+
+    function getCheckpoint(uint64 index) external view returns (bytes32, uint64, uint64, uint64);
+
+    function checkpointInterval() external view returns (uint64);
+
+    function setCheckpointInterval(uint64 interval) external;
+
+    function addCategory(string calldata category) external;
+
+    function retireCategory(string calldata category) external;
+
+    function categories() external view returns (string[] memory);
+
+    function assignCategory(string calldata task_id, string calldata category) external;
+
+    function getTaskCategories(string calldata task_id) external view returns (string[] memory);
+
+    function tasksByCategory(string calldata category, uint64 offset, uint64 limit) external view returns (string[] memory);
+
+    function getAuditLogLength(string calldata task_id) external view returns (uint64);
+
+    function getAuditLog(string calldata task_id, uint64 offset, uint64 limit) external view returns ((uint8,address,uint64,address,uint64)[] memory);
+
+    function openRound(string calldata task_id) external returns (uint64);
+
+    function isRoundClient(string calldata task_id, address account) external view returns (bool);
+
+    function grantRoundClient(string calldata task_id, address client) external;
+
+    function revokeRoundClient(string calldata task_id, address client) external;
+
+    function submitUpdateCommitment(string calldata task_id, uint64 round_id, bytes32 commitment) external;
+
+    function finalizeRound(string calldata task_id, bytes32 aggregate_hash, bytes32 proof_hash) external returns (uint64);
+
+    function getRoundCount(string calldata task_id) external view returns (uint64);
+
+    function getRound(string calldata task_id, uint64 round_id) external view returns (uint64, uint64, uint64, bytes32, bytes32);
+
+    function getUpdateCommitment(string calldata task_id, uint64 round_id, address client) external view returns (bytes32);
+
+    function getCircuitBreaker(string calldata task_id) external view returns (uint32, uint64, bool);
+
+    function setCircuitBreaker(string calldata task_id, uint32 threshold, uint64 window, bool halt_task) external;
+
+    function getBreakerState(string calldata task_id, uint64 version) external view returns (uint32, uint64, bool, bool);
+
+    function resetCircuitBreaker(string calldata task_id, uint64 version) external;
+
+    function getAdversarialKey(string calldata task_id) external view returns (bytes memory);
+
+    function setAdversarialKey(string calldata task_id, bytes calldata vk) external;
+
+    function fundBounty(string calldata task_id, uint64 version, uint64 deadline) external payable;
+
+    function refundBounty(string calldata task_id, uint64 version) external returns (uint256);
+
+    function submitAdversarialExample(string calldata task_id, uint64 version, bytes32 input_commitment, uint256 label, uint256 prediction, bytes calldata proof) external returns (bool);
+
+    function getBounty(string calldata task_id, uint64 version) external view returns (uint256, uint64, address, bytes32);
+
+    function getTrainingKey(string calldata task_id) external view returns (bytes memory);
+
+    function setTrainingKey(string calldata task_id, bytes calldata vk) external;
+
+    function submitTrainingProof(string calldata task_id, uint64 version, bytes calldata proof, uint256[] memory public_inputs) external returns (bool);
+
+    function getTrainingCommitment(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function getTrainingStatus(string calldata task_id, uint64 version) external view returns (bool, bytes32);
+
+    function getDependencies(string calldata task_id, uint64 version) external view returns ((string,uint64)[] memory);
+
+    function getDependents(string calldata task_id, uint64 version, uint64 offset, uint64 limit) external view returns ((string,uint64)[] memory);
+
+    function licenseOf(string calldata task_id, uint64 version) external view returns (string memory, bool, bool, bool);
+
+    function ratingDeposit() external view returns (uint256);
+
+    function setRatingDeposit(uint256 rating_deposit) external;
+
+    function rateVersion(string calldata task_id, uint64 version, uint8 score) external payable;
+
+    function withdrawRating(string calldata task_id, uint64 version) external;
+
+    function getRating(string calldata task_id, uint64 version) external view returns (uint64, uint64);
+
+    function getRatingOf(string calldata task_id, uint64 version, address rater) external view returns (uint8, uint256);
+
+    function keeperBounty() external view returns (uint256);
+
+    function setKeeperBounty(uint256 keeper_bounty) external;
+
+    function pokeInference(uint64 job_id) external returns (uint256);
+
+    function pokeSubscription(string calldata task_id, address subscriber) external returns (uint256);
+
+    function getHooks(string calldata task_id) external view returns ((address,uint64)[] memory);
+
+    function addHook(string calldata task_id, address hook, uint64 gas_limit) external;
+
+    function removeHook(string calldata task_id, address hook) external;
+
+    function getPrunedHistory(string calldata task_id) external view returns (uint64, bytes32);
+
+    function pruneVersions(string calldata task_id, uint64 keep_last_n) external returns (uint64);
+
+    error TaskNotFound(string);
+
+    error TaskExists(string);
+
+    error NoVersions(string);
+
+    error VersionNotFound(string, uint64);
+
+    error VersionAlreadyYanked(string, uint64);
+
+    error InvalidSuccessor(string, uint64);
+
+    error HashNotFound(bytes32);
+
+    error UnsupportedHashAlgorithm(uint8);
+
+    error CommitmentExists(string, bytes32);
+
+    error UnknownCommitment(string, bytes32);
+
+    error RevealTooEarly(string, bytes32);
+
+    error VersionNotPending(string, uint64);
+
+    error InvalidRollbackTarget(string, uint64);
+
+    error TagNotFound(string, string);
+
+    error TaskIsFrozen(string);
+
+    error TaskIsArchived(string);
+
+    error TaskNotArchived(string);
+
+    error InsufficientFee(uint256, uint256);
+
+    error TransferFailed(address, uint256);
+
+    error TaskNotGated(string);
+
+    error SignatureExpired(uint256);
+
+    error InvalidSignature();
+
+    error AlreadyInitialized();
+
+    error InvalidVerificationKey(string, uint64);
+
+    error VerificationKeyAlreadySet(string, uint64);
+
+    error NoVerificationKey(string, uint64);
+
+    error ImageIdMismatch(string, uint64, bytes32);
+
+    error NoVerifierRouter();
+
+    error ChallengeWindowClosed(string, uint64);
+
+    error ChallengeAlreadyOpen(string, uint64);
+
+    error NoOpenChallenge(string, uint64);
+
+    error InsufficientStake(address, uint256, uint256);
+
+    error UnstakeNotReady(uint64);
+
+    error NotAuditor(address);
+
+    error DatasetNotFound(string, uint64);
+
+    error NotDatasetOwner(string, address);
+
+    error NotEvaluator(string, address);
+
+    error MetricsLengthMismatch(uint256, uint256);
+
+    error MetricNotFound(string, uint64, string);
+
+    error UnsupportedFramework(uint8);
+
+    error NotTaskApproved(string, address);
+
+    error InvalidRecipient(address);
+
+    error TokenTransferFailed(address, address, uint256);
+
+    error InvalidRoyalty(string, string, uint16);
+
+    error JobNotFound(uint64);
+
+    error JobNotOpen(uint64);
+
+    error JobExpired(uint64, uint64);
+
+    error JobNotExpired(uint64, uint64);
+
+    error InvalidJobFulfillment(uint64);
+
+    error NotOracle(address);
+
+    error EvaluationRequestNotFound(uint64);
+
+    error EvaluationAlreadyFulfilled(uint64);
+
+    error NoCheckpointTarget();
+
+    error CheckpointFailed();
+
+    error NotL1Controller(address);
+
+    error TaskIdNotFound(bytes32);
+
+    error NotNamespaceAdmin(string, address);
+
+    error AliasExists(string);
+
+    error DuplicateHash(string, bytes32);
+
+    error NoVersionAt(string, uint64);
+
+    error SemverNotIncreasing(string, uint32, uint32, uint32);
+
+    error NoVersionInMajor(string, uint32);
+
+    error StaleVersion(string, uint64, uint64, uint64);
+
+    error NotEncrypted(string, uint64);
+
+    error EncryptionAlreadySet(string, uint64);
+
+    error InvalidStateChunk();
+
+    error ImportClosed();
+
+    error ApprovalRequired(string);
+
+    error NotApprover(string, address);
+
+    error InvalidThreshold(string, uint32, uint32);
+
+    error ProposalNotFound(string, uint64);
+
+    error ProposalClosed(string, uint64);
+
+    error AlreadyApproved(string, uint64, address);
+
+    error InvalidFieldElement(uint256);
+
+    error NotPoseidonCommitted(string, uint64);
+
+    error CheckpointTooSoon(uint64);
+
+    error CheckpointNotFound(uint64);
+
+    error PublishCooldown(string, uint64);
+
+    error UnknownCategory(string);
+
+    error CategoryExists(string);
+
+    error CategoryAlreadyAssigned(string, string);
+
+    error InvalidSunset(uint64);
+
+    error RoundNotFound(string, uint64);
+
+    error RoundStillOpen(string, uint64);
+
+    error RoundClosed(string, uint64);
+
+    error UpdateAlreadySubmitted(string, uint64, address);
+
+    error NoRefundDue(string, uint64, address);
+
+    error CircuitBreakerOpen(string, uint64);
+
+    error InvalidRating(uint8);
+
+    error AlreadyRated(string, uint64, address);
+
+    error NotRated(string, uint64, address);
+
+    error InvalidExpiry(uint64);
+
+    error InvalidDuration(uint64);
+
+    error SubscriptionNotExpired(string, address);
+
+    error BountyClosed(string, uint64);
+
+    error NotMisclassified(uint256, uint256);
+
+    error TooManyHooks(string);
+
+    error HookNotFound(string, address);
+
+    error InvalidHookGas(uint64);
+
+    error NotRecoveryGuardian(address);
+
+    error InvalidRecoveryThreshold(uint32, uint32);
+
+    error RecoveryPending(address);
+
+    error NoRecoveryPending();
+
+    error RecoveryAlreadyApproved(address);
+
+    error RecoveryNotReady(uint64);
+
+    error NoTrainingData(string, uint64);
+
+    error BountyKeyFrozen(string);
+
+    error BountyNotExpired(string, uint64, uint64);
+
+    error InvalidBreakerWindow(uint32, uint64);
+
+    error NotRoundClient(string, address);
+
+    error InvalidMaxRoyalty(uint16);
+
+    error StakeUnderChallenge(address, uint64);
+
+    error NotOwner(address);
+
+    error NotPendingOwner(address);
+
+    error NotTaskAdmin(string, address);
+
+    error NotPublisher(string, address);
+
+    error EnforcedPause();
+
+    error ExpectedPause();
+}
@@ -0,0 +1,982 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.23;
+
+// Generated by `cargo run --features export-abi --bin solidity-interface`; do not edit.
+interface IZkMLTrackRegistry {
+    struct RegisterTasksWithHashesTuple {
+        string f0;
+        bytes32 f1;
+    }
+
+    struct PublishNewVersionWithMetadataTuple {
+        string f0;
+        uint64 f1;
+    }
+
+    struct PublishNewVersionWithDependenciesTuple {
+        string f0;
+        uint64 f1;
+    }
+
+    struct PublishNewVersionsTuple {
+        string f0;
+        bytes32 f1;
+    }
+
+    struct GetLatestWithStalenessTuple {
+        uint64 f0;
+        bytes32 f1;
+        uint64 f2;
+        address f3;
+        uint64 f4;
+        string f5;
+        uint8 f6;
+        bytes32 f7;
+    }
+
+    struct GetLatestManyTuple {
+        uint64 f0;
+        bytes32 f1;
+        uint64 f2;
+        string f3;
+    }
+
+    struct GetVersionsRangeTuple {
+        uint64 f0;
+        bytes32 f1;
+        uint64 f2;
+        address f3;
+        uint64 f4;
+        string f5;
+        uint8 f6;
+        bytes32 f7;
+    }
+
+    struct GetAttestationsTuple {
+        bytes32 f0;
+        bytes32 f1;
+        address f2;
+        uint64 f3;
+    }
+
+    struct GetAuditsTuple {
+        address f0;
+        string f1;
+        bytes32 f2;
+        uint64 f3;
+    }
+
+    struct GetLineageTuple {
+        string f0;
+        uint64 f1;
+    }
+
+    struct GetTrainingInputsTuple {
+        string f0;
+        uint64 f1;
+        bytes32 f2;
+    }
+
+    struct GetMetricsTuple {
+        string f0;
+        uint256 f1;
+    }
+
+    struct GetAuditLogTuple {
+        uint8 f0;
+        address f1;
+        uint64 f2;
+        address f3;
+        uint64 f4;
+    }
+
+    struct GetDependenciesTuple {
+        string f0;
+        uint64 f1;
+    }
+
+    struct GetDependentsTuple {
+        string f0;
+        uint64 f1;
+    }
+
+    struct GetHooksTuple {
+        address f0;
+        uint64 f1;
+    }
+
+    event TaskRegistered(string task_id, address indexed registrar, uint64 timestamp);
+    event VersionPublished(string task_id, uint64 indexed version, bytes32 hash, address indexed publisher, uint64 timestamp, string artifact_uri, uint8 hash_algorithm, uint64 activates_at, bytes32 config_hash, uint64 operation_nonce);
+    event OwnershipChanged(address indexed previous_owner, address indexed new_owner);
+    event OwnershipTransferStarted(address indexed previous_owner, address indexed new_owner);
+    event TaskAdminChanged(string task_id, address indexed previous_admin, address indexed new_admin);
+    event PublisherGranted(string task_id, address indexed publisher, address indexed granted_by);
+    event PublisherRevoked(string task_id, address indexed publisher, address indexed revoked_by);
+    event Paused(address indexed account);
+    event Unpaused(address indexed account);
+    event VersionCommitted(string task_id, bytes32 indexed commitment, address indexed committer);
+    event ActivationDelayChanged(string task_id, uint64 activation_delay);
+    event PendingVersionCancelled(string task_id, uint64 indexed version);
+    event Rollback(string task_id, uint64 indexed from_version, uint64 indexed to_version, address indexed admin);
+    event TagSet(string task_id, string tag, uint64 indexed version);
+    event TaskFrozen(string task_id, address indexed frozen_by);
+    event TaskArchived(string task_id, string reason, address indexed archived_by);
+    event TaskUnarchived(string task_id, address indexed unarchived_by);
+    event FeesUpdated(uint256 registration_fee, uint256 publish_fee);
+    event FeesWithdrawn(address indexed to, uint256 amount);
+    event GatingUpdated(string task_id, bool gated, uint256 price_per_second);
+    event Subscribed(string task_id, address indexed subscriber, uint64 expires_at, uint256 paid);
+    event SubscriptionRevenueWithdrawn(string task_id, address indexed to, uint256 amount);
+    event VerificationKeySet(string task_id, uint64 indexed version, bytes32 vk_hash, string vk_uri);
+    event Groth16KeySet(string task_id, uint64 indexed version, bytes32 vk_digest);
+    event InferenceVerified(string task_id, uint64 indexed version, bytes32 indexed inference_id, address prover, bool valid);
+    event VerifierRouterChanged(address indexed previous_router, address indexed new_router);
+    event TrustedForwarderChanged(address indexed previous_forwarder, address indexed new_forwarder);
+    event ImageIdSet(string task_id, uint64 indexed version, bytes32 image_id);
+    event ReceiptVerified(string task_id, uint64 indexed version, bytes32 indexed journal_digest, address prover, bool valid);
+    event AggregationKeySet(string task_id, uint64 indexed version, bytes32 vk_digest);
+    event TrainingKeySet(string task_id, bytes32 vk_digest);
+    event TrainingVerified(string task_id, uint64 indexed version, address indexed prover, bytes32 inputs_hash);
+    event BatchVerified(string task_id, uint64 indexed version, bytes32 indexed batch_root, uint64 batch_size, address prover, bool valid);
+    event ChallengePeriodChanged(string task_id, uint64 challenge_period);
+    event ChallengeBondChanged(uint256 challenge_bond);
+    event RatingDepositChanged(uint256 rating_deposit);
+    event VersionRated(string task_id, uint64 indexed version, address indexed rater, uint8 score, uint256 deposit);
+    event RatingWithdrawn(string task_id, uint64 indexed version, address indexed rater, uint256 deposit);
+    event ArbiterChanged(address indexed previous_arbiter, address indexed new_arbiter);
+    event VersionChallenged(string task_id, uint64 indexed version, address indexed challenger, bytes32 evidence_hash, uint256 bond);
+    event ChallengeResolved(string task_id, uint64 indexed version, bool upheld, address indexed resolver);
+    event StakingUpdated(uint256 min_stake, uint64 unstake_cooldown);
+    event Staked(address indexed account, uint256 amount, uint256 total);
+    event UnstakeRequested(address indexed account, uint64 unlock_at);
+    event Unstaked(address indexed account, uint256 amount);
+    event StakeSlashed(address indexed account, uint256 amount, string task_id, uint64 version);
+    event AuditorGranted(address indexed auditor);
+    event AuditorRevoked(address indexed auditor);
+    event VersionAudited(string task_id, uint64 indexed version, address indexed auditor, string attestation_type, bytes32 report_hash);
+    event ParentLinked(string task_id, uint64 indexed version, string parent_task_id, uint64 parent_version);
+    event DatasetCommitted(string dataset_id, uint64 indexed revision, bytes32 hash, address indexed committer);
+    event DatasetLinked(string task_id, uint64 indexed version, string dataset_id, uint64 revision);
+    event DependencyLinked(string task_id, uint64 indexed version, string dependency_task_id, uint64 dependency_version);
+    event EvaluatorGranted(string task_id, address indexed evaluator);
+    event EvaluatorRevoked(string task_id, address indexed evaluator);
+    event MetricRecorded(string task_id, uint64 indexed version, string key, uint256 value, address indexed recorder);
+    event ModelCardSet(string task_id, uint64 indexed version, uint8 framework, uint64 parameter_count, bytes32 io_schema_hash, uint16 license_code);
+    event TaskTransferred(string task_id, address indexed from, address indexed to);
+    event TaskApproval(string task_id, address indexed owner, address indexed approved);
+    event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+    event PaymentTokenUpdated(address indexed token, uint256 registration_fee, uint256 publish_fee);
+    event TokenPaymentReceived(address indexed token, address indexed from, uint256 amount);
+    event TokenFeesWithdrawn(address indexed token, address indexed to, uint256 amount);
+    event TokenSubscriptionPriceUpdated(string task_id, uint256 price_per_second);
+    event TokenSubscriptionRevenueWithdrawn(string task_id, address indexed token, address indexed to, uint256 amount);
+    event RoyaltyUpdated(string task_id, string parent_task_id, uint16 royalty_bps);
+    event RoyaltyPaid(string task_id, string parent_task_id, address indexed token, uint256 amount);
+    event JobTimeoutChanged(uint64 job_timeout);
+    event InferenceRequested(uint64 indexed job_id, string task_id, uint64 version, address indexed requester, bytes32 input_commitment, uint256 payment, uint64 deadline);
+    event InferencePriceSet(string task_id, uint64 indexed version, uint256 price);
+    event RefundClaimed(string task_id, uint64 indexed version, address indexed consumer, uint256 amount);
+    event CircuitBreakerChanged(string task_id, uint32 threshold, uint64 window, bool halt_task);
+    event CircuitBreakerTripped(string task_id, uint64 indexed version, uint32 failures, bool task_halted);
+    event CircuitBreakerReset(string task_id, uint64 indexed version, address indexed admin);
+    event ExternalVerifierChanged(string task_id, address indexed previous_verifier, address indexed new_verifier);
+    event InferenceFulfilled(uint64 indexed job_id, address indexed prover, bytes32 output_commitment);
+    event InferenceRefunded(uint64 indexed job_id, address indexed requester, uint256 amount);
+    event OracleGranted(address indexed oracle);
+    event OracleRevoked(address indexed oracle);
+    event EvaluationRequested(uint64 indexed request_id, string task_id, uint64 version, address indexed requester, bytes32 suite_hash);
+    event EvaluationFulfilled(uint64 indexed request_id, string task_id, uint64 version, address indexed oracle, bytes32 results_hash);
+    event L1CheckpointTargetChanged(address indexed previous_target, address indexed new_target);
+    event CheckpointSentToL1(bytes32 digest, uint64 task_count, bytes32 version_root, bytes32 state_root, uint256 indexed message_id);
+    event L1ControllerChanged(address indexed previous_controller, address indexed new_controller);
+    event NamespaceAdminChanged(string namespace, address indexed previous_admin, address indexed new_admin);
+    event AliasAdded(string alias, string task_id);
+    event StrictModeChanged(string task_id, bool enabled);
+    event LicenseAssigned(string task_id, uint64 indexed version, string license, bool commercial_use, bool redistribution, bool fine_tuning);
+    event SemverAssigned(string task_id, uint64 indexed version, uint32 major, uint32 minor, uint32 patch);
+    event MaxAgeChanged(string task_id, uint64 max_age);
+    event VersionPinned(string task_id, uint64 indexed version, address indexed consumer);
+    event VersionUnpinned(string task_id, uint64 indexed version, address indexed consumer);
+    event PinnedVersionYanked(string task_id, uint64 indexed version, uint64 pin_count);
+    event EncryptedArtifactSet(string task_id, uint64 indexed version, string encrypted_uri, bytes32 key_commitment);
+    event AccessGranted(string task_id, uint64 indexed version, address indexed consumer, bytes32 wrapped_key_hash);
+    event StateImported(uint64 task_count, uint64 version_count);
+    event StateImportClosed();
+    event GuardianChanged(address indexed previous_guardian, address indexed new_guardian);
+    event ApproverGranted(string task_id, address indexed approver);
+    event ApproverRevoked(string task_id, address indexed approver);
+    event ApprovalPolicyChanged(string task_id, uint32 threshold, uint64 proposal_ttl);
+    event VersionProposed(string task_id, uint64 indexed proposal_id, bytes32 hash, address indexed proposer, uint64 expires_at);
+    event VersionApproved(string task_id, uint64 indexed proposal_id, address indexed approver, uint32 approvals);
+    event CheckpointRecorded(uint64 indexed index, bytes32 state_root, uint64 task_count, uint64 block_number);
+    event CheckpointIntervalChanged(uint64 interval);
+    event PublishCooldownChanged(string task_id, uint64 cooldown);
+    event CategoryAdded(string category);
+    event CategoryRetired(string category);
+    event TaskCategorized(string task_id, string category);
+    event VersionDeprecated(string task_id, uint64 indexed version, bytes32 reason_hash, uint64 sunset_at);
+    event VersionAuthored(string task_id, uint64 indexed version, address indexed author);
+    event VersionDeltaCommitted(string task_id, uint64 indexed version, bytes32 delta_hash);
+    event RoundOpened(string task_id, uint64 indexed round_id, address indexed coordinator);
+    event UpdateCommitted(string task_id, uint64 indexed round_id, address indexed client, bytes32 commitment);
+    event RoundFinalized(string task_id, uint64 indexed round_id, bytes32 aggregate_hash, bytes32 proof_hash, uint64 participant_count);
+    event CapabilityIssued(string task_id, address indexed consumer, uint64 expires_at);
+    event CapabilityRevoked(string task_id, address indexed consumer);
+    event KeeperBountyChanged(uint256 keeper_bounty);
+    event KeeperRewarded(address indexed keeper, uint256 amount);
+    event SubscriptionExpired(string task_id, address indexed subscriber);
+    event AdversarialKeySet(string task_id, bytes32 vk_digest);
+    event BountyFunded(string task_id, uint64 indexed version, address indexed funder, uint256 amount, uint256 total, uint64 deadline);
+    event BountyRefunded(string task_id, uint64 indexed version, address indexed funder, uint256 amount);
+    event AdversarialExampleFound(string task_id, uint64 indexed version, address indexed finder, bytes32 input_commitment, uint256 bounty);
+    event HookAdded(string task_id, address indexed hook, uint64 gas_limit);
+    event HookRemoved(string task_id, address indexed hook);
+    event HookFailed(string task_id, uint64 indexed version, address indexed hook);
+    event VersionsPruned(string task_id, uint64 pruned_through, bytes32 pruned_root);
+    event RecoveryGuardianChanged(address indexed guardian, bool enabled);
+    event RecoveryPolicyChanged(uint32 threshold, uint64 delay);
+    event RecoveryProposed(address indexed new_owner, address indexed proposer);
+    event RecoveryApproved(address indexed new_owner, address indexed guardian, uint32 approvals, uint64 executable_at);
+    event RecoveryCancelled(address indexed new_owner);
+    event RoundClientGranted(string task_id, address indexed client);
+    event RoundClientRevoked(string task_id, address indexed client);
+    event RoyaltyOffered(string task_id, string parent_task_id, uint16 royalty_bps);
+    event MaxRoyaltyChanged(uint16 max_royalty_bps);
+    event ProfileUpdated(address indexed account, bytes32 display_name_hash, string did, bytes32 website_hash);
+    event ProfileCleared(address indexed account);
+    event VersionYanked(string task_id, uint64 indexed version, string reason, uint64 successor_version, uint64 operation_nonce);
+    error TaskNotFound(string task_id);
+    error TaskExists(string task_id);
+    error NoVersions(string task_id);
+    error VersionNotFound(string task_id, uint64 version);
+    error VersionAlreadyYanked(string task_id, uint64 version);
+    error InvalidSuccessor(string task_id, uint64 successor_version);
+    error HashNotFound(bytes32 hash);
+    error UnsupportedHashAlgorithm(uint8 hash_algorithm);
+    error CommitmentExists(string task_id, bytes32 commitment);
+    error UnknownCommitment(string task_id, bytes32 commitment);
+    error RevealTooEarly(string task_id, bytes32 commitment);
+    error VersionNotPending(string task_id, uint64 version);
+    error InvalidRollbackTarget(string task_id, uint64 version);
+    error TagNotFound(string task_id, string tag);
+    error TaskIsFrozen(string task_id);
+    error TaskIsArchived(string task_id);
+    error TaskNotArchived(string task_id);
+    error InsufficientFee(uint256 required, uint256 provided);
+    error TransferFailed(address to, uint256 amount);
+    error TaskNotGated(string task_id);
+    error SignatureExpired(uint256 deadline);
+    error InvalidSignature();
+    error AlreadyInitialized();
+    error InvalidVerificationKey(string task_id, uint64 version);
+    error VerificationKeyAlreadySet(string task_id, uint64 version);
+    error NoVerificationKey(string task_id, uint64 version);
+    error ImageIdMismatch(string task_id, uint64 version, bytes32 image_id);
+    error NoVerifierRouter();
+    error ChallengeWindowClosed(string task_id, uint64 version);
+    error ChallengeAlreadyOpen(string task_id, uint64 version);
+    error NoOpenChallenge(string task_id, uint64 version);
+    error InsufficientStake(address account, uint256 required, uint256 staked);
+    error UnstakeNotReady(uint64 unlock_at);
+    error NotAuditor(address account);
+    error DatasetNotFound(string dataset_id, uint64 revision);
+    error NotDatasetOwner(string dataset_id, address account);
+    error NotEvaluator(string task_id, address account);
+    error MetricsLengthMismatch(uint256 keys, uint256 values);
+    error MetricNotFound(string task_id, uint64 version, string key);
+    error UnsupportedFramework(uint8 framework);
+    error NotTaskApproved(string task_id, address account);
+    error InvalidRecipient(address to);
+    error TokenTransferFailed(address token, address to, uint256 amount);
+    error InvalidRoyalty(string task_id, string parent_task_id, uint16 royalty_bps);
+    error JobNotFound(uint64 job_id);
+    error JobNotOpen(uint64 job_id);
+    error JobExpired(uint64 job_id, uint64 deadline);
+    error JobNotExpired(uint64 job_id, uint64 deadline);
+    error InvalidJobFulfillment(uint64 job_id);
+    error NotOracle(address account);
+    error EvaluationRequestNotFound(uint64 request_id);
+    error EvaluationAlreadyFulfilled(uint64 request_id);
+    error NoCheckpointTarget();
+    error CheckpointFailed();
+    error NotL1Controller(address account);
+    error TaskIdNotFound(bytes32 id);
+    error NotNamespaceAdmin(string namespace, address account);
+    error AliasExists(string alias);
+    error DuplicateHash(string task_id, bytes32 hash);
+    error NoVersionAt(string task_id, uint64 timestamp);
+    error SemverNotIncreasing(string task_id, uint32 major, uint32 minor, uint32 patch);
+    error NoVersionInMajor(string task_id, uint32 major);
+    error StaleVersion(string task_id, uint64 version, uint64 timestamp, uint64 max_age);
+    error NotEncrypted(string task_id, uint64 version);
+    error EncryptionAlreadySet(string task_id, uint64 version);
+    error InvalidStateChunk();
+    error ImportClosed();
+    error ApprovalRequired(string task_id);
+    error NotApprover(string task_id, address account);
+    error InvalidThreshold(string task_id, uint32 threshold, uint32 approver_count);
+    error ProposalNotFound(string task_id, uint64 proposal_id);
+    error ProposalClosed(string task_id, uint64 proposal_id);
+    error AlreadyApproved(string task_id, uint64 proposal_id, address approver);
+    error InvalidFieldElement(uint256 value);
+    error NotPoseidonCommitted(string task_id, uint64 version);
+    error CheckpointTooSoon(uint64 next_at);
+    error CheckpointNotFound(uint64 index);
+    error PublishCooldown(string task_id, uint64 next_at);
+    error UnknownCategory(string category);
+    error CategoryExists(string category);
+    error CategoryAlreadyAssigned(string task_id, string category);
+    error InvalidSunset(uint64 sunset_at);
+    error RoundNotFound(string task_id, uint64 round_id);
+    error RoundStillOpen(string task_id, uint64 round_id);
+    error RoundClosed(string task_id, uint64 round_id);
+    error UpdateAlreadySubmitted(string task_id, uint64 round_id, address client);
+    error NoRefundDue(string task_id, uint64 version, address account);
+    error CircuitBreakerOpen(string task_id, uint64 version);
+    error InvalidRating(uint8 score);
+    error AlreadyRated(string task_id, uint64 version, address rater);
+    error NotRated(string task_id, uint64 version, address rater);
+    error InvalidExpiry(uint64 expires_at);
+    error InvalidDuration(uint64 duration);
+    error SubscriptionNotExpired(string task_id, address subscriber);
+    error BountyClosed(string task_id, uint64 version);
+    error NotMisclassified(uint256 label, uint256 prediction);
+    error TooManyHooks(string task_id);
+    error HookNotFound(string task_id, address hook);
+    error InvalidHookGas(uint64 gas_limit);
+    error NotRecoveryGuardian(address account);
+    error InvalidRecoveryThreshold(uint32 threshold, uint32 guardian_count);
+    error RecoveryPending(address new_owner);
+    error NoRecoveryPending();
+    error RecoveryAlreadyApproved(address guardian);
+    error RecoveryNotReady(uint64 executable_at);
+    error NoTrainingData(string task_id, uint64 version);
+    error BountyKeyFrozen(string task_id);
+    error BountyNotExpired(string task_id, uint64 version, uint64 deadline);
+    error InvalidBreakerWindow(uint32 threshold, uint64 window);
+    error NotRoundClient(string task_id, address account);
+    error InvalidMaxRoyalty(uint16 max_royalty_bps);
+    error StakeUnderChallenge(address account, uint64 open_challenges);
+    error NotOwner(address account);
+    error NotPendingOwner(address account);
+    error NotTaskAdmin(string task_id, address account);
+    error NotPublisher(string task_id, address account);
+    error EnforcedPause();
+    error ExpectedPause();
+
+    function init() external;
+
+    function owner() external view returns (address);
+
+    function pendingOwner() external view returns (address);
+
+    function transferOwnership(address new_owner) external;
+
+    function acceptOwnership() external;
+
+    function paused() external view returns (bool);
+
+    function pause() external;
+
+    function unpause() external;
+
+    function guardian() external view returns (address);
+
+    function setGuardian(address guardian) external;
+
+    function getRecoveryPolicy() external view returns (uint32, uint64, uint32);
+
+    function setRecoveryGuardian(address guardian, bool enabled) external;
+
+    function setRecoveryPolicy(uint32 threshold, uint64 delay) external;
+
+    function getRecovery() external view returns (address, uint32, uint64);
+
+    function proposeRecovery(address new_owner) external;
+
+    function approveRecovery() external;
+
+    function executeRecovery() external;
+
+    function cancelRecovery() external;
+
+    function registerTask(string calldata task_id) external payable;
+
+    function registerTaskWithCategories(string calldata task_id, string[] memory categories) external payable;
+
+    function registerTasks(string[] memory task_ids) external payable;
+
+    function registerTasksWithHashes(RegisterTasksWithHashesTuple[] memory entries) external payable;
+
+    function taskCount() external view returns (uint64);
+
+    function taskIds(uint64 offset, uint64 limit) external view returns (string[] memory);
+
+    function allTaskIds(uint64 offset, uint64 limit) external view returns (string[] memory);
+
+    function publishNewVersion(string calldata task_id, bytes32 hash) external payable;
+
+    function publishNewVersionWithAuthorSig(string calldata task_id, bytes32 hash, bytes calldata author_signature) external payable;
+
+    function getReleaseDigest(string calldata task_id, bytes32 hash) external view returns (bytes32);
+
+    function getAuthor(string calldata task_id, uint64 version) external view returns (address);
+
+    function publishNewVersionWithDelta(string calldata task_id, bytes32 hash, bytes32 delta_hash) external payable;
+
+    function getPrevRecordHash(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function getRecordHash(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function getDeltaHash(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function getDeltaChain(string calldata task_id, uint64 version) external view returns (uint64, bytes32[] memory);
+
+    function publishNewVersionWithUri(string calldata task_id, bytes32 hash, string calldata artifact_uri) external payable;
+
+    function publishNewVersionWithAlgorithm(string calldata task_id, bytes32 hash, uint8 hash_algorithm, string calldata artifact_uri) external payable;
+
+    function publishNewVersionWithMetadata(string calldata task_id, bytes32 hash, bytes32 config_hash, string calldata parent_task_id, uint64 parent_version, PublishNewVersionWithMetadataTuple[] memory datasets) external payable;
+
+    function publishNewVersionWithLicense(string calldata task_id, bytes32 hash, string calldata license, bool commercial_use, bool redistribution, bool fine_tuning) external payable;
+
+    function publishNewVersionWithDependencies(string calldata task_id, bytes32 hash, PublishNewVersionWithDependenciesTuple[] memory dependencies) external payable;
+
+    function publishNewVersionSemver(string calldata task_id, bytes32 hash, uint32 major, uint32 minor, uint32 patch) external payable;
+
+    function publishNewVersions(PublishNewVersionsTuple[] memory entries) external payable;
+
+    function commitVersion(string calldata task_id, bytes32 commitment) external;
+
+    function revealVersion(string calldata task_id, bytes32 hash, bytes32 salt) external payable;
+
+    function taskAdmin(string calldata task_id) external view returns (address);
+
+    function isPublisher(string calldata task_id, address account) external view returns (bool);
+
+    function setTaskAdmin(string calldata task_id, address new_admin) external;
+
+    function grantPublisher(string calldata task_id, address publisher) external;
+
+    function revokePublisher(string calldata task_id, address publisher) external;
+
+    function getLatest(string calldata task_id) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getLatestFresh(string calldata task_id) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getLatestWithStaleness(string calldata task_id) external view returns (GetLatestWithStalenessTuple memory, bool);
+
+    function getLatestMany(string[] memory task_ids) external view returns (GetLatestManyTuple[] memory);
+
+    function getVersion(string calldata task_id, uint64 version) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getVersionsRange(string calldata task_id, uint64 from_version, uint64 limit) external view returns (GetVersionsRangeTuple[] memory);
+
+    function lookupHash(bytes32 hash) external view returns (string memory, uint64);
+
+    function getVersionCount(string calldata task_id) external view returns (uint64);
+
+    function yankVersion(string calldata task_id, uint64 version, string calldata reason, uint64 successor_version) external;
+
+    function getYankStatus(string calldata task_id, uint64 version) external view returns (bool, string memory, uint64);
+
+    function deprecateVersion(string calldata task_id, uint64 version, bytes32 reason_hash, uint64 sunset_at) external;
+
+    function getDeprecation(string calldata task_id, uint64 version) external view returns (bool, bytes32, uint64);
+
+    function getLatestUnyanked(string calldata task_id) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getActivationDelay(string calldata task_id) external view returns (uint64);
+
+    function setActivationDelay(string calldata task_id, uint64 activation_delay) external;
+
+    function getActivationStatus(string calldata task_id, uint64 version) external view returns (uint64, bool);
+
+    function cancelPendingVersion(string calldata task_id, uint64 version) external;
+
+    function rollbackLatest(string calldata task_id, uint64 to_version) external;
+
+    function setTag(string calldata task_id, string calldata tag, uint64 version) external;
+
+    function getByTag(string calldata task_id, string calldata tag) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function isFrozen(string calldata task_id) external view returns (bool);
+
+    function freezeTask(string calldata task_id) external;
+
+    function getArchiveStatus(string calldata task_id) external view returns (bool, string memory);
+
+    function archiveTask(string calldata task_id, string calldata reason) external;
+
+    function unarchiveTask(string calldata task_id) external;
+
+    function fees() external view returns (uint256, uint256);
+
+    function collectedFees() external view returns (uint256);
+
+    function setFee(uint256 registration_fee, uint256 publish_fee) external;
+
+    function withdrawFees(address to) external;
+
+    function getGating(string calldata task_id) external view returns (bool, uint256);
+
+    function setGating(string calldata task_id, bool gated, uint256 price_per_second) external;
+
+    function subscribe(string calldata task_id, uint64 duration) external payable;
+
+    function subscriptionExpiry(string calldata task_id, address account) external view returns (uint64);
+
+    function isSubscriber(string calldata task_id, address account) external view returns (bool);
+
+    function issueCapability(string calldata task_id, address consumer, uint64 expires_at) external;
+
+    function revokeCapability(string calldata task_id, address consumer) external;
+
+    function hasCapability(string calldata task_id, address consumer) external view returns (bool);
+
+    function capabilityExpiry(string calldata task_id, address consumer) external view returns (uint64);
+
+    function subscriptionRevenue(string calldata task_id) external view returns (uint256);
+
+    function withdrawSubscriptionRevenue(string calldata task_id, address to) external;
+
+    function publishNonce(string calldata task_id) external view returns (uint256);
+
+    function publishWithSig(string calldata task_id, bytes32 hash, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external payable;
+
+    function publishWithSignerSig(string calldata task_id, bytes32 hash, address signer, uint256 deadline, bytes calldata signature) external payable;
+
+    function setGroth16Key(string calldata task_id, uint64 version, bytes calldata vk) external;
+
+    function getGroth16Key(string calldata task_id, uint64 version) external view returns (bytes memory);
+
+    function verifyInference(string calldata task_id, uint64 version, bytes calldata proof, uint256[] memory public_inputs) external returns (bool);
+
+    function getExternalVerifier(string calldata task_id) external view returns (address);
+
+    function setExternalVerifier(string calldata task_id, address verifier) external;
+
+    function verifyCommittedInference(string calldata task_id, uint64 version, bytes calldata proof, uint256[] memory public_inputs) external returns (bool);
+
+    function poseidonCommitment(uint256[] memory inputs) external view returns (bytes32);
+
+    function isInferenceVerified(string calldata task_id, uint64 version, bytes32 inference_id) external view returns (bool);
+
+    function verifiedInferenceCount(string calldata task_id, uint64 version) external view returns (uint64);
+
+    function setVerificationKey(string calldata task_id, uint64 version, bytes32 vk_hash, string calldata vk_uri) external;
+
+    function getVerificationKey(string calldata task_id, uint64 version) external view returns (bytes32, string memory);
+
+    function attestationCount(string calldata task_id, uint64 version) external view returns (uint64);
+
+    function getAttestations(string calldata task_id, uint64 version, uint64 offset, uint64 limit) external view returns (GetAttestationsTuple[] memory);
+
+    function verifierRouter() external view returns (address);
+
+    function setVerifierRouter(address router) external;
+
+    function trustedForwarder() external view returns (address);
+
+    function isTrustedForwarder(address forwarder) external view returns (bool);
+
+    function setTrustedForwarder(address forwarder) external;
+
+    function setImageId(string calldata task_id, uint64 version, bytes32 image_id) external;
+
+    function getImageId(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function verifyReceipt(string calldata task_id, uint64 version, bytes32 image_id, bytes calldata journal, bytes calldata seal) external returns (bool);
+
+    function isJournalVerified(string calldata task_id, uint64 version, bytes32 journal_digest) external view returns (bool);
+
+    function setAggregationKey(string calldata task_id, uint64 version, bytes calldata vk) external;
+
+    function verifyBatch(string calldata task_id, uint64 version, bytes calldata proof, bytes32 batch_root, uint64 batch_size) external returns (bool);
+
+    function batchSize(string calldata task_id, uint64 version, bytes32 batch_root) external view returns (uint64);
+
+    function isBatchMember(string calldata task_id, uint64 version, bytes32 batch_root, bytes32 input_commitment, bytes32 output_commitment, bytes32[] memory proof) external view returns (bool);
+
+    function getChallengePeriod(string calldata task_id) external view returns (uint64);
+
+    function setChallengePeriod(string calldata task_id, uint64 challenge_period) external;
+
+    function challengeBond() external view returns (uint256);
+
+    function setChallengeBond(uint256 challenge_bond) external;
+
+    function arbiter() external view returns (address);
+
+    function setArbiter(address arbiter) external;
+
+    function challengeVersion(string calldata task_id, uint64 version, bytes32 evidence_hash) external payable;
+
+    function resolveChallenge(string calldata task_id, uint64 version, bool upheld) external;
+
+    function getRefund(string calldata task_id, uint64 version, address consumer) external view returns (uint256, uint256);
+
+    function claimRefund(string calldata task_id, uint64 version) external returns (uint256);
+
+    function getChallenge(string calldata task_id, uint64 version) external view returns (uint8, address, uint256, bytes32, uint64);
+
+    function staking() external view returns (uint256, uint64);
+
+    function setStaking(uint256 min_stake, uint64 unstake_cooldown) external;
+
+    function getStake(address account) external view returns (uint256, uint64);
+
+    function stake() external payable;
+
+    function requestUnstake() external;
+
+    function unstake() external;
+
+    function isAuditor(address account) external view returns (bool);
+
+    function grantAuditor(address auditor) external;
+
+    function revokeAuditor(address auditor) external;
+
+    function attest(string calldata task_id, uint64 version, string calldata attestation_type, bytes32 report_hash) external;
+
+    function getAudits(string calldata task_id, uint64 version) external view returns (GetAuditsTuple[] memory);
+
+    function reputationOf(address account) external view returns (uint64, uint64, uint64, uint64, uint64);
+
+    function setProfile(bytes32 display_name_hash, string calldata did, bytes32 website_hash) external;
+
+    function clearProfile() external;
+
+    function getProfile(address account) external view returns (bytes32, string memory, bytes32, uint64);
+
+    function getPublisherProfile(string calldata task_id, uint64 version) external view returns (address, bytes32, string memory, bytes32);
+
+    function getLineage(string calldata task_id, uint64 version) external view returns (GetLineageTuple[] memory);
+
+    function commitDataset(string calldata dataset_id, bytes32 hash) external returns (uint64);
+
+    function getDatasetInfo(string calldata dataset_id) external view returns (address, uint64);
+
+    function getDatasetHash(string calldata dataset_id, uint64 revision) external view returns (bytes32);
+
+    function getTrainingInputs(string calldata task_id, uint64 version) external view returns (GetTrainingInputsTuple[] memory);
+
+    function isEvaluator(string calldata task_id, address account) external view returns (bool);
+
+    function grantEvaluator(string calldata task_id, address evaluator) external;
+
+    function revokeEvaluator(string calldata task_id, address evaluator) external;
+
+    function recordMetrics(string calldata task_id, uint64 version, string[] memory metric_keys, uint256[] memory metric_values) external;
+
+    function getMetric(string calldata task_id, uint64 version, string calldata key) external view returns (uint256);
+
+    function getMetrics(string calldata task_id, uint64 version) external view returns (GetMetricsTuple[] memory);
+
+    function setModelCard(string calldata task_id, uint64 version, uint8 framework, uint64 parameter_count, bytes32 io_schema_hash, uint16 license_code) external;
+
+    function getModelCard(string calldata task_id, uint64 version) external view returns (uint8, uint64, bytes32, uint16);
+
+    function ownerOfTask(string calldata task_id) external view returns (address);
+
+    function approveTask(string calldata task_id, address approved) external;
+
+    function getApprovedTask(string calldata task_id) external view returns (address);
+
+    function setApprovalForAll(address operator, bool approved) external;
+
+    function isApprovedForAll(address owner, address operator) external view returns (bool);
+
+    function transferTask(string calldata task_id, address new_owner) external;
+
+    function tokenFees() external view returns (address, uint256, uint256);
+
+    function setPaymentToken(address token, uint256 registration_fee, uint256 publish_fee) external;
+
+    function collectedTokenFees(address token) external view returns (uint256);
+
+    function withdrawTokenFees(address token, address to) external;
+
+    function getTokenSubscriptionPrice(string calldata task_id) external view returns (uint256);
+
+    function setTokenSubscriptionPrice(string calldata task_id, uint256 price_per_second) external;
+
+    function tokenSubscriptionRevenue(string calldata task_id, address token) external view returns (uint256);
+
+    function withdrawTokenSubscriptionRevenue(string calldata task_id, address token, address to) external;
+
+    function getRoyalty(string calldata task_id) external view returns (string memory, uint16);
+
+    function getRoyaltyOffer(string calldata task_id) external view returns (string memory, uint16);
+
+    function maxRoyaltyBps() external view returns (uint16);
+
+    function setMaxRoyaltyBps(uint16 max_royalty_bps) external;
+
+    function setRoyalty(string calldata task_id, uint64 version, uint16 royalty_bps) external;
+
+    function acceptRoyalty(string calldata task_id, uint16 royalty_bps) external;
+
+    function jobTimeout() external view returns (uint64);
+
+    function setJobTimeout(uint64 job_timeout) external;
+
+    function getInferencePrice(string calldata task_id, uint64 version) external view returns (uint256);
+
+    function setInferencePrice(string calldata task_id, uint64 version, uint256 price) external;
+
+    function requestInference(string calldata task_id, uint64 version, bytes32 input_commitment) external payable returns (uint64);
+
+    function fulfillInference(uint64 job_id, bytes32 output_commitment, bytes calldata proof, uint256[] memory public_inputs) external returns (bool);
+
+    function refundInference(uint64 job_id) external;
+
+    function jobCount() external view returns (uint64);
+
+    function getJob(uint64 job_id) external view returns (string memory, uint64, address, bytes32, uint256, uint64, uint8, address, bytes32);
+
+    function isOracle(address account) external view returns (bool);
+
+    function grantOracle(address oracle) external;
+
+    function revokeOracle(address oracle) external;
+
+    function requestEvaluation(string calldata task_id, uint64 version, bytes32 suite_hash) external returns (uint64);
+
+    function fulfillEvaluation(string calldata task_id, uint64 version, uint64 request_id, bytes32 results_hash) external;
+
+    function evaluationCount() external view returns (uint64);
+
+    function getEvaluation(uint64 request_id) external view returns (string memory, uint64, address, bytes32, bool, address, bytes32, uint64);
+
+    function versionRoot() external view returns (bytes32);
+
+    function l1CheckpointTarget() external view returns (address);
+
+    function setL1CheckpointTarget(address target) external;
+
+    function checkpointToL1() external returns (bytes32);
+
+    function lastCheckpoint() external view returns (bytes32, uint64);
+
+    function l1Controller() external view returns (address);
+
+    function setL1Controller(address controller) external;
+
+    function registerTaskFromL1(string calldata task_id) external;
+
+    function publishFromL1(string calldata task_id, bytes32 hash, string calldata artifact_uri) external;
+
+    function multicall(bytes[] memory calls) external returns (bytes[] memory);
+
+    function taskIdOf(string calldata task_id) external view returns (bytes32);
+
+    function taskNameOf(bytes32 id) external view returns (string memory);
+
+    function publishNewVersionById(bytes32 id, bytes32 hash) external payable;
+
+    function getLatestById(bytes32 id) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getVersionById(bytes32 id, uint64 version) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getVersionCountById(bytes32 id) external view returns (uint64);
+
+    function namespaceAdmin(string calldata namespace) external view returns (address);
+
+    function setNamespaceAdmin(string calldata namespace, address admin) external;
+
+    function tasksInNamespace(string calldata namespace, uint64 offset, uint64 limit) external view returns (string[] memory);
+
+    function addAlias(string calldata _alias, string calldata task_id) external;
+
+    function resolveTaskId(string calldata task_id) external view returns (string memory);
+
+    function isStrictMode(string calldata task_id) external view returns (bool);
+
+    function setStrictMode(string calldata task_id, bool enabled) external;
+
+    function getVersionAt(string calldata task_id, uint64 timestamp) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function totalTasks() external view returns (uint64);
+
+    function totalVersions() external view returns (uint64);
+
+    function versionsOf(string calldata task_id) external view returns (uint64);
+
+    function lastActivity(string calldata task_id) external view returns (uint64);
+
+    function getSemver(string calldata task_id, uint64 version) external view returns (uint32, uint32, uint32);
+
+    function getLatestInMajor(string calldata task_id, uint32 major) external view returns (uint64, bytes32, uint64, address, uint64, string memory, uint8, bytes32);
+
+    function getMaxAge(string calldata task_id) external view returns (uint64);
+
+    function setMaxAge(string calldata task_id, uint64 max_age) external;
+
+    function getPublishCooldown(string calldata task_id) external view returns (uint64);
+
+    function setPublishCooldown(string calldata task_id, uint64 cooldown) external;
+
+    function pin(string calldata task_id, uint64 version) external;
+
+    function unpin(string calldata task_id) external;
+
+    function getPin(string calldata task_id, address consumer) external view returns (uint64);
+
+    function getPinCount(string calldata task_id, uint64 version) external view returns (uint64);
+
+    function getPinners(string calldata task_id, uint64 version, uint64 offset, uint64 limit) external view returns (address[] memory);
+
+    function setEncryptedArtifact(string calldata task_id, uint64 version, string calldata encrypted_uri, bytes32 key_commitment) external;
+
+    function getEncryptedArtifact(string calldata task_id, uint64 version) external view returns (string memory, bytes32);
+
+    function grantAccess(string calldata task_id, uint64 version, address consumer, bytes32 wrapped_key_hash) external;
+
+    function getAccessGrant(string calldata task_id, uint64 version, address consumer) external view returns (bytes32);
+
+    function contractVersion() external view returns (string memory);
+
+    function supportsInterface(bytes4 interface_id) external view returns (bool);
+
+    function exportState(uint64 cursor, uint64 limit) external view returns (bytes memory, uint64);
+
+    function importState(bytes calldata chunk) external;
+
+    function closeImport() external;
+
+    function isApprover(string calldata task_id, address account) external view returns (bool);
+
+    function grantApprover(string calldata task_id, address approver) external;
+
+    function revokeApprover(string calldata task_id, address approver) external;
+
+    function getApprovalPolicy(string calldata task_id) external view returns (uint32, uint64, uint32);
+
+    function setApprovalPolicy(string calldata task_id, uint32 threshold, uint64 proposal_ttl) external;
+
+    function proposeVersion(string calldata task_id, bytes32 hash, string calldata artifact_uri) external payable returns (uint64);
+
+    function approveVersion(string calldata task_id, uint64 proposal_id) external;
+
+    function getProposal(string calldata task_id, uint64 proposal_id) external view returns (address, bytes32, uint64, uint32, uint64);
+
+    function nonceOf(string calldata task_id, address account) external view returns (uint64);
+
+    function getVersionTreeRoot(string calldata task_id) external view returns (bytes32);
+
+    function verifyVersionProof(string calldata task_id, uint64 version, bytes32 hash, bytes32[] memory proof) external view returns (bool);
+
+    function stateRoot() external view returns (bytes32);
+
+    function recordCheckpoint() external returns (uint64);
+
+    function checkpointCount() external view returns (uint64);
+
+    function getCheckpoint(uint64 index) external view returns (bytes32, uint64, uint64, uint64);
+
+    function checkpointInterval() external view returns (uint64);
+
+    function setCheckpointInterval(uint64 interval) external;
+
+    function addCategory(string calldata category) external;
+
+    function retireCategory(string calldata category) external;
+
+    function categories() external view returns (string[] memory);
+
+    function assignCategory(string calldata task_id, string calldata category) external;
+
+    function getTaskCategories(string calldata task_id) external view returns (string[] memory);
+
+    function tasksByCategory(string calldata category, uint64 offset, uint64 limit) external view returns (string[] memory);
+
+    function getAuditLogLength(string calldata task_id) external view returns (uint64);
+
+    function getAuditLog(string calldata task_id, uint64 offset, uint64 limit) external view returns (GetAuditLogTuple[] memory);
+
+    function openRound(string calldata task_id) external returns (uint64);
+
+    function isRoundClient(string calldata task_id, address account) external view returns (bool);
+
+    function grantRoundClient(string calldata task_id, address client) external;
+
+    function revokeRoundClient(string calldata task_id, address client) external;
+
+    function submitUpdateCommitment(string calldata task_id, uint64 round_id, bytes32 commitment) external;
+
+    function finalizeRound(string calldata task_id, bytes32 aggregate_hash, bytes32 proof_hash) external returns (uint64);
+
+    function getRoundCount(string calldata task_id) external view returns (uint64);
+
+    function getRound(string calldata task_id, uint64 round_id) external view returns (uint64, uint64, uint64, bytes32, bytes32);
+
+    function getUpdateCommitment(string calldata task_id, uint64 round_id, address client) external view returns (bytes32);
+
+    function getCircuitBreaker(string calldata task_id) external view returns (uint32, uint64, bool);
+
+    function setCircuitBreaker(string calldata task_id, uint32 threshold, uint64 window, bool halt_task) external;
+
+    function getBreakerState(string calldata task_id, uint64 version) external view returns (uint32, uint64, bool, bool);
+
+    function resetCircuitBreaker(string calldata task_id, uint64 version) external;
+
+    function getAdversarialKey(string calldata task_id) external view returns (bytes memory);
+
+    function setAdversarialKey(string calldata task_id, bytes calldata vk) external;
+
+    function fundBounty(string calldata task_id, uint64 version, uint64 deadline) external payable;
+
+    function refundBounty(string calldata task_id, uint64 version) external returns (uint256);
+
+    function submitAdversarialExample(string calldata task_id, uint64 version, bytes32 input_commitment, uint256 label, uint256 prediction, bytes calldata proof) external returns (bool);
+
+    function getBounty(string calldata task_id, uint64 version) external view returns (uint256, uint64, address, bytes32);
+
+    function getTrainingKey(string calldata task_id) external view returns (bytes memory);
+
+    function setTrainingKey(string calldata task_id, bytes calldata vk) external;
+
+    function submitTrainingProof(string calldata task_id, uint64 version, bytes calldata proof, uint256[] memory public_inputs) external returns (bool);
+
+    function getTrainingCommitment(string calldata task_id, uint64 version) external view returns (bytes32);
+
+    function getTrainingStatus(string calldata task_id, uint64 version) external view returns (bool, bytes32);
+
+    function getDependencies(string calldata task_id, uint64 version) external view returns (GetDependenciesTuple[] memory);
+
+    function getDependents(string calldata task_id, uint64 version, uint64 offset, uint64 limit) external view returns (GetDependentsTuple[] memory);
+
+    function licenseOf(string calldata task_id, uint64 version) external view returns (string memory, bool, bool, bool);
+
+    function ratingDeposit() external view returns (uint256);
+
+    function setRatingDeposit(uint256 rating_deposit) external;
+
+    function rateVersion(string calldata task_id, uint64 version, uint8 score) external payable;
+
+    function withdrawRating(string calldata task_id, uint64 version) external;
+
+    function getRating(string calldata task_id, uint64 version) external view returns (uint64, uint64);
+
+    function getRatingOf(string calldata task_id, uint64 version, address rater) external view returns (uint8, uint256);
+
+    function keeperBounty() external view returns (uint256);
+
+    function setKeeperBounty(uint256 keeper_bounty) external;
+
+    function pokeInference(uint64 job_id) external returns (uint256);
+
+    function pokeSubscription(string calldata task_id, address subscriber) external returns (uint256);
+
+    function getHooks(string calldata task_id) external view returns (GetHooksTuple[] memory);
+
+    function addHook(string calldata task_id, address hook, uint64 gas_limit) external;
+
+    function removeHook(string calldata task_id, address hook) external;
+
+    function getPrunedHistory(string calldata task_id) external view returns (uint64, bytes32);
+
+    function pruneVersions(string calldata task_id, uint64 keep_last_n) external returns (uint64);
+}
@@ -0,0 +1,32 @@
+#!/bin/sh
+# Regenerates abi/IRegistry.sol from the contract. The Stylus exporter emits
+# two constructs the `sol!` parser rejects, patched up here: array-of-tuple
+# return types without their parentheses, and `memory` on strings nested in
+# tuple return types.
+#
+# Also regenerates abi/IZkMLTrackRegistry.sol, the full Solidity interface
+# (functions, events and custom errors) for contracts calling the registry,
+# and src/registry_interface.rs, the `sol_interface!` behind the contract's
+# `interface` feature, which takes functions only.
+set -e
+cd "$(dirname "$0")/.."
+cargo run -q --features export-abi --bin zkMLTrack \
+  | sed -E 's/returns (\([^;]*\)\[\] memory);/returns (\1);/' \
+  | sed -E '/returns \(\(/ s/string memory, /string, /g' \
+  > client/abi/IRegistry.sol
+cargo run -q --features export-abi --bin solidity-interface \
+  > client/abi/IZkMLTrackRegistry.sol
+{
+  printf '%s\n' \
+    '//! Typed calls into a deployed registry from other Stylus contracts.' \
+    '//! Generated from `client/abi/IRegistry.sol` by `client/generate-abi.sh`; do not edit.' \
+    '' \
+    '#![allow(clippy::too_many_arguments)]' \
+    '' \
+    'use stylus_sdk::prelude::*;' \
+    '' \
+    'sol_interface! {' \
+    '  interface IRegistry {'
+  grep '^    function ' client/abi/IRegistry.sol
+  printf '%s\n' '  }' '}'
+} > src/registry_interface.rs
@@ -0,0 +1,387 @@
+//! Host-side client for the zkMLTrack registry.
+//!
+//! Function bindings are generated from `abi/IRegistry.sol`, the interface the
+//! contract crate exports; rerun `generate-abi.sh` after changing the
+//! contract's public API. The client does not ship a JSON-RPC stack: services
+//! plug in the one they already use by implementing [`Transport`].
+
+use std::fmt;
+use std::thread;
+use std::time::Duration;
+
+use alloy_primitives::{Address, Bytes, Log, B256, U256};
+use alloy_sol_types::{sol, SolCall, SolEvent, SolInterface};
+
+sol!(
+  #[sol(all_derives)]
+  "abi/IRegistry.sol"
+);
+
+sol! {
+  // The exported interface carries no events; these mirror the contract's.
+  #![sol(all_derives)]
+
+  event TaskRegistered(string task_id, address indexed registrar, uint64 timestamp);
+  event VersionPublished(
+    string task_id,
+    uint64 indexed version,
+    bytes32 hash,
+    address indexed publisher,
+    uint64 timestamp,
+    string artifact_uri,
+    uint8 hash_algorithm,
+    uint64 activates_at,
+    bytes32 config_hash,
+    uint64 operation_nonce
+  );
+  event VersionYanked(
+    string task_id,
+    uint64 indexed version,
+    string reason,
+    uint64 successor_version,
+    uint64 operation_nonce
+  );
+  event VersionPinned(string task_id, uint64 indexed version, address indexed consumer);
+  event PinnedVersionYanked(string task_id, uint64 indexed version, uint64 pin_count);
+}
+
+/// A log together with the block it was emitted in.
+#[derive(Clone, Debug)]
+pub struct RawLog {
+  pub block_number: u64,
+  pub log: Log,
+}
+
+/// The node connection the client talks through.
+pub trait Transport {
+  type Error;
+
+  /// `eth_call` against the latest block.
+  fn call(&self, to: Address, input: Bytes) -> Result<Bytes, Self::Error>;
+
+  /// Signs and submits a transaction, returning its hash.
+  fn send(&self, to: Address, input: Bytes, value: U256) -> Result<B256, Self::Error>;
+
+  /// Logs emitted by `address` in `from_block..=to_block`.
+  fn logs(&self, address: Address, from_block: u64, to_block: u64) -> Result<Vec<RawLog>, Self::Error>;
+
+  fn block_number(&self) -> Result<u64, Self::Error>;
+
+  /// Revert data returned with a failed call, if any.
+  fn revert_data(error: &Self::Error) -> Option<&[u8]>;
+
+  /// Whether the failure is transient (timeouts, rate limits) and worth retrying.
+  fn is_transient(error: &Self::Error) -> bool;
+}
+
+#[derive(Debug)]
+pub enum Error<E> {
+  Transport(E),
+  /// The registry reverted with one of its typed errors.
+  Reverted(IRegistry::IRegistryErrors),
+  Decode(alloy_sol_types::Error),
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::Transport(error) => write!(f, "transport error: {error:?}"),
+      Error::Reverted(error) => write!(f, "registry reverted: {error:?}"),
+      Error::Decode(error) => write!(f, "undecodable response: {error}"),
+    }
+  }
+}
+
+impl<E: fmt::Debug> std::error::Error for Error<E> {}
+
+/// How reads are retried on transient transport failures. Transactions are
+/// never resubmitted, since a retried publish could land twice; reconcile
+/// with `nonce_of` instead.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+  /// Total attempts, including the first.
+  pub max_attempts: u32,
+  pub initial_backoff: Duration,
+  pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    RetryPolicy {
+      max_attempts: 3,
+      initial_backoff: Duration::from_millis(200),
+      max_backoff: Duration::from_secs(5),
+    }
+  }
+}
+
+/// A version as returned by `get_latest` and `get_version`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Version {
+  pub version: u64,
+  pub hash: B256,
+  pub timestamp: u64,
+  pub publisher: Address,
+  pub block_number: u64,
+  pub artifact_uri: String,
+  pub hash_algorithm: u8,
+  pub config_hash: B256,
+}
+
+/// Registry events the client decodes; other logs are skipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryEvent {
+  TaskRegistered(TaskRegistered),
+  VersionPublished(VersionPublished),
+  VersionYanked(VersionYanked),
+  VersionPinned(VersionPinned),
+  PinnedVersionYanked(PinnedVersionYanked),
+}
+
+impl RegistryEvent {
+  pub fn decode(log: &Log) -> Option<Self> {
+    let topic = *log.data.topics().first()?;
+    let event = match topic {
+      TaskRegistered::SIGNATURE_HASH => Self::TaskRegistered(TaskRegistered::decode_log_data(&log.data, true).ok()?),
+      VersionPublished::SIGNATURE_HASH => {
+        Self::VersionPublished(VersionPublished::decode_log_data(&log.data, true).ok()?)
+      }
+      VersionYanked::SIGNATURE_HASH => Self::VersionYanked(VersionYanked::decode_log_data(&log.data, true).ok()?),
+      VersionPinned::SIGNATURE_HASH => Self::VersionPinned(VersionPinned::decode_log_data(&log.data, true).ok()?),
+      PinnedVersionYanked::SIGNATURE_HASH => {
+        Self::PinnedVersionYanked(PinnedVersionYanked::decode_log_data(&log.data, true).ok()?)
+      }
+      _ => return None,
+    };
+    Some(event)
+  }
+}
+
+/// Typed access to a deployed registry.
+pub struct Client<T> {
+  transport: T,
+  address: Address,
+  retry: RetryPolicy,
+}
+
+impl<T: Transport> Client<T> {
+  pub fn new(transport: T, address: Address) -> Self {
+    Client {
+      transport,
+      address,
+      retry: RetryPolicy::default(),
+    }
+  }
+
+  pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+    self.retry = retry;
+    self
+  }
+
+  pub fn address(&self) -> Address {
+    self.address
+  }
+
+  pub fn transport(&self) -> &T {
+    &self.transport
+  }
+
+  pub fn get_latest(&self, task_id: &str) -> Result<Version, Error<T::Error>> {
+    let r = self.view(IRegistry::getLatestCall {
+      task_id: task_id.into(),
+    })?;
+    Ok(Version {
+      version: r._0,
+      hash: r._1,
+      timestamp: r._2,
+      publisher: r._3,
+      block_number: r._4,
+      artifact_uri: r._5,
+      hash_algorithm: r._6,
+      config_hash: r._7,
+    })
+  }
+
+  pub fn get_version(&self, task_id: &str, version: u64) -> Result<Version, Error<T::Error>> {
+    let r = self.view(IRegistry::getVersionCall {
+      task_id: task_id.into(),
+      version,
+    })?;
+    Ok(Version {
+      version: r._0,
+      hash: r._1,
+      timestamp: r._2,
+      publisher: r._3,
+      block_number: r._4,
+      artifact_uri: r._5,
+      hash_algorithm: r._6,
+      config_hash: r._7,
+    })
+  }
+
+  /// Up to `limit` versions starting at `from_version`, oldest first.
+  pub fn get_versions_range(
+    &self,
+    task_id: &str,
+    from_version: u64,
+    limit: u64,
+  ) -> Result<Vec<Version>, Error<T::Error>> {
+    let r = self.view(IRegistry::getVersionsRangeCall {
+      task_id: task_id.into(),
+      from_version,
+      limit,
+    })?;
+    Ok(
+      r._0
+        .into_iter()
+        .map(
+          |(version, hash, timestamp, publisher, block_number, artifact_uri, hash_algorithm, config_hash)| Version {
+            version,
+            hash,
+            timestamp,
+            publisher,
+            block_number,
+            artifact_uri,
+            hash_algorithm,
+            config_hash,
+          },
+        )
+        .collect(),
+    )
+  }
+
+  pub fn get_version_count(&self, task_id: &str) -> Result<u64, Error<T::Error>> {
+    let r = self.view(IRegistry::getVersionCountCall {
+      task_id: task_id.into(),
+    })?;
+    Ok(r._0)
+  }
+
+  /// Returns `(registration_fee, publish_fee)` in wei.
+  pub fn fees(&self) -> Result<(U256, U256), Error<T::Error>> {
+    let r = self.view(IRegistry::feesCall {})?;
+    Ok((r._0, r._1))
+  }
+
+  /// Writes `account` has made to the task; see `RetryPolicy` for why this matters.
+  pub fn nonce_of(&self, task_id: &str, account: Address) -> Result<u64, Error<T::Error>> {
+    let r = self.view(IRegistry::nonceOfCall {
+      task_id: task_id.into(),
+      account,
+    })?;
+    Ok(r._0)
+  }
+
+  /// Registers a task, attaching `fee` wei.
+  pub fn register_task(&self, task_id: &str, fee: U256) -> Result<B256, Error<T::Error>> {
+    self.submit(
+      IRegistry::registerTaskCall {
+        task_id: task_id.into(),
+      },
+      fee,
+    )
+  }
+
+  /// Publishes `hash` as the task's next version, attaching `fee` wei.
+  pub fn publish(&self, task_id: &str, hash: B256, fee: U256) -> Result<B256, Error<T::Error>> {
+    self.submit(
+      IRegistry::publishNewVersionCall {
+        task_id: task_id.into(),
+        hash,
+      },
+      fee,
+    )
+  }
+
+  pub fn publish_with_uri(
+    &self,
+    task_id: &str,
+    hash: B256,
+    artifact_uri: &str,
+    fee: U256,
+  ) -> Result<B256, Error<T::Error>> {
+    self.submit(
+      IRegistry::publishNewVersionWithUriCall {
+        task_id: task_id.into(),
+        hash,
+        artifact_uri: artifact_uri.into(),
+      },
+      fee,
+    )
+  }
+
+  /// Follows registry events from `from_block` onwards.
+  pub fn events(&self, from_block: u64) -> EventStream<'_, T> {
+    EventStream {
+      client: self,
+      next_block: from_block,
+    }
+  }
+
+  fn view<C: SolCall>(&self, call: C) -> Result<C::Return, Error<T::Error>> {
+    let input = Bytes::from(call.abi_encode());
+    let output = self.with_retries(|transport| transport.call(self.address, input.clone()))?;
+    C::abi_decode_returns(&output, true).map_err(Error::Decode)
+  }
+
+  fn submit<C: SolCall>(&self, call: C, value: U256) -> Result<B256, Error<T::Error>> {
+    let input = Bytes::from(call.abi_encode());
+    self
+      .transport
+      .send(self.address, input, value)
+      .map_err(|error| self.classify(error))
+  }
+
+  fn with_retries<R>(&self, mut op: impl FnMut(&T) -> Result<R, T::Error>) -> Result<R, Error<T::Error>> {
+    let mut backoff = self.retry.initial_backoff;
+    let mut attempt = 1;
+    loop {
+      match op(&self.transport) {
+        Ok(value) => return Ok(value),
+        Err(error) if attempt < self.retry.max_attempts && T::is_transient(&error) => {
+          thread::sleep(backoff);
+          backoff = (backoff * 2).min(self.retry.max_backoff);
+          attempt += 1;
+        }
+        Err(error) => return Err(self.classify(error)),
+      }
+    }
+  }
+
+  /// Surfaces registry reverts as `Error::Reverted`; anything else stays a
+  /// transport error.
+  fn classify(&self, error: T::Error) -> Error<T::Error> {
+    match T::revert_data(&error).and_then(|data| IRegistry::IRegistryErrors::abi_decode(data, true).ok()) {
+      Some(reverted) => Error::Reverted(reverted),
+      None => Error::Transport(error),
+    }
+  }
+}
+
+/// Polling cursor over registry events, created by `Client::events`.
+pub struct EventStream<'a, T> {
+  client: &'a Client<T>,
+  next_block: u64,
+}
+
+impl<T: Transport> EventStream<'_, T> {
+  /// Returns the events emitted since the last poll, up to the chain head.
+  pub fn poll(&mut self) -> Result<Vec<RegistryEvent>, Error<T::Error>> {
+    let head = self.client.with_retries(|transport| transport.block_number())?;
+    if head < self.next_block {
+      return Ok(Vec::new());
+    }
+    let address = self.client.address;
+    let from_block = self.next_block;
+    let logs = self
+      .client
+      .with_retries(|transport| transport.logs(address, from_block, head))?;
+    self.next_block = head + 1;
+    Ok(logs.iter().filter_map(|raw| RegistryEvent::decode(&raw.log)).collect())
+  }
+
+  /// First block the next `poll` will read.
+  pub fn next_block(&self) -> u64 {
+    self.next_block
+  }
+}
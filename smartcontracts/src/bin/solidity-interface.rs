@@ -0,0 +1,5 @@
+//! Prints the registry's Solidity interface; see `zkMLTrack::interface`.
+
+fn main() {
+  print!("{}", zkMLTrack::interface::solidity_interface("MIT", "pragma solidity ^0.8.23;"));
+}
@@ -0,0 +1,54 @@
+//! ERC-2771 meta-transactions: a trusted forwarder relays calls for users and
+//! appends the original sender's address to the calldata.
+//!
+//! The entrypoint strips that suffix before routing, since the ABI decoder
+//! rejects trailing bytes, and `msg_sender` then reports the relayed account.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::Address;
+use stylus_sdk::msg;
+
+// Each call runs in a fresh instance, so this only ever holds the current
+// call's sender. Tests share threads across calls and reset it in `host`.
+#[cfg(not(test))]
+static mut FORWARDED_SENDER: Option<Address> = None;
+
+#[cfg(test)]
+std::thread_local! {
+  static FORWARDED_SENDER: core::cell::Cell<Option<Address>> = const { core::cell::Cell::new(None) };
+}
+
+#[cfg_attr(feature = "interface", allow(dead_code))]
+fn set(sender: Option<Address>) {
+  #[cfg(not(test))]
+  unsafe {
+    FORWARDED_SENDER = sender;
+  }
+  #[cfg(test)]
+  FORWARDED_SENDER.with(|cell| cell.set(sender));
+}
+
+/// The account `trusted` relayed the current call for, if it did.
+pub fn forwarded_sender() -> Option<Address> {
+  #[cfg(not(test))]
+  return unsafe { FORWARDED_SENDER };
+  #[cfg(test)]
+  FORWARDED_SENDER.with(|cell| cell.get())
+}
+
+/// Removes the sender suffix from a call made by `trusted` and records the
+/// sender. A selector followed by an address is the shortest relayed call;
+/// anything shorter is taken to come from the forwarder itself.
+#[cfg_attr(feature = "interface", allow(dead_code))]
+pub fn unwrap(mut input: Vec<u8>, trusted: Address) -> Vec<u8> {
+  let relayed = trusted != Address::ZERO && msg::sender() == trusted && input.len() >= 24;
+  let sender = relayed.then(|| Address::from_slice(&input.split_off(input.len() - 20)));
+  set(sender);
+  input
+}
+
+/// The account behind the current call: the relayed sender for calls through
+/// the trusted forwarder, `msg::sender()` otherwise.
+pub fn msg_sender() -> Address {
+  forwarded_sender().unwrap_or_else(msg::sender)
+}
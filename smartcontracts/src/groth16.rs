@@ -0,0 +1,117 @@
+//! Groth16 verification over BN254 through the EVM's `ecAdd`, `ecMul` and
+//! `ecPairing` precompiles (EIP-196/197).
+//!
+//! Points use the precompiles' big-endian encoding: G1 is `x || y`, G2 is
+//! `x_imag || x_real || y_imag || y_real`, each coordinate one 32-byte word.
+//! A verification key is `alpha (G1) || beta (G2) || gamma (G2) || delta (G2) || IC[0..=n] (G1)`
+//! and a proof is `A (G1) || B (G2) || C (G1)`.
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::{address, Address, U256};
+use stylus_sdk::call::{self, Call};
+
+const EC_ADD: Address = address!("0000000000000000000000000000000000000006");
+const EC_MUL: Address = address!("0000000000000000000000000000000000000007");
+const EC_PAIRING: Address = address!("0000000000000000000000000000000000000008");
+
+const G1_LEN: usize = 64;
+const G2_LEN: usize = 128;
+
+/// Length of a key's fixed part, before the `IC` points.
+pub const VK_FIXED_LEN: usize = G1_LEN + 3 * G2_LEN;
+pub const PROOF_LEN: usize = 2 * G1_LEN + G2_LEN;
+
+/// BN254 base field modulus, used to negate G1 points.
+const FIELD_MODULUS: U256 = U256::from_limbs([
+  0x3c208c16d87cfd47,
+  0x97816a916871ca8d,
+  0xb85045b68181585d,
+  0x30644e72e131a029,
+]);
+
+/// BN254 scalar field order; public inputs must be reduced below it.
+pub const SCALAR_FIELD: U256 = U256::from_limbs([
+  0x43e1f593f0000001,
+  0x2833e84879b97091,
+  0xb85045b68181585d,
+  0x30644e72e131a029,
+]);
+
+/// Number of public inputs `vk` accepts, or `None` if it is malformed.
+pub fn input_count(vk: &[u8]) -> Option<usize> {
+  let ic_len = vk.len().checked_sub(VK_FIXED_LEN)?;
+  if ic_len < G1_LEN || ic_len % G1_LEN != 0 {
+    return None;
+  }
+  Some(ic_len / G1_LEN - 1)
+}
+
+/// Returns whether `proof` is valid for `public_inputs` under `vk`. Malformed
+/// input and precompile failures count as an invalid proof.
+pub fn verify(vk: &[u8], proof: &[u8], public_inputs: &[U256]) -> bool {
+  if proof.len() != PROOF_LEN
+    || input_count(vk) != Some(public_inputs.len())
+    || public_inputs.iter().any(|input| *input >= SCALAR_FIELD)
+  {
+    return false;
+  }
+
+  let Some(vk_x) = linear_combination(&vk[VK_FIXED_LEN..], public_inputs) else {
+    return false;
+  };
+  let Some(neg_a) = negate_g1(&proof[..G1_LEN]) else {
+    return false;
+  };
+
+  let (alpha, rest) = vk.split_at(G1_LEN);
+  let (beta, rest) = rest.split_at(G2_LEN);
+  let (gamma, rest) = rest.split_at(G2_LEN);
+  let delta = &rest[..G2_LEN];
+  let b = &proof[G1_LEN..G1_LEN + G2_LEN];
+  let c = &proof[G1_LEN + G2_LEN..];
+
+  // e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+  let mut input = Vec::with_capacity(4 * (G1_LEN + G2_LEN));
+  for (g1, g2) in [(&neg_a[..], b), (alpha, beta), (&vk_x[..], gamma), (c, delta)] {
+    input.extend_from_slice(g1);
+    input.extend_from_slice(g2);
+  }
+  match call::static_call(Call::new(), EC_PAIRING, &input) {
+    Ok(output) => output.len() == 32 && U256::from_be_slice(&output) == U256::from(1),
+    Err(_) => false,
+  }
+}
+
+/// `IC[0] + sum(inputs[i] * IC[i + 1])`.
+fn linear_combination(ic: &[u8], inputs: &[U256]) -> Option<Vec<u8>> {
+  let mut acc = ic[..G1_LEN].to_vec();
+  for (point, scalar) in ic[G1_LEN..].chunks(G1_LEN).zip(inputs) {
+    let mut mul = Vec::with_capacity(G1_LEN + 32);
+    mul.extend_from_slice(point);
+    mul.extend_from_slice(&scalar.to_be_bytes::<32>());
+    let term = precompile(EC_MUL, &mul)?;
+
+    let mut add = acc;
+    add.extend_from_slice(&term);
+    acc = precompile(EC_ADD, &add)?;
+  }
+  Some(acc)
+}
+
+fn negate_g1(point: &[u8]) -> Option<Vec<u8>> {
+  let x = U256::from_be_slice(&point[..32]);
+  let y = U256::from_be_slice(&point[32..]);
+  if x >= FIELD_MODULUS || y >= FIELD_MODULUS {
+    return None;
+  }
+  let neg_y = if y.is_zero() { y } else { FIELD_MODULUS - y };
+
+  let mut out = point[..32].to_vec();
+  out.extend_from_slice(&neg_y.to_be_bytes::<32>());
+  Some(out)
+}
+
+fn precompile(to: Address, input: &[u8]) -> Option<Vec<u8>> {
+  let output = call::static_call(Call::new(), to, input).ok()?;
+  (output.len() == G1_LEN).then_some(output)
+}
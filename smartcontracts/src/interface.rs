@@ -0,0 +1,185 @@
+//! `IZkMLTrackRegistry.sol`, a Solidity interface for contracts and Foundry
+//! tests that call the registry.
+//!
+//! Functions come from the Stylus exporter, events and named errors from the
+//! `sol!` declarations in the crate root, which the exporter leaves out or
+//! strips of parameter names. The exporter also writes nested tuple types,
+//! which Solidity has no syntax for; each becomes a struct named after its
+//! function, with fields `f0`, `f1`, ... since the tuples carry no names.
+
+use core::fmt::{self, Write};
+use stylus_sdk::abi::export::GenerateAbi;
+
+use crate::{Registry, EVENTS_AND_ERRORS};
+
+pub const NAME: &str = "IZkMLTrackRegistry";
+
+struct Functions;
+
+impl fmt::Display for Functions {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    <Registry as GenerateAbi>::fmt_abi(f)
+  }
+}
+
+/// Full source of the interface file.
+pub fn solidity_interface(license: &str, pragma: &str) -> String {
+  let mut structs = Vec::new();
+  let functions: Vec<String> = Functions
+    .to_string()
+    .lines()
+    .map(str::trim)
+    .filter(|line| line.starts_with("function "))
+    .map(|line| rewrite_function(line, &mut structs))
+    .collect();
+
+  let mut out = String::new();
+  let _ = writeln!(out, "// SPDX-License-Identifier: {license}");
+  let _ = writeln!(out, "{pragma}");
+  let _ = writeln!(out);
+  let _ = writeln!(out, "// Generated by `cargo run --features export-abi --bin solidity-interface`; do not edit.");
+  let _ = writeln!(out, "interface {NAME} {{");
+  for (name, fields) in &structs {
+    let _ = writeln!(out, "    struct {name} {{");
+    for (i, field) in fields.iter().enumerate() {
+      let _ = writeln!(out, "        {field} f{i};");
+    }
+    let _ = writeln!(out, "    }}");
+    let _ = writeln!(out);
+  }
+  for declaration in declarations(EVENTS_AND_ERRORS) {
+    let _ = writeln!(out, "    {declaration}");
+  }
+  for function in functions {
+    let _ = writeln!(out);
+    let _ = writeln!(out, "    {function}");
+  }
+  let _ = writeln!(out, "}}");
+  out
+}
+
+/// Splits the stringified `sol!` input into one tidied declaration per line.
+fn declarations(source: &str) -> Vec<String> {
+  let source = source.split_whitespace().collect::<Vec<_>>().join(" ");
+  let source = source
+    .replace(" ,", ",")
+    .replace("( ", "(")
+    .replace(" )", ")")
+    .replace(" (", "(")
+    .replace(" ;", ";");
+  source
+    .split_inclusive(';')
+    .map(str::trim)
+    .filter(|declaration| !declaration.is_empty())
+    .map(String::from)
+    .collect()
+}
+
+/// Replaces tuple types in one exported function with generated structs.
+fn rewrite_function(line: &str, structs: &mut Vec<(String, Vec<String>)>) -> String {
+  let name = &line["function ".len()..line.find('(').unwrap_or(line.len())];
+  let prefix = {
+    let mut chars = name.chars();
+    chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+  };
+  let mut tuples = 0;
+  let mut next_name = || {
+    tuples += 1;
+    if tuples == 1 { format!("{prefix}Tuple") } else { format!("{prefix}Tuple{tuples}") }
+  };
+
+  let open = line.find('(').unwrap_or(line.len());
+  let close = matching_paren(line, open);
+  let params = rewrite_list(&line[open + 1..close], structs, &mut next_name);
+  let mut rest = &line[close + 1..];
+
+  let mut out = format!("{}({params})", &line[..open]);
+  if let Some(at) = rest.find(" returns ") {
+    out.push_str(&rest[..at]);
+    let returns = rest[at + " returns ".len()..].trim_end_matches(';');
+    // Array-of-tuple returns come out without their enclosing parentheses.
+    let returns = if matching_paren(returns, 0) + 1 == returns.len() {
+      String::from(&returns[1..returns.len() - 1])
+    } else {
+      String::from(returns)
+    };
+    let returns = rewrite_list(&returns, structs, &mut next_name);
+    out.push_str(&format!(" returns ({returns});"));
+  } else {
+    if let Some(stripped) = rest.strip_suffix(';') {
+      rest = stripped;
+    }
+    out.push_str(rest);
+    out.push(';');
+  }
+  out
+}
+
+/// Rewrites a comma-separated parameter list, pulling tuples out into structs.
+fn rewrite_list(
+  list: &str,
+  structs: &mut Vec<(String, Vec<String>)>,
+  next_name: &mut impl FnMut() -> String,
+) -> String {
+  split_top_level(list)
+    .into_iter()
+    .map(|param| {
+      if !param.starts_with('(') {
+        return param;
+      }
+      let close = matching_paren(&param, 0);
+      let fields = split_top_level(&param[1..close])
+        .into_iter()
+        .map(|field| String::from(field.trim_end_matches(" memory").trim_end_matches(" calldata")))
+        .collect();
+      let name = next_name();
+      structs.push((name.clone(), fields));
+      let rest = &param[close + 1..];
+      if rest.contains("memory") || rest.contains("calldata") {
+        format!("{name}{rest}")
+      } else {
+        format!("{name} memory{rest}")
+      }
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+fn split_top_level(list: &str) -> Vec<String> {
+  let mut items = Vec::new();
+  let (mut depth, mut start) = (0usize, 0);
+  for (i, c) in list.char_indices() {
+    match c {
+      '(' => depth += 1,
+      ')' => depth = depth.saturating_sub(1),
+      ',' if depth == 0 => {
+        items.push(String::from(list[start..i].trim()));
+        start = i + 1;
+      }
+      _ => {}
+    }
+  }
+  let last = list[start..].trim();
+  if !last.is_empty() {
+    items.push(String::from(last));
+  }
+  items
+}
+
+/// Index of the `)` closing the `(` at `open`, or the end of `s`.
+fn matching_paren(s: &str, open: usize) -> usize {
+  let mut depth = 0usize;
+  for (i, c) in s.char_indices().skip_while(|(i, _)| *i < open) {
+    match c {
+      '(' => depth += 1,
+      ')' => {
+        depth -= 1;
+        if depth == 0 {
+          return i;
+        }
+      }
+      _ => {}
+    }
+  }
+  s.len()
+}
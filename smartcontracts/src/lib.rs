@@ -1,90 +1,8126 @@
-#![cfg_attr(not(feature = "export-abi"), no_main)]
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+#![allow(non_snake_case)]
+// The `export-abi` expansion of the router nests deeper with each public method.
+#![recursion_limit = "1024"]
 extern crate alloc;
 
+mod forwarder;
+mod groth16;
+#[cfg(feature = "export-abi")]
+pub mod interface;
+mod merkle;
+mod poseidon;
+#[cfg(feature = "interface")]
+mod registry_interface;
+#[cfg(test)]
+mod tests;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+use stylus_sdk::abi::Bytes;
 use stylus_sdk::prelude::*;
 use stylus_sdk::tx::{origin};
-use stylus_sdk::{msg};
-use stylus_sdk::alloy_primitives::{Address, U8, U64};
-use stylus_sdk::storage::{StorageMap, StorageU64, StorageAddress};
+use stylus_sdk::{call, contract, crypto, evm, function_selector, msg};
+use stylus_sdk::keccak_const::Keccak256;
+use stylus_sdk::alloy_primitives::{address, Address, FixedBytes, B256, U8, U16, U32, U64, U256};
+use alloy_sol_types::{sol, SolValue};
+use stylus_sdk::storage::{
+  StorageAddress, StorageArray, StorageBool, StorageBytes, StorageFixedBytes, StorageGuard, StorageGuardMut, StorageMap,
+  StorageString, StorageU16, StorageU256, StorageU32, StorageU64, StorageU8, StorageVec,
+};
 use stylus_sdk::block;
 
+use forwarder::msg_sender;
+#[cfg(feature = "interface")]
+pub use registry_interface::IRegistry;
+
+/// `sol!`, additionally keeping the declarations' source for `interface`.
+macro_rules! sol_with_source {
+  ($($declarations:tt)*) => {
+    sol! { $($declarations)* }
+
+    #[cfg(feature = "export-abi")]
+    const EVENTS_AND_ERRORS: &str = stringify!($($declarations)*);
+  };
+}
+
+sol_with_source! {
+  event TaskRegistered(string task_id, address indexed registrar, uint64 timestamp);
+  event VersionPublished(
+    string task_id,
+    uint64 indexed version,
+    bytes32 hash,
+    address indexed publisher,
+    uint64 timestamp,
+    string artifact_uri,
+    uint8 hash_algorithm,
+    uint64 activates_at,
+    bytes32 config_hash,
+    uint64 operation_nonce
+  );
+  event OwnershipChanged(address indexed previous_owner, address indexed new_owner);
+  event OwnershipTransferStarted(address indexed previous_owner, address indexed new_owner);
+  event TaskAdminChanged(string task_id, address indexed previous_admin, address indexed new_admin);
+  event PublisherGranted(string task_id, address indexed publisher, address indexed granted_by);
+  event PublisherRevoked(string task_id, address indexed publisher, address indexed revoked_by);
+  event Paused(address indexed account);
+  event Unpaused(address indexed account);
+  event VersionCommitted(string task_id, bytes32 indexed commitment, address indexed committer);
+  event ActivationDelayChanged(string task_id, uint64 activation_delay);
+  event PendingVersionCancelled(string task_id, uint64 indexed version);
+  event Rollback(string task_id, uint64 indexed from_version, uint64 indexed to_version, address indexed admin);
+  event TagSet(string task_id, string tag, uint64 indexed version);
+  event TaskFrozen(string task_id, address indexed frozen_by);
+  event TaskArchived(string task_id, string reason, address indexed archived_by);
+  event TaskUnarchived(string task_id, address indexed unarchived_by);
+  event FeesUpdated(uint256 registration_fee, uint256 publish_fee);
+  event FeesWithdrawn(address indexed to, uint256 amount);
+  event GatingUpdated(string task_id, bool gated, uint256 price_per_second);
+  event Subscribed(string task_id, address indexed subscriber, uint64 expires_at, uint256 paid);
+  event SubscriptionRevenueWithdrawn(string task_id, address indexed to, uint256 amount);
+  event VerificationKeySet(string task_id, uint64 indexed version, bytes32 vk_hash, string vk_uri);
+  event Groth16KeySet(string task_id, uint64 indexed version, bytes32 vk_digest);
+  event InferenceVerified(
+    string task_id,
+    uint64 indexed version,
+    bytes32 indexed inference_id,
+    address prover,
+    bool valid
+  );
+  event VerifierRouterChanged(address indexed previous_router, address indexed new_router);
+  event TrustedForwarderChanged(address indexed previous_forwarder, address indexed new_forwarder);
+  event ImageIdSet(string task_id, uint64 indexed version, bytes32 image_id);
+  event ReceiptVerified(
+    string task_id,
+    uint64 indexed version,
+    bytes32 indexed journal_digest,
+    address prover,
+    bool valid
+  );
+  event AggregationKeySet(string task_id, uint64 indexed version, bytes32 vk_digest);
+  event TrainingKeySet(string task_id, bytes32 vk_digest);
+  event TrainingVerified(string task_id, uint64 indexed version, address indexed prover, bytes32 inputs_hash);
+  event BatchVerified(
+    string task_id,
+    uint64 indexed version,
+    bytes32 indexed batch_root,
+    uint64 batch_size,
+    address prover,
+    bool valid
+  );
+  event ChallengePeriodChanged(string task_id, uint64 challenge_period);
+  event ChallengeBondChanged(uint256 challenge_bond);
+  event RatingDepositChanged(uint256 rating_deposit);
+  event VersionRated(string task_id, uint64 indexed version, address indexed rater, uint8 score, uint256 deposit);
+  event RatingWithdrawn(string task_id, uint64 indexed version, address indexed rater, uint256 deposit);
+  event ArbiterChanged(address indexed previous_arbiter, address indexed new_arbiter);
+  event VersionChallenged(
+    string task_id,
+    uint64 indexed version,
+    address indexed challenger,
+    bytes32 evidence_hash,
+    uint256 bond
+  );
+  event ChallengeResolved(string task_id, uint64 indexed version, bool upheld, address indexed resolver);
+  event StakingUpdated(uint256 min_stake, uint64 unstake_cooldown);
+  event Staked(address indexed account, uint256 amount, uint256 total);
+  event UnstakeRequested(address indexed account, uint64 unlock_at);
+  event Unstaked(address indexed account, uint256 amount);
+  event StakeSlashed(address indexed account, uint256 amount, string task_id, uint64 version);
+  event AuditorGranted(address indexed auditor);
+  event AuditorRevoked(address indexed auditor);
+  event VersionAudited(
+    string task_id,
+    uint64 indexed version,
+    address indexed auditor,
+    string attestation_type,
+    bytes32 report_hash
+  );
+  event ParentLinked(string task_id, uint64 indexed version, string parent_task_id, uint64 parent_version);
+  event DatasetCommitted(string dataset_id, uint64 indexed revision, bytes32 hash, address indexed committer);
+  event DatasetLinked(string task_id, uint64 indexed version, string dataset_id, uint64 revision);
+  event DependencyLinked(string task_id, uint64 indexed version, string dependency_task_id, uint64 dependency_version);
+  event EvaluatorGranted(string task_id, address indexed evaluator);
+  event EvaluatorRevoked(string task_id, address indexed evaluator);
+  event MetricRecorded(string task_id, uint64 indexed version, string key, uint256 value, address indexed recorder);
+  event ModelCardSet(
+    string task_id,
+    uint64 indexed version,
+    uint8 framework,
+    uint64 parameter_count,
+    bytes32 io_schema_hash,
+    uint16 license_code
+  );
+  event TaskTransferred(string task_id, address indexed from, address indexed to);
+  event TaskApproval(string task_id, address indexed owner, address indexed approved);
+  event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+  event PaymentTokenUpdated(address indexed token, uint256 registration_fee, uint256 publish_fee);
+  event TokenPaymentReceived(address indexed token, address indexed from, uint256 amount);
+  event TokenFeesWithdrawn(address indexed token, address indexed to, uint256 amount);
+  event TokenSubscriptionPriceUpdated(string task_id, uint256 price_per_second);
+  event TokenSubscriptionRevenueWithdrawn(string task_id, address indexed token, address indexed to, uint256 amount);
+  event RoyaltyUpdated(string task_id, string parent_task_id, uint16 royalty_bps);
+  event RoyaltyPaid(string task_id, string parent_task_id, address indexed token, uint256 amount);
+  event JobTimeoutChanged(uint64 job_timeout);
+  event InferenceRequested(
+    uint64 indexed job_id,
+    string task_id,
+    uint64 version,
+    address indexed requester,
+    bytes32 input_commitment,
+    uint256 payment,
+    uint64 deadline
+  );
+  event InferencePriceSet(string task_id, uint64 indexed version, uint256 price);
+  event RefundClaimed(string task_id, uint64 indexed version, address indexed consumer, uint256 amount);
+  event CircuitBreakerChanged(string task_id, uint32 threshold, uint64 window, bool halt_task);
+  event CircuitBreakerTripped(string task_id, uint64 indexed version, uint32 failures, bool task_halted);
+  event CircuitBreakerReset(string task_id, uint64 indexed version, address indexed admin);
+  event ExternalVerifierChanged(string task_id, address indexed previous_verifier, address indexed new_verifier);
+  event InferenceFulfilled(uint64 indexed job_id, address indexed prover, bytes32 output_commitment);
+  event InferenceRefunded(uint64 indexed job_id, address indexed requester, uint256 amount);
+  event OracleGranted(address indexed oracle);
+  event OracleRevoked(address indexed oracle);
+  event EvaluationRequested(
+    uint64 indexed request_id,
+    string task_id,
+    uint64 version,
+    address indexed requester,
+    bytes32 suite_hash
+  );
+  event EvaluationFulfilled(
+    uint64 indexed request_id,
+    string task_id,
+    uint64 version,
+    address indexed oracle,
+    bytes32 results_hash
+  );
+  event L1CheckpointTargetChanged(address indexed previous_target, address indexed new_target);
+  event CheckpointSentToL1(
+    bytes32 digest,
+    uint64 task_count,
+    bytes32 version_root,
+    bytes32 state_root,
+    uint256 indexed message_id
+  );
+  event L1ControllerChanged(address indexed previous_controller, address indexed new_controller);
+  event NamespaceAdminChanged(string namespace, address indexed previous_admin, address indexed new_admin);
+  event AliasAdded(string alias, string task_id);
+  event StrictModeChanged(string task_id, bool enabled);
+  event LicenseAssigned(
+    string task_id,
+    uint64 indexed version,
+    string license,
+    bool commercial_use,
+    bool redistribution,
+    bool fine_tuning
+  );
+  event SemverAssigned(string task_id, uint64 indexed version, uint32 major, uint32 minor, uint32 patch);
+  event MaxAgeChanged(string task_id, uint64 max_age);
+  event VersionPinned(string task_id, uint64 indexed version, address indexed consumer);
+  event VersionUnpinned(string task_id, uint64 indexed version, address indexed consumer);
+  event PinnedVersionYanked(string task_id, uint64 indexed version, uint64 pin_count);
+  event EncryptedArtifactSet(string task_id, uint64 indexed version, string encrypted_uri, bytes32 key_commitment);
+  event AccessGranted(string task_id, uint64 indexed version, address indexed consumer, bytes32 wrapped_key_hash);
+  event StateImported(uint64 task_count, uint64 version_count);
+  event StateImportClosed();
+  event GuardianChanged(address indexed previous_guardian, address indexed new_guardian);
+  event ApproverGranted(string task_id, address indexed approver);
+  event ApproverRevoked(string task_id, address indexed approver);
+  event ApprovalPolicyChanged(string task_id, uint32 threshold, uint64 proposal_ttl);
+  event VersionProposed(string task_id, uint64 indexed proposal_id, bytes32 hash, address indexed proposer, uint64 expires_at);
+  event VersionApproved(string task_id, uint64 indexed proposal_id, address indexed approver, uint32 approvals);
+  event CheckpointRecorded(uint64 indexed index, bytes32 state_root, uint64 task_count, uint64 block_number);
+  event CheckpointIntervalChanged(uint64 interval);
+  event PublishCooldownChanged(string task_id, uint64 cooldown);
+  event CategoryAdded(string category);
+  event CategoryRetired(string category);
+  event TaskCategorized(string task_id, string category);
+  event VersionDeprecated(string task_id, uint64 indexed version, bytes32 reason_hash, uint64 sunset_at);
+  event VersionAuthored(string task_id, uint64 indexed version, address indexed author);
+  event VersionDeltaCommitted(string task_id, uint64 indexed version, bytes32 delta_hash);
+  event RoundOpened(string task_id, uint64 indexed round_id, address indexed coordinator);
+  event UpdateCommitted(string task_id, uint64 indexed round_id, address indexed client, bytes32 commitment);
+  event RoundFinalized(
+    string task_id,
+    uint64 indexed round_id,
+    bytes32 aggregate_hash,
+    bytes32 proof_hash,
+    uint64 participant_count
+  );
+  event CapabilityIssued(string task_id, address indexed consumer, uint64 expires_at);
+  event CapabilityRevoked(string task_id, address indexed consumer);
+  event KeeperBountyChanged(uint256 keeper_bounty);
+  event KeeperRewarded(address indexed keeper, uint256 amount);
+  event SubscriptionExpired(string task_id, address indexed subscriber);
+  event AdversarialKeySet(string task_id, bytes32 vk_digest);
+  event BountyFunded(
+    string task_id,
+    uint64 indexed version,
+    address indexed funder,
+    uint256 amount,
+    uint256 total,
+    uint64 deadline
+  );
+  event BountyRefunded(string task_id, uint64 indexed version, address indexed funder, uint256 amount);
+  event AdversarialExampleFound(
+    string task_id,
+    uint64 indexed version,
+    address indexed finder,
+    bytes32 input_commitment,
+    uint256 bounty
+  );
+  event HookAdded(string task_id, address indexed hook, uint64 gas_limit);
+  event HookRemoved(string task_id, address indexed hook);
+  event HookFailed(string task_id, uint64 indexed version, address indexed hook);
+  event VersionsPruned(string task_id, uint64 pruned_through, bytes32 pruned_root);
+  event RecoveryGuardianChanged(address indexed guardian, bool enabled);
+  event RecoveryPolicyChanged(uint32 threshold, uint64 delay);
+  event RecoveryProposed(address indexed new_owner, address indexed proposer);
+  event RecoveryApproved(address indexed new_owner, address indexed guardian, uint32 approvals, uint64 executable_at);
+  event RecoveryCancelled(address indexed new_owner);
+  event RoundClientGranted(string task_id, address indexed client);
+  event RoundClientRevoked(string task_id, address indexed client);
+  event RoyaltyOffered(string task_id, string parent_task_id, uint16 royalty_bps);
+  event MaxRoyaltyChanged(uint16 max_royalty_bps);
+  event ProfileUpdated(address indexed account, bytes32 display_name_hash, string did, bytes32 website_hash);
+  event ProfileCleared(address indexed account);
+  event VersionYanked(
+    string task_id,
+    uint64 indexed version,
+    string reason,
+    uint64 successor_version,
+    uint64 operation_nonce
+  );
+
+  error TaskNotFound(string task_id);
+  error TaskExists(string task_id);
+  error NoVersions(string task_id);
+  error VersionNotFound(string task_id, uint64 version);
+  error VersionAlreadyYanked(string task_id, uint64 version);
+  error InvalidSuccessor(string task_id, uint64 successor_version);
+  error HashNotFound(bytes32 hash);
+  error UnsupportedHashAlgorithm(uint8 hash_algorithm);
+  error CommitmentExists(string task_id, bytes32 commitment);
+  error UnknownCommitment(string task_id, bytes32 commitment);
+  error RevealTooEarly(string task_id, bytes32 commitment);
+  error VersionNotPending(string task_id, uint64 version);
+  error InvalidRollbackTarget(string task_id, uint64 version);
+  error TagNotFound(string task_id, string tag);
+  error TaskIsFrozen(string task_id);
+  error TaskIsArchived(string task_id);
+  error TaskNotArchived(string task_id);
+  error InsufficientFee(uint256 required, uint256 provided);
+  error TransferFailed(address to, uint256 amount);
+  error TaskNotGated(string task_id);
+  error SignatureExpired(uint256 deadline);
+  error InvalidSignature();
+  error AlreadyInitialized();
+  error InvalidVerificationKey(string task_id, uint64 version);
+  error VerificationKeyAlreadySet(string task_id, uint64 version);
+  error NoVerificationKey(string task_id, uint64 version);
+  error ImageIdMismatch(string task_id, uint64 version, bytes32 image_id);
+  error NoVerifierRouter();
+  error ChallengeWindowClosed(string task_id, uint64 version);
+  error ChallengeAlreadyOpen(string task_id, uint64 version);
+  error NoOpenChallenge(string task_id, uint64 version);
+  error InsufficientStake(address account, uint256 required, uint256 staked);
+  error UnstakeNotReady(uint64 unlock_at);
+  error NotAuditor(address account);
+  error DatasetNotFound(string dataset_id, uint64 revision);
+  error NotDatasetOwner(string dataset_id, address account);
+  error NotEvaluator(string task_id, address account);
+  error MetricsLengthMismatch(uint256 keys, uint256 values);
+  error MetricNotFound(string task_id, uint64 version, string key);
+  error UnsupportedFramework(uint8 framework);
+  error NotTaskApproved(string task_id, address account);
+  error InvalidRecipient(address to);
+  error TokenTransferFailed(address token, address to, uint256 amount);
+  error InvalidRoyalty(string task_id, string parent_task_id, uint16 royalty_bps);
+  error JobNotFound(uint64 job_id);
+  error JobNotOpen(uint64 job_id);
+  error JobExpired(uint64 job_id, uint64 deadline);
+  error JobNotExpired(uint64 job_id, uint64 deadline);
+  error InvalidJobFulfillment(uint64 job_id);
+  error NotOracle(address account);
+  error EvaluationRequestNotFound(uint64 request_id);
+  error EvaluationAlreadyFulfilled(uint64 request_id);
+  error NoCheckpointTarget();
+  error CheckpointFailed();
+  error NotL1Controller(address account);
+  error TaskIdNotFound(bytes32 id);
+  error NotNamespaceAdmin(string namespace, address account);
+  error AliasExists(string alias);
+  error DuplicateHash(string task_id, bytes32 hash);
+  error NoVersionAt(string task_id, uint64 timestamp);
+  error SemverNotIncreasing(string task_id, uint32 major, uint32 minor, uint32 patch);
+  error NoVersionInMajor(string task_id, uint32 major);
+  error StaleVersion(string task_id, uint64 version, uint64 timestamp, uint64 max_age);
+  error NotEncrypted(string task_id, uint64 version);
+  error EncryptionAlreadySet(string task_id, uint64 version);
+  error InvalidStateChunk();
+  error ImportClosed();
+  error ApprovalRequired(string task_id);
+  error NotApprover(string task_id, address account);
+  error InvalidThreshold(string task_id, uint32 threshold, uint32 approver_count);
+  error ProposalNotFound(string task_id, uint64 proposal_id);
+  error ProposalClosed(string task_id, uint64 proposal_id);
+  error AlreadyApproved(string task_id, uint64 proposal_id, address approver);
+  error InvalidFieldElement(uint256 value);
+  error NotPoseidonCommitted(string task_id, uint64 version);
+  error CheckpointTooSoon(uint64 next_at);
+  error CheckpointNotFound(uint64 index);
+  error PublishCooldown(string task_id, uint64 next_at);
+  error UnknownCategory(string category);
+  error CategoryExists(string category);
+  error CategoryAlreadyAssigned(string task_id, string category);
+  error InvalidSunset(uint64 sunset_at);
+  error RoundNotFound(string task_id, uint64 round_id);
+  error RoundStillOpen(string task_id, uint64 round_id);
+  error RoundClosed(string task_id, uint64 round_id);
+  error UpdateAlreadySubmitted(string task_id, uint64 round_id, address client);
+  error NoRefundDue(string task_id, uint64 version, address account);
+  error CircuitBreakerOpen(string task_id, uint64 version);
+  error InvalidRating(uint8 score);
+  error AlreadyRated(string task_id, uint64 version, address rater);
+  error NotRated(string task_id, uint64 version, address rater);
+  error InvalidExpiry(uint64 expires_at);
+  error InvalidDuration(uint64 duration);
+  error SubscriptionNotExpired(string task_id, address subscriber);
+  error BountyClosed(string task_id, uint64 version);
+  error NotMisclassified(uint256 label, uint256 prediction);
+  error TooManyHooks(string task_id);
+  error HookNotFound(string task_id, address hook);
+  error InvalidHookGas(uint64 gas_limit);
+  error NotRecoveryGuardian(address account);
+  error InvalidRecoveryThreshold(uint32 threshold, uint32 guardian_count);
+  error RecoveryPending(address new_owner);
+  error NoRecoveryPending();
+  error RecoveryAlreadyApproved(address guardian);
+  error RecoveryNotReady(uint64 executable_at);
+  error NoTrainingData(string task_id, uint64 version);
+  error BountyKeyFrozen(string task_id);
+  error BountyNotExpired(string task_id, uint64 version, uint64 deadline);
+  error InvalidBreakerWindow(uint32 threshold, uint64 window);
+  error NotRoundClient(string task_id, address account);
+  error InvalidMaxRoyalty(uint16 max_royalty_bps);
+  error StakeUnderChallenge(address account, uint64 open_challenges);
+  error NotOwner(address account);
+  error NotPendingOwner(address account);
+  error NotTaskAdmin(string task_id, address account);
+  error NotPublisher(string task_id, address account);
+  error EnforcedPause();
+  error ExpectedPause();
+}
+
+#[derive(SolidityError)]
+pub enum RegistryError {
+  TaskNotFound(TaskNotFound),
+  TaskExists(TaskExists),
+  NoVersions(NoVersions),
+  VersionNotFound(VersionNotFound),
+  VersionAlreadyYanked(VersionAlreadyYanked),
+  InvalidSuccessor(InvalidSuccessor),
+  HashNotFound(HashNotFound),
+  UnsupportedHashAlgorithm(UnsupportedHashAlgorithm),
+  CommitmentExists(CommitmentExists),
+  UnknownCommitment(UnknownCommitment),
+  RevealTooEarly(RevealTooEarly),
+  VersionNotPending(VersionNotPending),
+  InvalidRollbackTarget(InvalidRollbackTarget),
+  TagNotFound(TagNotFound),
+  TaskIsFrozen(TaskIsFrozen),
+  TaskIsArchived(TaskIsArchived),
+  TaskNotArchived(TaskNotArchived),
+  InsufficientFee(InsufficientFee),
+  TransferFailed(TransferFailed),
+  TaskNotGated(TaskNotGated),
+  SignatureExpired(SignatureExpired),
+  InvalidSignature(InvalidSignature),
+  AlreadyInitialized(AlreadyInitialized),
+  InvalidVerificationKey(InvalidVerificationKey),
+  VerificationKeyAlreadySet(VerificationKeyAlreadySet),
+  NoVerificationKey(NoVerificationKey),
+  ImageIdMismatch(ImageIdMismatch),
+  NoVerifierRouter(NoVerifierRouter),
+  ChallengeWindowClosed(ChallengeWindowClosed),
+  ChallengeAlreadyOpen(ChallengeAlreadyOpen),
+  NoOpenChallenge(NoOpenChallenge),
+  InsufficientStake(InsufficientStake),
+  UnstakeNotReady(UnstakeNotReady),
+  NotAuditor(NotAuditor),
+  DatasetNotFound(DatasetNotFound),
+  NotDatasetOwner(NotDatasetOwner),
+  NotEvaluator(NotEvaluator),
+  MetricsLengthMismatch(MetricsLengthMismatch),
+  MetricNotFound(MetricNotFound),
+  UnsupportedFramework(UnsupportedFramework),
+  NotTaskApproved(NotTaskApproved),
+  InvalidRecipient(InvalidRecipient),
+  TokenTransferFailed(TokenTransferFailed),
+  InvalidRoyalty(InvalidRoyalty),
+  JobNotFound(JobNotFound),
+  JobNotOpen(JobNotOpen),
+  JobExpired(JobExpired),
+  JobNotExpired(JobNotExpired),
+  InvalidJobFulfillment(InvalidJobFulfillment),
+  NotOracle(NotOracle),
+  EvaluationRequestNotFound(EvaluationRequestNotFound),
+  EvaluationAlreadyFulfilled(EvaluationAlreadyFulfilled),
+  NoCheckpointTarget(NoCheckpointTarget),
+  CheckpointFailed(CheckpointFailed),
+  NotL1Controller(NotL1Controller),
+  TaskIdNotFound(TaskIdNotFound),
+  NotNamespaceAdmin(NotNamespaceAdmin),
+  AliasExists(AliasExists),
+  DuplicateHash(DuplicateHash),
+  NoVersionAt(NoVersionAt),
+  SemverNotIncreasing(SemverNotIncreasing),
+  NoVersionInMajor(NoVersionInMajor),
+  StaleVersion(StaleVersion),
+  NotEncrypted(NotEncrypted),
+  EncryptionAlreadySet(EncryptionAlreadySet),
+  InvalidStateChunk(InvalidStateChunk),
+  ImportClosed(ImportClosed),
+  ApprovalRequired(ApprovalRequired),
+  NotApprover(NotApprover),
+  InvalidThreshold(InvalidThreshold),
+  ProposalNotFound(ProposalNotFound),
+  ProposalClosed(ProposalClosed),
+  AlreadyApproved(AlreadyApproved),
+  InvalidFieldElement(InvalidFieldElement),
+  NotPoseidonCommitted(NotPoseidonCommitted),
+  CheckpointTooSoon(CheckpointTooSoon),
+  CheckpointNotFound(CheckpointNotFound),
+  PublishCooldown(PublishCooldown),
+  UnknownCategory(UnknownCategory),
+  CategoryExists(CategoryExists),
+  CategoryAlreadyAssigned(CategoryAlreadyAssigned),
+  InvalidSunset(InvalidSunset),
+  RoundNotFound(RoundNotFound),
+  RoundStillOpen(RoundStillOpen),
+  RoundClosed(RoundClosed),
+  UpdateAlreadySubmitted(UpdateAlreadySubmitted),
+  NoRefundDue(NoRefundDue),
+  CircuitBreakerOpen(CircuitBreakerOpen),
+  InvalidRating(InvalidRating),
+  AlreadyRated(AlreadyRated),
+  NotRated(NotRated),
+  InvalidExpiry(InvalidExpiry),
+  InvalidDuration(InvalidDuration),
+  SubscriptionNotExpired(SubscriptionNotExpired),
+  BountyClosed(BountyClosed),
+  NotMisclassified(NotMisclassified),
+  TooManyHooks(TooManyHooks),
+  HookNotFound(HookNotFound),
+  InvalidHookGas(InvalidHookGas),
+  NotRecoveryGuardian(NotRecoveryGuardian),
+  InvalidRecoveryThreshold(InvalidRecoveryThreshold),
+  RecoveryPending(RecoveryPending),
+  NoRecoveryPending(NoRecoveryPending),
+  RecoveryAlreadyApproved(RecoveryAlreadyApproved),
+  RecoveryNotReady(RecoveryNotReady),
+  NoTrainingData(NoTrainingData),
+  BountyKeyFrozen(BountyKeyFrozen),
+  BountyNotExpired(BountyNotExpired),
+  InvalidBreakerWindow(InvalidBreakerWindow),
+  NotRoundClient(NotRoundClient),
+  InvalidMaxRoyalty(InvalidMaxRoyalty),
+  StakeUnderChallenge(StakeUnderChallenge),
+  NotOwner(NotOwner),
+  NotPendingOwner(NotPendingOwner),
+  NotTaskAdmin(NotTaskAdmin),
+  NotPublisher(NotPublisher),
+  EnforcedPause(EnforcedPause),
+  ExpectedPause(ExpectedPause),
+}
+
+/// Field order is the storage layout: the fields written by every publish come
+/// first and are packed, so a plain publish touches three slots (hash; publisher,
+/// timestamp, algorithm and flags; block and activation numbers) instead of
+/// seven. A URI, config hash or parent adds one slot each.
 #[storage]
 pub struct VersionInfo {
-  hash: [U8; 32],
+  hash: StorageFixedBytes<32>,
+  publisher: StorageAddress,
+  timestamp: StorageU64,
+  hash_algorithm: StorageU8,
+  yanked: StorageBool,
+  cancelled: StorageBool,
+  block_number: StorageU64,
+  /// Timestamp from which the version may be served as latest.
+  activates_at: StorageU64,
+  successor_version: StorageU64,
+  parent_version: StorageU64,
+  /// Commitment to the hyperparameters and training code revision; zero if unset.
+  config_hash: StorageFixedBytes<32>,
+  artifact_uri: StorageString,
+  yank_reason: StorageString,
+  /// Base model this version was derived from when `parent_version` is non-zero.
+  parent_task_id: StorageString,
+  /// Groth16 verification key in the layout described in `groth16`.
+  groth16_vk: StorageBytes,
+  /// Inference id (keccak of the ABI-encoded public inputs) => proof verified.
+  verified_inferences: StorageMap<FixedBytes<32>, StorageBool>,
+  verified_count: StorageU64,
+  /// Canonical verification key for the version's circuit, for off-chain provers
+  /// and external verifier contracts. Write-once.
+  vk_hash: StorageFixedBytes<32>,
+  vk_uri: StorageString,
+  /// Append-only log of verified inferences.
+  attestations: StorageVec<Attestation>,
+  /// zkVM guest program whose receipts attest inferences for this version. Write-once.
+  image_id: StorageFixedBytes<32>,
+  /// SHA-256 journal digest => receipt verified.
+  verified_journals: StorageMap<FixedBytes<32>, StorageBool>,
+  /// Groth16 key of the aggregation circuit, whose public inputs are
+  /// `[root_hi, root_lo, batch_size]` (the batch root split into 128-bit halves).
+  aggregation_vk: StorageBytes,
+  /// Verified batch Merkle root => number of inferences it covers.
+  batch_roots: StorageMap<FixedBytes<32>, StorageU64>,
+  /// Latest dispute raised against the version, if any.
+  challenge: Challenge,
+  /// Third-party audit reports, in submission order.
+  audits: StorageVec<Audit>,
+  /// Dataset revisions the version was trained on.
+  training_inputs: StorageVec<DatasetRef>,
+  /// Sum and number of the scores currently standing.
+  rating_sum: StorageU64,
+  rating_count: StorageU64,
+  ratings: StorageMap<Address, Rating>,
+  /// SPDX identifier such as "Apache-2.0"; empty if none was declared.
+  license: StorageString,
+  /// `LICENSE_*` bits.
+  license_flags: StorageU8,
+  /// Versions of other tasks (or earlier ones of this task) this version
+  /// runs alongside, and the later versions that declared this one.
+  dependencies: StorageVec<VersionRef>,
+  dependents: StorageVec<VersionRef>,
+  /// Benchmark scores by name, e.g. "accuracy" or "latency_ms".
+  metrics: StorageMap<String, Metric>,
+  /// Metric names in the order they were first recorded.
+  metric_keys: StorageVec<StorageString>,
+  model_card: ModelCard,
+  /// `major.minor.patch` when published with one; all zero otherwise.
+  semver_major: StorageU32,
+  semver_minor: StorageU32,
+  semver_patch: StorageU32,
+  /// Consumers currently pinned to the version, in no particular order.
+  pinners: StorageVec<StorageAddress>,
+  /// One-based position of each pinner in `pinners`. Left behind by pruning,
+  /// which is harmless since a pruned version can't be pinned again.
+  pinner_slots: StorageMap<Address, StorageU64>,
+  /// The subset of those pins held by contracts. Only these keep the version
+  /// from being pruned, since any account can pin for free.
+  contract_pins: StorageU64,
+  /// Location of the encrypted weights for private models; `hash` still
+  /// commits to the plaintext. Write-once.
+  encrypted_uri: StorageString,
+  /// Commitment to the symmetric key the artifact is encrypted under; zero
+  /// if the version is not encrypted.
+  key_commitment: StorageFixedBytes<32>,
+  /// Consumer => hash of the decryption key wrapped to that consumer.
+  key_grants: StorageMap<Address, StorageFixedBytes<32>>,
+  /// Author who signed the release offline; zero if unsigned.
+  author: StorageAddress,
+  /// Softer than a yank: the version still works but consumers should move
+  /// off it. `deprecation_reason` hashes the off-chain explanation.
+  deprecated: StorageBool,
+  deprecation_reason: StorageFixedBytes<32>,
+  /// When the version stops being supported; zero if open-ended.
+  sunset_at: StorageU64,
+  /// Commitment to the weight diff from the previous version; zero if the
+  /// version was published without one.
+  delta_hash: StorageFixedBytes<32>,
+  /// Minimum escrow for an inference job against the version; zero if free.
+  inference_price: StorageU256,
+  /// Consumer => value paid for fulfilled inference jobs against the version.
+  consumer_payments: StorageMap<Address, StorageU256>,
+  total_payments: StorageU256,
+  /// Slashed stake set aside for consumers when a challenge is upheld,
+  /// shared in proportion to `consumer_payments`.
+  refund_pool: StorageU256,
+  refunded: StorageMap<Address, StorageBool>,
+  /// Rejected proofs in the current failure window, which started at
+  /// `failure_window_start`.
+  proof_failures: StorageU32,
+  failure_window_start: StorageU64,
+  /// Set once `proof_failures` reaches the task's breaker threshold; proofs
+  /// against the version are refused until the admin resets it.
+  breaker_tripped: StorageBool,
+  /// Keccak of the ABI-encoded public inputs of the accepted training proof;
+  /// zero until the version is training-verified.
+  training_proof: StorageFixedBytes<32>,
+  /// `Task::record_hash` of the previous version, linking the task's history
+  /// into a chain; zero for version 1.
+  prev_record_hash: StorageFixedBytes<32>,
+  /// Escrow paid to the first proven adversarial example against the version.
+  bounty: StorageU256,
+  /// After this the bounty stops taking claims and funders may take back what
+  /// they put in.
+  bounty_deadline: StorageU64,
+  /// Funder => their unrefunded share of `bounty`.
+  bounty_funds: StorageMap<Address, StorageU256>,
+  /// Who proved the version misclassifies an input, and which input; zero
+  /// until then. A found example flags the version and closes its bounty.
+  adversarial_finder: StorageAddress,
+  adversarial_input: StorageFixedBytes<32>,
+}
+
+/// Minimal structured metadata so UIs can describe a model without fetching the artifact.
+#[storage]
+#[derive(Erase)]
+pub struct ModelCard {
+  /// One of the `FRAMEWORK_*` codes.
+  framework: StorageU8,
+  parameter_count: StorageU64,
+  license_code: StorageU16,
+  /// Digest of the model's input/output schema.
+  io_schema_hash: StorageFixedBytes<32>,
+}
+
+/// A fixed-point score with 18 decimals; re-recording overwrites it.
+#[storage]
+pub struct Metric {
+  value: StorageU256,
+  recorded: StorageBool,
+}
+
+#[storage]
+#[derive(Erase)]
+pub struct DatasetRef {
+  dataset_id: StorageString,
+  revision: StorageU64,
+}
+
+/// One consumer's score for a version, backed by a refundable deposit.
+#[storage]
+#[derive(Erase)]
+pub struct Rating {
+  /// 1 to 5; zero if the account has not rated the version.
+  score: StorageU8,
+  deposit: StorageU256,
+}
+
+/// Copy of the record fields of a task's `latest_version`, so `get_latest`
+/// can answer from the task alone. `version` is zero when there is no valid
+/// copy, e.g. while the version is pending, cancelled or challenged.
+#[storage]
+pub struct LatestCache {
+  version: StorageU64,
+  hash: StorageFixedBytes<32>,
+  timestamp: StorageU64,
+  publisher: StorageAddress,
+  block_number: StorageU64,
+  hash_algorithm: StorageU8,
+  config_hash: StorageFixedBytes<32>,
+  activates_at: StorageU64,
+  artifact_uri: StorageString,
+}
+
+/// An owner replacement proposed by the recovery guardians.
+#[storage]
+pub struct Recovery {
+  /// Zero while no replacement is pending.
+  new_owner: StorageAddress,
+  approvals: StorageU32,
+  /// When the replacement can be executed; zero until the threshold is met.
+  executable_at: StorageU64,
+  /// Bumped by every proposal, so approvals of earlier ones lapse.
+  round: StorageU64,
+  /// Guardian => round it last approved.
+  approved_round: StorageMap<Address, StorageU64>,
+}
+
+/// Contract notified after each publish to a task.
+#[storage]
+#[derive(Erase)]
+pub struct Hook {
+  target: StorageAddress,
+  /// Gas forwarded to the call; the publish goes through whatever it uses.
+  gas_limit: StorageU64,
+}
+
+/// Edge of the cross-task dependency graph.
+#[storage]
+#[derive(Erase)]
+pub struct VersionRef {
+  task_id: StorageString,
+  version: StorageU64,
+}
+
+/// Hash history of a training dataset; revisions are numbered from 1.
+#[storage]
+pub struct Dataset {
+  /// Account that made the first commitment; only it may add revisions.
+  owner: StorageAddress,
+  hashes: StorageVec<StorageFixedBytes<32>>,
+}
+
+/// An auditor's report on a version, e.g. a "security" or "bias" review.
+#[storage]
+#[derive(Erase)]
+pub struct Audit {
+  auditor: StorageAddress,
+  attestation_type: StorageString,
+  report_hash: StorageFixedBytes<32>,
   timestamp: StorageU64,
 }
 
+/// A bonded dispute against a version; `status` is one of the `CHALLENGE_*` codes.
+#[storage]
+#[derive(Erase)]
+pub struct Challenge {
+  status: StorageU8,
+  challenger: StorageAddress,
+  bond: StorageU256,
+  evidence_hash: StorageFixedBytes<32>,
+}
+
+/// Durable record of one verified inference.
+#[storage]
+#[derive(Erase)]
+pub struct Attestation {
+  input_commitment: StorageFixedBytes<32>,
+  output_commitment: StorageFixedBytes<32>,
+  prover: StorageAddress,
+  block_number: StorageU64,
+}
+
 #[storage]
 pub struct Task {
+  registered: StorageBool,
+  /// Version served as latest; normally the newest one, but may be rolled back.
   latest_version: StorageU64,
   versions: StorageMap<U64, VersionInfo>,
+  admin: StorageAddress,
+  publishers: StorageMap<Address, StorageBool>,
+  commitments: StorageMap<FixedBytes<32>, Commitment>,
+  /// Review window, in seconds, between publishing and promotion to latest.
+  activation_delay: StorageU64,
+  /// Total versions ever published; versions are numbered `1..=version_count`.
+  version_count: StorageU64,
+  /// Moving channel labels such as "production" or "canary".
+  tags: StorageMap<String, StorageU64>,
+  /// Once set, the lineage is immutable: no more publishes or rollbacks.
+  frozen: StorageBool,
+  /// Gated tasks only reveal artifact URIs to active subscribers; hashes stay public.
+  gated: StorageBool,
+  subscription_price: StorageU256,
+  /// Subscriber => timestamp at which access expires.
+  subscriptions: StorageMap<Address, StorageU64>,
+  /// Subscriber => ETH paid for the current subscription; cleared when a
+  /// keeper sweeps it.
+  subscription_paid: StorageMap<Address, StorageU256>,
+  subscription_revenue: StorageU256,
+  /// Consumer => timestamp at which the download grant issued by the admin
+  /// expires; zero if none, or revoked.
+  capabilities: StorageMap<Address, StorageU64>,
+  /// Replay protection for `publish_with_sig`.
+  publish_nonce: StorageU256,
+  /// Accounts allowed to record metrics on any of the task's versions.
+  evaluators: StorageMap<Address, StorageBool>,
+  /// ERC-721-style single approval to transfer the task; cleared on every admin change.
+  approved: StorageAddress,
+  /// Price per second of access when paying in the registry's payment token.
+  token_subscription_price: StorageU256,
+  /// Token => subscription revenue received in it and not yet withdrawn.
+  token_subscription_revenue: StorageMap<Address, StorageU256>,
+  /// Base task whose admin receives `royalty_bps` of this task's subscription revenue.
+  royalty_parent: StorageString,
+  royalty_bps: StorageU16,
+  /// Royalty the task's admin offered and the parent's admin has yet to accept.
+  royalty_offer_parent: StorageString,
+  royalty_offer_bps: StorageU16,
+  /// Seconds after publishing during which a version can be challenged; zero
+  /// disables challenges.
+  challenge_period: StorageU64,
+  /// When set, publishing a hash the task has already recorded reverts.
+  strict_mode: StorageBool,
+  /// Every hash ever published to the task, tracked even outside strict mode.
+  published_hashes: StorageMap<FixedBytes<32>, StorageBool>,
+  /// Timestamp of the last write touching the task.
+  last_activity: StorageU64,
+  /// Highest semantic version published so far; new ones must exceed it.
+  semver_major: StorageU32,
+  semver_minor: StorageU32,
+  semver_patch: StorageU32,
+  /// Major => newest version published in that major line.
+  latest_in_major: StorageMap<U32, StorageU64>,
+  /// Seconds after publishing at which a version counts as stale; zero disables.
+  max_age: StorageU64,
+  /// Consumer contract => version it declares a dependency on; zero if none.
+  pins: StorageMap<Address, StorageU64>,
+  /// With a non-zero threshold, versions can only be published through
+  /// `propose_version` and `approval_threshold` distinct approvals.
+  approvers: StorageMap<Address, StorageBool>,
+  approver_count: StorageU32,
+  approval_threshold: StorageU32,
+  /// Seconds a proposal stays open; zero means proposals never expire.
+  proposal_ttl: StorageU64,
+  /// Proposal `n` is stored at index `n - 1`.
+  proposals: StorageVec<Proposal>,
+  /// Account => writes it has made to the task, so off-chain tooling can
+  /// spot dropped or replayed transactions.
+  operation_nonces: StorageMap<Address, StorageU64>,
+  /// Archived tasks take no new versions and are left out of `task_ids`;
+  /// their history stays readable.
+  archived: StorageBool,
+  tombstone_reason: StorageString,
+  /// Frontier of the Merkle tree over the task's versions; see `merkle`.
+  version_tree: StorageArray<StorageFixedBytes<32>, { merkle::DEPTH }>,
+  /// Root of that tree, cached so checkpoints read one word per task.
+  version_tree_root: StorageFixedBytes<32>,
+  /// Position in `Registry::task_index`, and so in the state tree.
+  state_index: StorageU64,
+  /// Versions up to this one were deleted by `prune_versions`; the version
+  /// tree still covers them.
+  pruned_through: StorageU64,
+  /// Frontier and root of the same tree over just the pruned versions.
+  pruned_tree: StorageArray<StorageFixedBytes<32>, { merkle::DEPTH }>,
+  pruned_root: StorageFixedBytes<32>,
+  latest: LatestCache,
+  /// Minimum seconds between publishes; zero disables the limit.
+  publish_cooldown: StorageU64,
+  /// Append-only record of every register, publish, rollback, yank and role change.
+  action_log: StorageVec<Action>,
+  /// Groth16 key of the task's training circuit, which attests that a set of
+  /// weights came out of training on committed data.
+  training_vk: StorageBytes,
+  /// Groth16 key of the circuit proving a version misclassifies an input; see
+  /// `set_adversarial_key`.
+  adversarial_vk: StorageBytes,
+  /// Versions with a nonzero bounty; the adversarial key is frozen while any
+  /// are open, so claims are judged by the key funders saw.
+  open_bounties: StorageU64,
+  /// Called, in order, after every publish; at most `MAX_HOOKS`.
+  hooks: StorageVec<Hook>,
+  /// Contract implementing `IProofVerifier` that `verify_inference` dispatches
+  /// to; zero to use the version's Groth16 key.
+  external_verifier: StorageAddress,
+  /// Rejected proofs within `breaker_window` seconds that trip a version's
+  /// circuit breaker; zero disables it.
+  breaker_threshold: StorageU32,
+  breaker_window: StorageU64,
+  /// Whether tripping also halts verification for every version of the task.
+  breaker_halts_task: StorageBool,
+  halted: StorageBool,
+  /// Federated learning rounds; round `n` is stored at index `n - 1`.
+  rounds: StorageVec<Round>,
+  /// Clients the admin, as round coordinator, lets submit updates.
+  round_clients: StorageMap<Address, StorageBool>,
+  /// Categories the task is listed under, in assignment order.
+  categories: StorageVec<StorageString>,
+  in_category: StorageMap<String, StorageBool>,
+}
+
+/// A federated learning round: clients commit to their local updates while it
+/// is open, then the coordinator records the aggregate and its proof.
+#[storage]
+pub struct Round {
+  opened_at: StorageU64,
+  /// Zero while the round is open.
+  finalized_at: StorageU64,
+  participant_count: StorageU64,
+  /// Client => commitment to the update it contributed.
+  updates: StorageMap<Address, StorageFixedBytes<32>>,
+  submitted: StorageMap<Address, StorageBool>,
+  aggregate_hash: StorageFixedBytes<32>,
+  proof_hash: StorageFixedBytes<32>,
+}
+
+/// A version awaiting sign-off from the task's approvers.
+#[storage]
+pub struct Proposal {
+  proposer: StorageAddress,
+  hash: StorageFixedBytes<32>,
+  /// Zero if the proposal never expires.
+  expires_at: StorageU64,
+  approvals: StorageU32,
+  /// Version the proposal was published as; zero while it is pending.
+  version: StorageU64,
+  artifact_uri: StorageString,
+  approved_by: StorageMap<Address, StorageBool>,
+}
+
+/// A sealed `keccak(hash || salt)` awaiting its reveal.
+#[storage]
+#[derive(Erase)]
+pub struct Commitment {
+  committer: StorageAddress,
+  block_number: StorageU64,
+}
+
+/// A publisher's deposit. A non-zero `unlock_at` means an unstake is pending;
+/// the stake then no longer counts towards publishing but can still be slashed.
+#[storage]
+pub struct Stake {
+  amount: StorageU256,
+  unlock_at: StorageU64,
+  /// Unresolved challenges against the account's versions; the stake cannot
+  /// be withdrawn while any are open.
+  open_challenges: StorageU64,
+}
+
+/// Track record of an account across the registry.
+#[storage]
+pub struct Reputation {
+  /// Versions published.
+  publishes: StorageU64,
+  /// Challenges raised by the account that were upheld.
+  upheld_challenges: StorageU64,
+  /// Disputes lost, either as the publisher of an upheld challenge's version or
+  /// as the challenger of a rejected one.
+  lost_disputes: StorageU64,
+  /// Audits by the account of versions that then survived a challenge.
+  upheld_attestations: StorageU64,
+  /// Audits by the account of versions that were then found faulty.
+  overturned_attestations: StorageU64,
+}
+
+/// Self-declared identity of a publisher. Names and URLs are stored as hashes
+/// of their UTF-8 text; UIs fetch the text off-chain and check it matches.
+#[storage]
+pub struct Profile {
+  display_name_hash: StorageFixedBytes<32>,
+  /// Decentralized identifier such as `did:web:example.org`.
+  did: StorageString,
+  website_hash: StorageFixedBytes<32>,
+  /// Zero if the account never set a profile or cleared it.
+  updated_at: StorageU64,
+}
+
+/// One mutation of a task; `action` is one of the `ACTION_*` codes. `version`
+/// and `subject` (the account a role change applies to) are zero when unused.
+#[storage]
+pub struct Action {
+  action: StorageU8,
+  actor: StorageAddress,
+  version: StorageU64,
+  subject: StorageAddress,
+  block_number: StorageU64,
+}
+
+/// An escrowed inference request; `status` is one of the `JOB_*` codes.
+#[storage]
+pub struct Job {
+  task_id: StorageString,
+  version: StorageU64,
+  requester: StorageAddress,
+  input_commitment: StorageFixedBytes<32>,
+  payment: StorageU256,
+  /// After this timestamp the job can no longer be fulfilled, only refunded.
+  deadline: StorageU64,
+  status: StorageU8,
+  prover: StorageAddress,
+  output_commitment: StorageFixedBytes<32>,
+}
+
+/// A queued request for an off-chain evaluation of a version.
+#[storage]
+pub struct EvaluationRequest {
+  task_id: StorageString,
+  version: StorageU64,
+  requester: StorageAddress,
+  /// Identifies the evaluation suite to run.
+  suite_hash: StorageFixedBytes<32>,
+  fulfilled: StorageBool,
+  oracle: StorageAddress,
+  results_hash: StorageFixedBytes<32>,
+  fulfilled_at: StorageU64,
+}
+
+/// Where a hash was first published.
+#[storage]
+pub struct HashLocation {
+  task_id: StorageString,
+  version: StorageU64,
+}
+
+/// Registry-wide state root at a point in time; see `record_checkpoint`.
+#[storage]
+pub struct Checkpoint {
+  state_root: StorageFixedBytes<32>,
+  task_count: StorageU64,
+  block_number: StorageU64,
+  timestamp: StorageU64,
 }
 
 #[storage]
-#[entrypoint]
 pub struct Registry {
-  pub tasks: StorageMap<String, Task>,
+  /// Keyed by `task_key` of the canonical name, the same `bytes32` id the
+  /// `*_by_id` entrypoints take.
+  pub tasks: StorageMap<FixedBytes<32>, Task>,
+  /// May be an EOA or a contract wallet such as a Safe; owner-only entrypoints
+  /// compare against `msg_sender()`, so a multisig simply executes the call.
   owner: StorageAddress,
+  pending_owner: StorageAddress,
+  paused: StorageBool,
+  /// Append-only list of registered task ids, for on-chain enumeration.
+  task_index: StorageVec<StorageString>,
+  /// Nodes of the state tree over `task_index`, keyed `height << 64 | index`,
+  /// and its root; see `state_root`.
+  state_tree: StorageMap<U256, StorageFixedBytes<32>>,
+  state_tree_root: StorageFixedBytes<32>,
+  hash_index: StorageMap<FixedBytes<32>, HashLocation>,
+  registration_fee: StorageU256,
+  publish_fee: StorageU256,
+  /// Fees received and not yet withdrawn.
+  collected_fees: StorageU256,
+  initialized: StorageBool,
+  /// External zkVM receipt verifier, e.g. a RISC Zero verifier router.
+  verifier_router: StorageAddress,
+  /// Minimum bond posted with a challenge.
+  challenge_bond: StorageU256,
+  /// ERC-2771 forwarder whose calls are attributed to the sender appended to
+  /// their calldata; zero if relaying is disabled.
+  trusted_forwarder: StorageAddress,
+  /// Value locked with each version rating until it is withdrawn.
+  rating_deposit: StorageU256,
+  /// Paid from `collected_fees` to whoever performs a `poke_*` housekeeping call.
+  keeper_bounty: StorageU256,
+  /// May resolve challenges on any task, alongside the task admin.
+  arbiter: StorageAddress,
+  /// Stake a publisher must hold for publishes to be accepted; zero disables staking.
+  min_stake: StorageU256,
+  /// Seconds between `request_unstake` and `unstake`.
+  unstake_cooldown: StorageU64,
+  stakes: StorageMap<Address, Stake>,
+  /// Accounts allowed to `attest` on any version.
+  auditors: StorageMap<Address, StorageBool>,
+  reputations: StorageMap<Address, Reputation>,
+  profiles: StorageMap<Address, Profile>,
+  datasets: StorageMap<String, Dataset>,
+  /// Task owner => operator => may transfer all of the owner's tasks.
+  operators: StorageMap<Address, StorageMap<Address, StorageBool>>,
+  /// ERC-20 accepted for fees and subscriptions besides ETH; zero if none.
+  payment_token: StorageAddress,
+  token_registration_fee: StorageU256,
+  token_publish_fee: StorageU256,
+  /// Token => fees received in it and not yet withdrawn. Kept per token so
+  /// balances survive a change of payment token.
+  token_fees: StorageMap<Address, StorageU256>,
+  /// Inference jobs, indexed by job id.
+  jobs: StorageVec<Job>,
+  /// Seconds a prover has to fulfil a job before it becomes refundable.
+  job_timeout: StorageU64,
+  /// Accounts allowed to push evaluation results.
+  oracles: StorageMap<Address, StorageBool>,
+  /// Evaluation requests, indexed by request id.
+  evaluations: StorageVec<EvaluationRequest>,
+  /// Running commitment to every publish: `keccak(root || task_id_hash || version || hash)`.
+  version_root: StorageFixedBytes<32>,
+  /// L1 contract that receives checkpoint messages.
+  l1_checkpoint_target: StorageAddress,
+  last_checkpoint_digest: StorageFixedBytes<32>,
+  last_checkpoint_at: StorageU64,
+  /// L1 governance contract; its retryable tickets arrive from its L2 alias.
+  l1_controller: StorageAddress,
+  /// `keccak(task_id)` => task_id, for the `bytes32`-keyed entrypoints.
+  task_names: StorageMap<FixedBytes<32>, StorageString>,
+  /// Namespace such as "acme/vision" => account that may register tasks in it
+  /// and in every namespace below it.
+  namespace_admins: StorageMap<String, StorageAddress>,
+  /// Namespace => ids of the tasks registered directly in it.
+  namespace_tasks: StorageMap<String, StorageVec<StorageString>>,
+  /// Old or alternative id => canonical task id.
+  aliases: StorageMap<String, StorageString>,
+  /// Versions published across all tasks.
+  total_versions: StorageU64,
+  /// Set once the owner is done carrying state over from a previous deployment.
+  import_closed: StorageBool,
+  /// Incident-response key: may pause the registry and yank versions, nothing else.
+  guardian: StorageAddress,
+  /// Social recovery of `owner`: `recovery_threshold` of these can replace it,
+  /// `recovery_delay` seconds after they agree. A zero threshold disables it.
+  recovery_guardians: StorageMap<Address, StorageBool>,
+  recovery_guardian_count: StorageU32,
+  recovery_threshold: StorageU32,
+  recovery_delay: StorageU64,
+  recovery: Recovery,
+  /// Append-only log of global state roots.
+  checkpoints: StorageVec<Checkpoint>,
+  /// Seconds after which anyone may record the next checkpoint; zero leaves
+  /// it to the owner.
+  checkpoint_interval: StorageU64,
+  /// Owner-managed taxonomy such as "vision" or "nlp", in creation order.
+  category_names: StorageVec<StorageString>,
+  /// Category => whether tasks may still be assigned to it.
+  categories: StorageMap<String, StorageBool>,
+  /// Category => ids of the tasks listed under it.
+  category_tasks: StorageMap<String, StorageVec<StorageString>>,
+  /// Largest royalty a child task may offer its lineage parent; zero disables royalties.
+  max_royalty_bps: StorageU16,
 }
 
-#[public]
-impl Registry {
+unsafe impl TopLevelStorage for Registry {}
 
-  pub fn init(&mut self) {
-    let origin = Address::from(origin());
-    self.owner.set(origin);
-  }
+const _: () = {
+  <Registry>::__stylus_assert_overrides();
+};
 
-  fn assert_owner(&self) {
-    let sender = Address::from(msg::sender());
+#[cfg(feature = "export-abi")]
+pub fn print_abi(license: &str, pragma: &str) {
+  stylus_sdk::abi::export::print_abi::<Registry>(license, pragma);
+}
 
-    assert_eq!(
-        sender,
-        self.owner.get(),
-        "only owner can call"
-    );
+/// What `#[entrypoint]` on `Registry` would generate, with relayed calls
+/// unwrapped before they are decoded.
+#[cfg(not(feature = "interface"))]
+#[entrypoint]
+fn route(input: Vec<u8>) -> stylus_sdk::ArbResult {
+  let trusted_forwarder = unsafe { Registry::new(U256::ZERO, 0) }.trusted_forwarder.get();
+  let input = forwarder::unwrap(input, trusted_forwarder);
+  #[cfg(feature = "debug")]
+  let selector = input.get(..4).map(stylus_sdk::alloy_primitives::hex::encode).unwrap_or_default();
+  let result = stylus_sdk::abi::router_entrypoint::<Registry, Registry>(input);
+  // Revert reasons stay typed errors on chain; only debug builds spell them out.
+  #[cfg(feature = "debug")]
+  if let Err(data) = &result {
+    stylus_sdk::console!("call 0x{selector} reverted with 0x{}", stylus_sdk::alloy_primitives::hex::encode(data));
   }
+  result
+}
 
-  pub fn register_task(&mut self, task_id: String) {
-    self.assert_owner();
-    assert!(
-      !self.tasks.contains_key(&task_id),
-      "task already registered"
-    );
-    let task = Task {
-      latest_version: 1,
-      versions: StorageMap::new(),
-    };
-    self.tasks.insert(task_id.clone(), task);
+/// Digest algorithm codes accepted at publish time. Records published before
+/// the field existed read back as zero, i.e. SHA-256.
+pub const HASH_SHA256: u8 = 0;
+pub const HASH_KECCAK256: u8 = 1;
+pub const HASH_BLAKE3: u8 = 2;
+pub const HASH_POSEIDON: u8 = 3;
+
+/// Model card framework codes; zero means no card has been set.
+pub const FRAMEWORK_UNSET: u8 = 0;
+pub const FRAMEWORK_PYTORCH: u8 = 1;
+pub const FRAMEWORK_ONNX: u8 = 2;
+pub const FRAMEWORK_TFLITE: u8 = 3;
+
+/// Inference job lifecycle codes.
+pub const JOB_OPEN: u8 = 0;
+pub const JOB_FULFILLED: u8 = 1;
+pub const JOB_REFUNDED: u8 = 2;
+
+/// Action codes in a task's audit log.
+pub const ACTION_REGISTER: u8 = 0;
+pub const ACTION_PUBLISH: u8 = 1;
+pub const ACTION_ROLLBACK: u8 = 2;
+pub const ACTION_YANK: u8 = 3;
+pub const ACTION_ADMIN_CHANGE: u8 = 4;
+pub const ACTION_PUBLISHER_GRANT: u8 = 5;
+pub const ACTION_PUBLISHER_REVOKE: u8 = 6;
+pub const ACTION_APPROVER_GRANT: u8 = 7;
+pub const ACTION_APPROVER_REVOKE: u8 = 8;
+pub const ACTION_EVALUATOR_GRANT: u8 = 9;
+pub const ACTION_EVALUATOR_REVOKE: u8 = 10;
+pub const ACTION_ROUND_CLIENT_GRANT: u8 = 11;
+pub const ACTION_ROUND_CLIENT_REVOKE: u8 = 12;
+
+/// Denominator for basis-point shares.
+pub const BPS: u16 = 10_000;
+
+/// Most a keeper is paid for a `poke_*`, as a share of the value it frees.
+pub const KEEPER_SHARE_BPS: u16 = 100;
+
+/// Permission bits of a version's license.
+pub const LICENSE_COMMERCIAL_USE: u8 = 1;
+pub const LICENSE_REDISTRIBUTION: u8 = 2;
+pub const LICENSE_FINE_TUNING: u8 = 4;
+
+/// Hooks a task can register; bounds the gas every publish can be made to spend.
+pub const MAX_HOOKS: usize = 4;
+
+/// Versions one `prune_versions` call deletes at most, to bound its gas.
+pub const MAX_PRUNE_BATCH: u64 = 64;
+
+/// Challenge lifecycle codes.
+pub const CHALLENGE_NONE: u8 = 0;
+pub const CHALLENGE_OPEN: u8 = 1;
+pub const CHALLENGE_UPHELD: u8 = 2;
+pub const CHALLENGE_REJECTED: u8 = 3;
+
+/// Feature-set version reported by `contract_version`; bumped whenever the
+/// public interface changes.
+pub const CONTRACT_VERSION: &str = "1.0.0";
+
+/// ERC-165 id of `supportsInterface(bytes4)` itself.
+pub const IERC165_ID: [u8; 4] = function_selector!("supportsInterface", FixedBytes<4>);
+
+/// Task registration, publishing and version lookup.
+pub const IREGISTRY_CORE_ID: [u8; 4] = interface_id(&[
+  function_selector!("registerTask", String),
+  function_selector!("publishNewVersion", String, FixedBytes<32>),
+  function_selector!("getLatest", String),
+  function_selector!("getVersion", String, U64),
+  function_selector!("getVersionCount", String),
+  function_selector!("yankVersion", String, U64, String, U64),
+]);
+
+/// Registration and publish fees, subscriptions and ERC-20 payment.
+pub const IREGISTRY_PAYMENTS_ID: [u8; 4] = interface_id(&[
+  function_selector!("fees"),
+  function_selector!("setFee", U256, U256),
+  function_selector!("withdrawFees", Address),
+  function_selector!("subscribe", String, U64),
+  function_selector!("setPaymentToken", Address, U256, U256),
+]);
+
+/// On-chain Groth16 and zkVM receipt verification.
+pub const IREGISTRY_VERIFICATION_ID: [u8; 4] = interface_id(&[
+  function_selector!("setGroth16Key", String, U64, Bytes),
+  function_selector!("verifyInference", String, U64, Bytes, Vec<U256>),
+  function_selector!("setImageId", String, U64, FixedBytes<32>),
+  function_selector!("verifyReceipt", String, U64, FixedBytes<32>, Bytes, Bytes),
+]);
+
+/// ERC-165 interface id: the XOR of the interface's function selectors.
+const fn interface_id(selectors: &[[u8; 4]]) -> [u8; 4] {
+  let mut id = [0u8; 4];
+  let mut i = 0;
+  while i < selectors.len() {
+    let mut j = 0;
+    while j < 4 {
+      id[j] ^= selectors[i][j];
+      j += 1;
+    }
+    i += 1;
   }
+  id
+}
 
-  pub fn publish_new_version(
-    &mut self,
-    task_id: String,
-    hash: [U8; 32],
-  ) {
-    self.assert_owner();
-    let task = self
-      .tasks
-      .get_mut(&task_id)
-      .expect("task not found");
+/// Everything a publish entrypoint supplies for a new version.
+struct NewVersion {
+  /// Account the version is attributed to and authorized against; the
+  /// caller unless the publish was relayed with a signature.
+  publisher: Address,
+  hash: FixedBytes<32>,
+  hash_algorithm: u8,
+  artifact_uri: String,
+  config_hash: FixedBytes<32>,
+  /// `(parent_task_id, parent_version)` of the base model for fine-tunes.
+  parent: Option<(String, U64)>,
+  /// `(dataset_id, revision)` pairs the version was trained on.
+  datasets: Vec<(String, U64)>,
+  /// `(task_id, version)` pairs the version depends on.
+  dependencies: Vec<(String, U64)>,
+  /// SPDX identifier and `LICENSE_*` bits.
+  license: Option<(String, u8)>,
+  /// `(major, minor, patch)`, which must be above every earlier one for the task.
+  semver: Option<(u32, u32, u32)>,
+  /// Whether the version cleared the task's approval threshold.
+  approved: bool,
+  /// Model author recovered from a release signature; zero if none was given.
+  author: Address,
+  /// Commitment to the weight diff from the previous version; zero if none.
+  delta_hash: FixedBytes<32>,
+}
 
-    let new_ver = task.latest_version + 1;
-    let info = VersionInfo {
+impl NewVersion {
+  fn new(hash: FixedBytes<32>) -> Self {
+    NewVersion {
+      publisher: msg_sender(),
       hash,
-      timestamp: block::timestamp(),
+      hash_algorithm: HASH_SHA256,
+      artifact_uri: String::new(),
+      config_hash: FixedBytes::ZERO,
+      parent: None,
+      datasets: Vec::new(),
+      dependencies: Vec::new(),
+      license: None,
+      semver: None,
+      approved: false,
+      author: Address::ZERO,
+      delta_hash: FixedBytes::ZERO,
+    }
+  }
+}
+
+const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+
+/// Upper bound for the `s` half of a canonical (low-s) secp256k1 signature.
+const SECP256K1_HALF_ORDER: U256 = U256::from_be_bytes([
+  0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+  0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+]);
+
+const EIP712_DOMAIN_TYPEHASH: [u8; 32] = Keccak256::new()
+  .update(b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
+  .finalize();
+const EIP712_NAME_HASH: [u8; 32] = Keccak256::new().update(b"zkMLTrack Registry").finalize();
+const EIP712_VERSION_HASH: [u8; 32] = Keccak256::new().update(b"1").finalize();
+const PUBLISH_TYPEHASH: [u8; 32] = Keccak256::new()
+  .update(b"Publish(string taskId,bytes32 hash,uint256 nonce,uint256 deadline)")
+  .finalize();
+const RELEASE_TYPEHASH: [u8; 32] = Keccak256::new()
+  .update(b"Release(string taskId,bytes32 hash)")
+  .finalize();
+
+/// EIP-712 digest of `struct_hash` under this registry's domain.
+fn eip712_digest(struct_hash: B256) -> B256 {
+  let domain_separator = crypto::keccak(
+    (
+      B256::from(EIP712_DOMAIN_TYPEHASH),
+      B256::from(EIP712_NAME_HASH),
+      B256::from(EIP712_VERSION_HASH),
+      U256::from(block::chainid()),
+      contract::address(),
+    )
+      .abi_encode(),
+  );
+
+  let mut preimage = Vec::with_capacity(66);
+  preimage.extend_from_slice(&[0x19, 0x01]);
+  preimage.extend_from_slice(domain_separator.as_slice());
+  preimage.extend_from_slice(struct_hash.as_slice());
+  crypto::keccak(preimage)
+}
+
+/// EIP-712 digest an author signs to vouch for `hash` as a release of `task_id`.
+/// It carries no nonce: the statement stays true however often it is relayed.
+fn release_digest(task_id: &str, hash: FixedBytes<32>) -> B256 {
+  eip712_digest(crypto::keccak(
+    (B256::from(RELEASE_TYPEHASH), crypto::keccak(task_id.as_bytes()), hash).abi_encode(),
+  ))
+}
+
+/// Recovers the EOA behind a 65-byte `r || s || v` signature.
+fn recover_signer(digest: B256, signature: &[u8]) -> Option<Address> {
+  if signature.len() != 65 {
+    return None;
+  }
+  let r = B256::from_slice(&signature[..32]);
+  let s = B256::from_slice(&signature[32..64]);
+  ecrecover(digest, signature[64], r, s)
+}
+
+/// Recovers the signer of `digest` through the ecrecover precompile. Rejects
+/// malleable high-s signatures; returns `None` for anything unrecoverable.
+fn ecrecover(digest: B256, v: u8, r: B256, s: B256) -> Option<Address> {
+  if U256::from_be_bytes(s.0) > SECP256K1_HALF_ORDER || (v != 27 && v != 28) {
+    return None;
+  }
+
+  let input = (digest, U256::from(v), r, s).abi_encode();
+  let output = call::static_call(call::Call::new(), ECRECOVER, &input).ok()?;
+  if output.len() != 32 {
+    return None;
+  }
+  let signer = Address::from_slice(&output[12..]);
+  (signer != Address::ZERO).then_some(signer)
+}
+
+sol_interface! {
+  interface IERC1271 {
+    function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4);
+  }
+}
+
+sol_interface! {
+  /// RISC Zero-style receipt verifier; reverts unless the seal is valid.
+  interface IReceiptVerifier {
+    function verify(bytes seal, bytes32 image_id, bytes32 journal_digest) external view;
+  }
+}
+
+sol_interface! {
+  /// Proof system adapter a task can verify through instead of the built-in
+  /// Groth16 verifier. `publicInputs` is the inputs as packed 32-byte words.
+  interface IProofVerifier {
+    function verify(bytes proof, bytes publicInputs) external view returns (bool);
+  }
+}
+
+const SHA256: Address = address!("0000000000000000000000000000000000000002");
+
+/// SHA-256 through the precompile, which is how zkVMs commit to their journal.
+fn sha256(data: &[u8]) -> Option<B256> {
+  let output = call::static_call(call::Call::new(), SHA256, data).ok()?;
+  (output.len() == 32).then(|| B256::from_slice(&output))
+}
+
+sol_interface! {
+  /// Arbitrum's system precompile, used for L2-to-L1 messages.
+  interface IArbSys {
+    function sendTxToL1(address destination, bytes calldata data) external payable returns (uint256);
+  }
+}
+
+const ARBSYS: Address = address!("0000000000000000000000000000000000000064");
+
+const L1_ALIAS_OFFSET: Address = address!("1111000000000000000000000000000000001111");
+
+/// The address an L1 contract's retryable tickets are sent from on L2.
+fn l1_to_l2_alias(l1: Address) -> Address {
+  let sum = U256::from_be_slice(l1.as_slice()).wrapping_add(U256::from_be_slice(L1_ALIAS_OFFSET.as_slice()));
+  Address::from_word(B256::from(sum))
+}
+
+sol_interface! {
+  /// Observer registered with `add_hook`, called after every publish to its task.
+  interface IPublishHook {
+    function onVersionPublished(string task_id, uint64 version, bytes32 hash, address publisher) external;
+  }
+}
+
+sol_interface! {
+  interface IERC20 {
+    function transfer(address to, uint256 amount) external returns (bool);
+    function transferFrom(address from, address to, uint256 amount) external returns (bool);
+  }
+}
+
+/// A fee schedule entry; the ETH and token prices are configured separately.
+#[derive(Clone, Copy)]
+enum Fee {
+  /// Registering this many tasks at once.
+  Registration(usize),
+  /// Publishing this many versions at once.
+  Publish(usize),
+  /// Registering this many tasks, each with a first version.
+  Onboarding(usize),
+}
+
+const ERC1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Checks `signature` over `digest` for `signer`: through ERC-1271 when the signer
+/// is a contract wallet, otherwise as a 65-byte `r || s || v` ECDSA signature.
+fn is_valid_signature(signer: Address, digest: B256, signature: &[u8]) -> bool {
+  if signer.has_code() {
+    return IERC1271::new(signer)
+      .is_valid_signature(call::Call::new(), digest, signature.to_vec().into())
+      .is_ok_and(|magic| magic.0 == ERC1271_MAGIC_VALUE);
+  }
+
+  recover_signer(digest, signature) == Some(signer)
+}
+
+/// Leaf of a batch Merkle tree: `keccak(0x00 || input_commitment ||
+/// output_commitment)`. The prefix keeps an inner node from passing as a leaf.
+fn batch_leaf(input_commitment: B256, output_commitment: B256) -> B256 {
+  crypto::keccak([&[0x00], input_commitment.as_slice(), output_commitment.as_slice()].concat())
+}
+
+/// Root reached from `leaf` through `proof`, hashing each pair in sorted order
+/// as `keccak(0x01 || a || b)` so no leaf index is needed.
+fn merkle_root(leaf: B256, proof: &[FixedBytes<32>]) -> B256 {
+  proof.iter().fold(leaf, |node, sibling| {
+    let (a, b) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+    crypto::keccak([&[0x01], a.as_slice(), b.as_slice()].concat())
+  })
+}
+
+impl Task {
+  fn has_version(&self, version: U64) -> bool {
+    version > self.pruned_through.get() && version <= self.version_count.get()
+  }
+
+  /// Lists `consumer` as pinned to `version`.
+  fn add_pin(&mut self, version: U64, consumer: Address) {
+    let mut info = self.versions.setter(version);
+    info.pinners.push(consumer);
+    let slot = U64::from(info.pinners.len());
+    info.pinner_slots.insert(consumer, slot);
+    if consumer.has_code() {
+      let count = info.contract_pins.get() + U64::from(1);
+      info.contract_pins.set(count);
+    }
+  }
+
+  /// Drops `consumer` from `version`'s pinners; the last pinner takes its
+  /// place. Pruned versions no longer list theirs.
+  fn release_pin(&mut self, version: U64, consumer: Address) {
+    if !self.has_version(version) {
+      return;
+    }
+    let mut info = self.versions.setter(version);
+    let Some(index) = index(info.pinner_slots.get(consumer)).and_then(|slot| slot.checked_sub(1)) else {
+      return;
     };
+    let last = info.pinners.len() - 1;
+    if index != last {
+      let moved = info.pinners.get(last).unwrap_or_default();
+      if let Some(mut entry) = info.pinners.setter(index) {
+        entry.set(moved);
+      }
+      info.pinner_slots.insert(moved, U64::from(index + 1));
+    }
+    info.pinners.erase_last();
+    info.pinner_slots.delete(consumer);
+    if consumer.has_code() {
+      let count = info.contract_pins.get().saturating_sub(U64::from(1));
+      info.contract_pins.set(count);
+    }
+  }
+
+  /// Adds `version`'s leaf to the version tree. Versions must be appended in order.
+  fn append_version_leaf(&mut self, version: U64, hash: FixedBytes<32>) {
+    let version = version.to::<u64>();
+    let (height, node) = merkle::append(
+      |height| self.version_tree.get(height).unwrap_or_default(),
+      version - 1,
+      merkle::leaf(version, hash),
+    );
+    if let Some(mut slot) = self.version_tree.setter(height) {
+      slot.set(node);
+    }
+    let root = merkle::root(|height| self.version_tree.get(height).unwrap_or_default(), version);
+    self.version_tree_root.set(root);
+  }
+
+  /// `keccak(hash || timestamp || version || prev_record_hash)` of `version`,
+  /// packed, with the timestamp and version as 8-byte big-endian words; zero
+  /// for version 0. Trusting the head's value is enough to check every record
+  /// before it.
+  fn record_hash(&self, version: U64) -> FixedBytes<32> {
+    if version == U64::ZERO {
+      return FixedBytes::ZERO;
+    }
+    let info = self.versions.getter(version);
+    crypto::keccak(
+      [
+        info.hash.get().as_slice(),
+        &info.timestamp.get().to_be_bytes::<8>(),
+        &version.to_be_bytes::<8>(),
+        info.prev_record_hash.get().as_slice(),
+      ]
+      .concat(),
+    )
+  }
+
+  fn exported_settings(&self) -> ExportedSettings {
+    ExportedSettings {
+      activation_delay: self.activation_delay.get().to::<u64>(),
+      challenge_period: self.challenge_period.get().to::<u64>(),
+      max_age: self.max_age.get().to::<u64>(),
+      publish_cooldown: self.publish_cooldown.get().to::<u64>(),
+      strict_mode: self.strict_mode.get(),
+      gated: self.gated.get(),
+      frozen: self.frozen.get(),
+      archived: self.archived.get(),
+      tombstone_reason: self.tombstone_reason.get_string(),
+      subscription_price: self.subscription_price.get(),
+      token_subscription_price: self.token_subscription_price.get(),
+      royalty_parent: self.royalty_parent.get_string(),
+      royalty_bps: self.royalty_bps.get().to::<u16>(),
+      semver_major: self.semver_major.get().to::<u32>(),
+      semver_minor: self.semver_minor.get().to::<u32>(),
+      semver_patch: self.semver_patch.get().to::<u32>(),
+      external_verifier: self.external_verifier.get(),
+      breaker_threshold: self.breaker_threshold.get().to::<u32>(),
+      breaker_window: self.breaker_window.get().to::<u64>(),
+      breaker_halts_task: self.breaker_halts_task.get(),
+      categories: (0..self.categories.len())
+        .filter_map(|i| self.categories.getter(i))
+        .map(|category| category.get_string())
+        .collect(),
+    }
+  }
+
+  /// Restores everything in `settings` but the categories, which also list the
+  /// task in the registry-wide index.
+  fn import_settings(&mut self, settings: &ExportedSettings) {
+    self.activation_delay.set(U64::from(settings.activation_delay));
+    self.challenge_period.set(U64::from(settings.challenge_period));
+    self.max_age.set(U64::from(settings.max_age));
+    self.publish_cooldown.set(U64::from(settings.publish_cooldown));
+    self.strict_mode.set(settings.strict_mode);
+    self.gated.set(settings.gated);
+    self.frozen.set(settings.frozen);
+    self.archived.set(settings.archived);
+    self.tombstone_reason.set_str(&settings.tombstone_reason);
+    self.subscription_price.set(settings.subscription_price);
+    self.token_subscription_price.set(settings.token_subscription_price);
+    self.royalty_parent.set_str(&settings.royalty_parent);
+    self.royalty_bps.set(U16::from(settings.royalty_bps));
+    self.semver_major.set(U32::from(settings.semver_major));
+    self.semver_minor.set(U32::from(settings.semver_minor));
+    self.semver_patch.set(U32::from(settings.semver_patch));
+    self.external_verifier.set(settings.external_verifier);
+    self.breaker_threshold.set(U32::from(settings.breaker_threshold));
+    self.breaker_window.set(U64::from(settings.breaker_window));
+    self.breaker_halts_task.set(settings.breaker_halts_task);
+  }
+
+  /// Refreshes `latest` from `latest_version`. Call after anything that moves
+  /// `latest_version` or changes whether it is active.
+  fn cache_latest(&mut self) {
+    let version = self.latest_version.get();
+    let info = self.versions.getter(version);
+    if version == U64::ZERO || info.cancelled.get() || info.challenge.status.get() == U8::from(CHALLENGE_OPEN) {
+      self.latest.version.set(U64::ZERO);
+      return;
+    }
+    let (hash, timestamp, publisher, block_number) =
+      (info.hash.get(), info.timestamp.get(), info.publisher.get(), info.block_number.get());
+    let (hash_algorithm, config_hash, activates_at) =
+      (info.hash_algorithm.get(), info.config_hash.get(), info.activates_at.get());
+    let artifact_uri = info.artifact_uri.get_string();
+
+    let cache = &mut self.latest;
+    cache.version.set(version);
+    cache.hash.set(hash);
+    cache.timestamp.set(timestamp);
+    cache.publisher.set(publisher);
+    cache.block_number.set(block_number);
+    cache.hash_algorithm.set(hash_algorithm);
+    cache.config_hash.set(config_hash);
+    cache.activates_at.set(activates_at);
+    cache.artifact_uri.set_str(&artifact_uri);
+  }
+
+  /// `get_latest`'s record straight from the cache, if it holds the active version.
+  fn cached_latest(&self, show_uri: bool) -> Option<VersionRecord> {
+    let cache = &self.latest;
+    let version = cache.version.get();
+    if version == U64::ZERO
+      || version != self.latest_version.get()
+      || cache.activates_at.get() > U64::from(block::timestamp())
+    {
+      return None;
+    }
+    Some((
+      version,
+      cache.hash.get(),
+      cache.timestamp.get(),
+      cache.publisher.get(),
+      cache.block_number.get(),
+      if show_uri { cache.artifact_uri.get_string() } else { String::new() },
+      cache.hash_algorithm.get().to::<u8>(),
+      cache.config_hash.get(),
+    ))
+  }
+
+  fn version_tree_root(&self) -> FixedBytes<32> {
+    if self.version_count.get() == U64::ZERO {
+      return merkle::root(|_| FixedBytes::ZERO, 0);
+    }
+    self.version_tree_root.get()
+  }
+
+  /// Whether `account` may see artifact URIs: always for ungated tasks, otherwise
+  /// only for the task's admin, its publishers, and subscribers and capability
+  /// holders with time left.
+  fn uri_visible_to(&self, account: Address) -> bool {
+    !self.gated.get()
+      || account == self.admin.get()
+      || self.publishers.get(account)
+      || self.subscriptions.get(account) > U64::from(block::timestamp())
+      || self.capabilities.get(account) > U64::from(block::timestamp())
+  }
+
+  /// Newest version that has cleared its activation delay and was not
+  /// cancelled, or zero if there is none.
+  fn active_version(&self) -> U64 {
+    let now = U64::from(block::timestamp());
+    let mut version = self.latest_version.get();
+    while version > self.pruned_through.get() {
+      if self.versions.getter(version).is_active(now) {
+        return version;
+      }
+      version -= U64::from(1);
+    }
+    U64::ZERO
+  }
+
+  /// Whether a version published at `published` is older than `max_age`.
+  fn is_stale(&self, published: U64) -> bool {
+    let max_age = self.max_age.get();
+    max_age != U64::ZERO && U64::from(block::timestamp()).saturating_sub(published) > max_age
+  }
+}
+
+/// ABI shape of a version as returned by the getters:
+/// `(version, hash, timestamp, publisher, block_number, artifact_uri, hash_algorithm, config_hash)`,
+/// i.e. `(uint64, bytes32, uint64, address, uint64, string, uint8, bytes32)`. It is
+/// `SolValue`-encodable, so clients can decode it as a struct with those fields.
+pub type VersionRecord = (U64, FixedBytes<32>, U64, Address, U64, String, u8, FixedBytes<32>);
+
+/// ABI shape of an attestation: `(input_commitment, output_commitment, prover, block_number)`.
+pub type AttestationRecord = (FixedBytes<32>, FixedBytes<32>, Address, U64);
+
+/// ABI shape of an audit: `(auditor, attestation_type, report_hash, timestamp)`.
+pub type AuditRecord = (Address, String, FixedBytes<32>, U64);
+
+/// ABI shape of an audit log entry: `(action, actor, version, subject, block_number)`.
+pub type ActionRecord = (u8, Address, U64, Address, U64);
+
+/// ABI shape of a model card: `(framework, parameter_count, io_schema_hash, license_code)`.
+pub type ModelCardRecord = (u8, U64, FixedBytes<32>, u16);
+
+/// ABI shape of an inference job: `(task_id, version, requester, input_commitment,
+/// payment, deadline, status, prover, output_commitment)`.
+pub type JobRecord = (String, U64, Address, FixedBytes<32>, U256, U64, u8, Address, FixedBytes<32>);
+
+/// ABI shape of an evaluation request: `(task_id, version, requester, suite_hash,
+/// fulfilled, oracle, results_hash, fulfilled_at)`.
+pub type EvaluationRecord = (String, U64, Address, FixedBytes<32>, bool, Address, FixedBytes<32>, U64);
+
+sol! {
+  /// A version as carried between deployments by `export_state`/`import_state`.
+  struct ExportedVersion {
+    bytes32 hash;
+    address publisher;
+    uint64 timestamp;
+    uint64 block_number;
+    uint8 hash_algorithm;
+    bool yanked;
+    bytes32 config_hash;
+    string artifact_uri;
+  }
+
+  /// The task-wide configuration `import_state` restores. Anything keyed by
+  /// account (roles, subscriptions, pins, capabilities) or holding funds is
+  /// not enumerable or not movable, so it stays behind, as do hooks, approval
+  /// policy, circuit keys and rounds. Categories must already exist on the
+  /// importing deployment.
+  struct ExportedSettings {
+    uint64 activation_delay;
+    uint64 challenge_period;
+    uint64 max_age;
+    uint64 publish_cooldown;
+    bool strict_mode;
+    bool gated;
+    bool frozen;
+    bool archived;
+    string tombstone_reason;
+    uint256 subscription_price;
+    uint256 token_subscription_price;
+    string royalty_parent;
+    uint16 royalty_bps;
+    uint32 semver_major;
+    uint32 semver_minor;
+    uint32 semver_patch;
+    address external_verifier;
+    uint32 breaker_threshold;
+    uint64 breaker_window;
+    bool breaker_halts_task;
+    string[] categories;
+  }
+
+  /// A task and the versions it has not pruned. `versions` starts at
+  /// `pruned_through + 1`; `pruned_frontier` is the version tree's frontier
+  /// after the pruned versions and `prev_record_hash` the record hash of the
+  /// last of them, so both chains carry on past the gap.
+  struct ExportedTask {
+    string task_id;
+    address admin;
+    uint64 latest_version;
+    uint64 pruned_through;
+    bytes32 pruned_root;
+    bytes32[] pruned_frontier;
+    bytes32 prev_record_hash;
+    ExportedSettings settings;
+    ExportedVersion[] versions;
+  }
+}
+
+/// Compact per-task summary returned by `get_latest_many`:
+/// `(version, hash, timestamp, artifact_uri)`.
+pub type LatestSummary = (U64, FixedBytes<32>, U64, String);
+
+impl VersionInfo {
+  /// Whether deleting the version would strand value: an open challenge's
+  /// bond, a bounty or refunds. Rating deposits stay withdrawable after pruning.
+  fn holds_funds(&self) -> bool {
+    self.challenge.status.get() == U8::from(CHALLENGE_OPEN)
+      || self.bounty.get() != U256::ZERO
+      || self.refund_pool.get() != U256::ZERO
+  }
+
+  /// Clears every field but the maps, whose keys are not enumerable; those
+  /// are unreachable once the version is pruned.
+  fn erase(&mut self) {
+    self.hash.erase();
+    self.publisher.erase();
+    self.timestamp.erase();
+    self.hash_algorithm.erase();
+    self.yanked.erase();
+    self.cancelled.erase();
+    self.block_number.erase();
+    self.activates_at.erase();
+    self.successor_version.erase();
+    self.parent_version.erase();
+    self.config_hash.erase();
+    self.artifact_uri.erase();
+    self.yank_reason.erase();
+    self.parent_task_id.erase();
+    self.groth16_vk.erase();
+    self.verified_count.erase();
+    self.vk_hash.erase();
+    self.vk_uri.erase();
+    self.attestations.erase();
+    self.image_id.erase();
+    self.aggregation_vk.erase();
+    self.challenge.erase();
+    self.audits.erase();
+    self.training_inputs.erase();
+    self.license.erase();
+    self.license_flags.erase();
+    self.dependencies.erase();
+    self.dependents.erase();
+    self.metric_keys.erase();
+    self.model_card.erase();
+    self.semver_major.erase();
+    self.semver_minor.erase();
+    self.semver_patch.erase();
+    self.pinners.erase();
+    self.contract_pins.erase();
+    self.encrypted_uri.erase();
+    self.key_commitment.erase();
+    self.author.erase();
+    self.deprecated.erase();
+    self.deprecation_reason.erase();
+    self.sunset_at.erase();
+    self.delta_hash.erase();
+    self.inference_price.erase();
+    self.total_payments.erase();
+    self.proof_failures.erase();
+    self.failure_window_start.erase();
+    self.breaker_tripped.erase();
+    self.training_proof.erase();
+    self.prev_record_hash.erase();
+    self.bounty_deadline.erase();
+    self.adversarial_finder.erase();
+    self.adversarial_input.erase();
+  }
+
+  /// Versions under an open challenge are held back until it is resolved.
+  fn is_active(&self, now: U64) -> bool {
+    !self.cancelled.get()
+      && self.activates_at.get() <= now
+      && self.challenge.status.get() != U8::from(CHALLENGE_OPEN)
+  }
+
+  /// `show_uri` is false for gated tasks the caller is not subscribed to,
+  /// in which case the artifact URI comes back empty.
+  fn record(&self, version: U64, show_uri: bool) -> VersionRecord {
+    (
+      version,
+      self.hash.get(),
+      self.timestamp.get(),
+      self.publisher.get(),
+      self.block_number.get(),
+      if show_uri { self.artifact_uri.get_string() } else { String::new() },
+      self.hash_algorithm.get().to::<u8>(),
+      self.config_hash.get(),
+    )
+  }
+}
+
+/// A `uint64` id as a list index. Fails where `usize` is narrower, e.g. on
+/// wasm32, which callers report as the id not existing.
+fn index(id: U64) -> Option<usize> {
+  usize::try_from(id).ok()
+}
+
+/// Index range for an `offset`/`limit` page over a list of `len` items.
+fn page(len: usize, offset: U64, limit: U64) -> Range<usize> {
+  let start = offset.saturating_to::<usize>().min(len);
+  let end = start.saturating_add(limit.saturating_to::<usize>()).min(len);
+  start..end
+}
+
+/// Namespace of a task or namespace id: everything before the last `/`.
+fn parent_namespace(id: &str) -> Option<&str> {
+  id.rsplit_once('/').map(|(namespace, _)| namespace)
+}
+
+fn job_not_found(job_id: U64) -> RegistryError {
+  RegistryError::JobNotFound(JobNotFound {
+    job_id: job_id.to::<u64>(),
+  })
+}
+
+fn evaluation_not_found(request_id: U64) -> RegistryError {
+  RegistryError::EvaluationRequestNotFound(EvaluationRequestNotFound {
+    request_id: request_id.to::<u64>(),
+  })
+}
+
+/// `bytes32` id of a task, and its key in `Registry::tasks`.
+pub fn task_key(task_id: &str) -> FixedBytes<32> {
+  crypto::keccak(task_id.as_bytes())
+}
+
+fn version_not_found(task_id: &str, version: U64) -> RegistryError {
+  RegistryError::VersionNotFound(VersionNotFound {
+    task_id: String::from(task_id),
+    version: version.to::<u64>(),
+  })
+}
+
+/// Splits a 32-byte digest into its high and low 128-bit halves, which both
+/// fit a BN254 scalar.
+fn hash_limbs(value: FixedBytes<32>) -> [U256; 2] {
+  let value = U256::from_be_bytes(value.0);
+  [value >> 128, value & U256::from(u128::MAX)]
+}
+
+/// Rejects 32-byte digests that are not reduced BN254 scalars.
+fn field_element(value: FixedBytes<32>) -> Result<U256, RegistryError> {
+  let value = U256::from_be_bytes(value.0);
+  if value >= groth16::SCALAR_FIELD {
+    return Err(RegistryError::InvalidFieldElement(InvalidFieldElement { value }));
+  }
+  Ok(value)
+}
+
+impl Registry {
+  fn assert_owner(&self) -> Result<(), RegistryError> {
+    let sender = msg_sender();
+    if sender != self.owner.get() {
+      return Err(RegistryError::NotOwner(NotOwner { account: sender }));
+    }
+    Ok(())
+  }
+
+  /// Fails unless the caller is a recovery guardian and recovery is enabled;
+  /// returns the caller.
+  fn assert_recovery_guardian(&self) -> Result<Address, RegistryError> {
+    let sender = msg_sender();
+    if !self.recovery_guardians.get(sender) || self.recovery_threshold.get() == U32::ZERO {
+      return Err(RegistryError::NotRecoveryGuardian(NotRecoveryGuardian { account: sender }));
+    }
+    Ok(sender)
+  }
+
+  fn assert_owner_or_guardian(&self) -> Result<(), RegistryError> {
+    if msg_sender() != self.guardian.get() {
+      self.assert_owner()?;
+    }
+    Ok(())
+  }
+
+  fn assert_not_paused(&self) -> Result<(), RegistryError> {
+    if self.paused.get() {
+      return Err(RegistryError::EnforcedPause(EnforcedPause {}));
+    }
+    Ok(())
+  }
+
+  /// The task id `task_id` stands for, following an alias if there is one.
+  fn canonical_id(&self, task_id: &str) -> String {
+    let target = self.aliases.getter(String::from(task_id)).get_string();
+    if target.is_empty() {
+      String::from(task_id)
+    } else {
+      target
+    }
+  }
+
+  /// Loads a registered task, reverting with `TaskNotFound` otherwise.
+  /// Follows aliases, so every read path accepts old ids.
+  fn task(&self, task_id: &str) -> Result<StorageGuard<'_, Task>, RegistryError> {
+    let task = self.tasks.getter(task_key(&self.canonical_id(task_id)));
+    if !task.registered.get() {
+      return Err(RegistryError::TaskNotFound(TaskNotFound {
+        task_id: String::from(task_id),
+      }));
+    }
+    Ok(task)
+  }
+
+  /// Unlike `task`, requires the canonical id, so events emitted by writes
+  /// always carry it. Every write goes through here, which is what keeps
+  /// `last_activity` and the caller's operation nonce current.
+  fn task_mut(&mut self, task_id: &str) -> Result<StorageGuardMut<'_, Task>, RegistryError> {
+    let mut task = self.task_write(task_id)?;
+    let mut nonce = task.operation_nonces.setter(msg_sender());
+    let next = nonce.get() + U64::from(1);
+    nonce.set(next);
+    Ok(task)
+  }
 
-    task.versions.insert(new_ver, info);
-    task.latest_version = new_ver;
+  /// `task_mut` without the nonce bump, for the other steps of a call that
+  /// goes through `task_mut` once, so the nonce advances once per call.
+  fn task_write(&mut self, task_id: &str) -> Result<StorageGuardMut<'_, Task>, RegistryError> {
+    if !self.tasks.getter(task_key(task_id)).registered.get() {
+      return Err(RegistryError::TaskNotFound(TaskNotFound {
+        task_id: String::from(task_id),
+      }));
+    }
+    let mut task = self.tasks.setter(task_key(task_id));
+    task.last_activity.set(U64::from(block::timestamp()));
+    Ok(task)
   }
 
-  pub fn get_latest(&self, task_id: String) -> VersionInfo {
-    let task = self.tasks.get(&task_id).expect("task not found");
-    task
-      .versions
-      .get(&task.latest_version)
-      .expect("no versions yet")
-      .clone()
+  /// Whether the current call pays in the configured ERC-20 token: it must
+  /// send no ETH and a token must be set.
+  fn pays_in_token(&self) -> bool {
+    msg::value().is_zero() && self.payment_token.get() != Address::ZERO
   }
-}
\ No newline at end of file
+
+  /// Charges `fee`: in the payment token via `transferFrom` when the call sends
+  /// no ETH and a token is configured with a nonzero price for it, otherwise
+  /// by booking the attached value, reverting if it does not cover the ETH
+  /// price. A zero token price means the fee is not payable in the token.
+  fn collect_fee(&mut self, fee: Fee) -> Result<(), RegistryError> {
+    if self.pays_in_token() {
+      let (price, count) = match fee {
+        Fee::Registration(count) => (self.token_registration_fee.get(), count),
+        Fee::Publish(count) => (self.token_publish_fee.get(), count),
+        Fee::Onboarding(count) => (
+          self.token_registration_fee.get().saturating_add(self.token_publish_fee.get()),
+          count,
+        ),
+      };
+      let amount = price.saturating_mul(U256::from(count));
+      if !amount.is_zero() {
+        let token = self.payment_token.get();
+        self.pull_token(token, msg_sender(), amount)?;
+        let mut collected = self.token_fees.setter(token);
+        let total = collected.get() + amount;
+        collected.set(total);
+        return Ok(());
+      }
+    }
+
+    let required = match fee {
+      Fee::Registration(count) => self.registration_fee.get().saturating_mul(U256::from(count)),
+      Fee::Publish(count) => self.publish_fee.get().saturating_mul(U256::from(count)),
+      Fee::Onboarding(count) => self
+        .registration_fee
+        .get()
+        .saturating_add(self.publish_fee.get())
+        .saturating_mul(U256::from(count)),
+    };
+    let provided = msg::value();
+    if provided < required {
+      return Err(RegistryError::InsufficientFee(InsufficientFee { required, provided }));
+    }
+    self.collected_fees.set(self.collected_fees.get() + provided);
+    Ok(())
+  }
+
+  /// Moves `amount` of `token` from `from` into the registry; `from` must have
+  /// approved it beforehand.
+  fn pull_token(&mut self, token: Address, from: Address, amount: U256) -> Result<(), RegistryError> {
+    let ok = IERC20::new(token)
+      .transfer_from(call::Call::new_in(self), from, contract::address(), amount)
+      .unwrap_or(false);
+    if !ok {
+      return Err(RegistryError::TokenTransferFailed(TokenTransferFailed {
+        token,
+        to: contract::address(),
+        amount,
+      }));
+    }
+
+    evm::log(TokenPaymentReceived { token, from, amount });
+    Ok(())
+  }
+
+  fn push_token(&mut self, token: Address, to: Address, amount: U256) -> Result<(), RegistryError> {
+    let ok = IERC20::new(token)
+      .transfer(call::Call::new_in(self), to, amount)
+      .unwrap_or(false);
+    if !ok {
+      return Err(RegistryError::TokenTransferFailed(TokenTransferFailed { token, to, amount }));
+    }
+    Ok(())
+  }
+
+  fn assert_not_frozen(&self, task_id: &str) -> Result<(), RegistryError> {
+    if self.task(task_id)?.frozen.get() {
+      return Err(RegistryError::TaskIsFrozen(TaskIsFrozen {
+        task_id: String::from(task_id),
+      }));
+    }
+    Ok(())
+  }
+
+  fn assert_not_archived(&self, task_id: &str) -> Result<(), RegistryError> {
+    if self.task(task_id)?.archived.get() {
+      return Err(RegistryError::TaskIsArchived(TaskIsArchived {
+        task_id: String::from(task_id),
+      }));
+    }
+    Ok(())
+  }
+
+  /// Refuses proofs against a version whose circuit breaker has tripped, or
+  /// any version of a task the breaker halted.
+  fn assert_breaker_closed(&self, task_id: &str, version: U64) -> Result<(), RegistryError> {
+    let task = self.task(task_id)?;
+    if task.halted.get() || task.versions.getter(version).breaker_tripped.get() {
+      return Err(RegistryError::CircuitBreakerOpen(CircuitBreakerOpen {
+        task_id: String::from(task_id),
+        version: version.to::<u64>(),
+      }));
+    }
+    Ok(())
+  }
+
+  /// Whether a rejected proof from `account` counts toward the task's
+  /// breaker: it must be on the task's staff or bonded with `min_stake`, so an
+  /// anonymous griefer cannot trip it with junk proofs.
+  fn counts_toward_breaker(&self, task_id: &str, account: Address) -> bool {
+    let task = self.tasks.getter(task_key(task_id));
+    if account == task.admin.get() || task.publishers.get(account) || task.evaluators.get(account) {
+      return true;
+    }
+    !self.min_stake.get().is_zero() && self.assert_staked(account).is_ok()
+  }
+
+  /// Counts a rejected proof against the version, tripping its breaker once
+  /// the task's threshold is reached within one window.
+  fn record_proof_failure(&mut self, task_id: &str, version: U64) {
+    if !self.counts_toward_breaker(task_id, msg_sender()) {
+      return;
+    }
+    let now = U64::from(block::timestamp());
+    let mut task = self.tasks.setter(task_key(task_id));
+    let threshold = task.breaker_threshold.get();
+    let window = task.breaker_window.get();
+    let halt_task = task.breaker_halts_task.get();
+    if threshold == U32::ZERO {
+      return;
+    }
+    let mut info = task.versions.setter(version);
+    let failures = if now >= info.failure_window_start.get().saturating_add(window) {
+      info.failure_window_start.set(now);
+      U32::from(1)
+    } else {
+      info.proof_failures.get() + U32::from(1)
+    };
+    info.proof_failures.set(failures);
+    if failures < threshold {
+      return;
+    }
+    info.breaker_tripped.set(true);
+    if halt_task {
+      task.halted.set(true);
+    }
+
+    evm::log(CircuitBreakerTripped {
+      task_id: String::from(task_id),
+      version: version.to::<u64>(),
+      failures: failures.to::<u32>(),
+      task_halted: halt_task,
+    });
+  }
+
+  /// Publishes must be `publish_cooldown` apart. Versions published as the
+  /// owner are exempt, so an emergency fix is never held back by the limit.
+  fn assert_cooled_down(&self, task_id: &str, publisher: Address) -> Result<(), RegistryError> {
+    let task = self.task(task_id)?;
+    let cooldown = task.publish_cooldown.get();
+    let count = task.version_count.get();
+    if cooldown == U64::ZERO || count == U64::ZERO || publisher == self.owner.get() {
+      return Ok(());
+    }
+    let next_at = task.versions.getter(count).timestamp.get().saturating_add(cooldown);
+    if U64::from(block::timestamp()) < next_at {
+      return Err(RegistryError::PublishCooldown(PublishCooldown {
+        task_id: String::from(task_id),
+        next_at: next_at.to::<u64>(),
+      }));
+    }
+    Ok(())
+  }
+
+  /// The task admin manages publishers; the registry owner keeps an override.
+  fn assert_task_admin(&self, task_id: &str) -> Result<(), RegistryError> {
+    let sender = msg_sender();
+    let task = self.task(task_id)?;
+    if sender != task.admin.get() && sender != self.owner.get() {
+      return Err(RegistryError::NotTaskAdmin(NotTaskAdmin {
+        task_id: String::from(task_id),
+        account: sender,
+      }));
+    }
+    Ok(())
+  }
+
+  /// Version must exist and the caller must be its publisher or the task admin.
+  fn assert_version_manager(&self, task_id: &str, version: U64) -> Result<(), RegistryError> {
+    let task = self.task(task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(task_id, version));
+    }
+    if msg_sender() != task.versions.getter(version).publisher.get() {
+      self.assert_task_admin(task_id)?;
+    }
+    Ok(())
+  }
+
+  fn assert_publisher(&self, task_id: &str, account: Address) -> Result<(), RegistryError> {
+    let task = self.task(task_id)?;
+    if !task.publishers.get(account)
+      && account != task.admin.get()
+      && account != self.owner.get()
+    {
+      return Err(RegistryError::NotPublisher(NotPublisher {
+        task_id: String::from(task_id),
+        account,
+      }));
+    }
+    Ok(())
+  }
+
+  /// Checks the deadline, burns the task's signature nonce and returns the EIP-712
+  /// digest of the `Publish` message the signer must have signed.
+  fn consume_publish_digest(
+    &mut self,
+    task_id: &str,
+    hash: FixedBytes<32>,
+    deadline: U256,
+  ) -> Result<B256, RegistryError> {
+    if U256::from(block::timestamp()) > deadline {
+      return Err(RegistryError::SignatureExpired(SignatureExpired { deadline }));
+    }
+
+    // `publish` counts the call.
+    let mut task = self.task_write(task_id)?;
+    let nonce = task.publish_nonce.get();
+    task.publish_nonce.set(nonce + U256::from(1));
+
+    let struct_hash = crypto::keccak(
+      (
+        B256::from(PUBLISH_TYPEHASH),
+        crypto::keccak(task_id.as_bytes()),
+        hash,
+        nonce,
+        deadline,
+      )
+        .abi_encode(),
+    );
+    Ok(eip712_digest(struct_hash))
+  }
+
+  fn assert_staked(&self, account: Address) -> Result<(), RegistryError> {
+    let required = self.min_stake.get();
+    if required.is_zero() {
+      return Ok(());
+    }
+    let stake = self.stakes.getter(account);
+    let staked = if stake.unlock_at.get() == U64::ZERO { stake.amount.get() } else { U256::ZERO };
+    if staked < required {
+      return Err(RegistryError::InsufficientStake(InsufficientStake {
+        account,
+        required,
+        staked,
+      }));
+    }
+    Ok(())
+  }
+
+  /// Takes up to `min_stake` from `account`'s stake and returns the amount taken.
+  fn slash_stake(&mut self, account: Address, task_id: &str, version: U64) -> U256 {
+    let mut stake = self.stakes.setter(account);
+    let staked = stake.amount.get();
+    let amount = staked.min(self.min_stake.get());
+    if amount.is_zero() {
+      return amount;
+    }
+    stake.amount.set(staked - amount);
+
+    evm::log(StakeSlashed {
+      account,
+      amount,
+      task_id: String::from(task_id),
+      version: version.to::<u64>(),
+    });
+    amount
+  }
+
+  fn dataset_hash(&self, dataset_id: &str, revision: U64) -> Result<FixedBytes<32>, RegistryError> {
+    let dataset = self.datasets.getter(String::from(dataset_id));
+    revision
+      .checked_sub(U64::from(1))
+      .and_then(index)
+      .and_then(|i| dataset.hashes.get(i))
+      .ok_or_else(|| {
+        RegistryError::DatasetNotFound(DatasetNotFound {
+          dataset_id: String::from(dataset_id),
+          revision: revision.to::<u64>(),
+        })
+      })
+  }
+
+  /// Pays the caller `keeper_bounty` for a poke that freed `value`, capped at
+  /// `KEEPER_SHARE_BPS` of it and at what is left of the collected fees, so
+  /// poking never pays more than the poked value cost. Returns the amount paid.
+  fn pay_keeper(&mut self, value: U256) -> Result<U256, RegistryError> {
+    let share = value * U256::from(KEEPER_SHARE_BPS) / U256::from(BPS);
+    let amount = self.keeper_bounty.get().min(share).min(self.collected_fees.get());
+    if amount == U256::ZERO {
+      return Ok(amount);
+    }
+    self.collected_fees.set(self.collected_fees.get() - amount);
+    let keeper = msg_sender();
+    if call::transfer_eth(keeper, amount).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to: keeper, amount }));
+    }
+
+    evm::log(KeeperRewarded { keeper, amount });
+    Ok(amount)
+  }
+
+  /// Adds to a task's withdrawable subscription revenue; `token` is zero for ETH.
+  fn credit_subscription_revenue(&mut self, task_id: &str, token: Address, amount: U256) {
+    let mut task = self.tasks.setter(task_key(task_id));
+    if token == Address::ZERO {
+      let revenue = task.subscription_revenue.get() + amount;
+      task.subscription_revenue.set(revenue);
+    } else {
+      let mut revenue = task.token_subscription_revenue.setter(token);
+      let total = revenue.get() + amount;
+      revenue.set(total);
+    }
+  }
+
+  /// Creates `task_id` with the caller as its admin; callers handle auth and fees.
+  fn register(&mut self, task_id: String) -> Result<(), RegistryError> {
+    let is_alias = !self.aliases.getter(task_id.clone()).is_empty();
+    let mut task = self.tasks.setter(task_key(&task_id));
+    if task.registered.get() || is_alias {
+      return Err(RegistryError::TaskExists(TaskExists { task_id }));
+    }
+    task.registered.set(true);
+    task.latest_version.set(U64::ZERO);
+    task.last_activity.set(U64::from(block::timestamp()));
+    task.admin.set(msg_sender());
+    task.state_index.set(U64::from(self.task_index.len()));
+    self.task_index.grow().set_str(&task_id);
+    self.update_state_leaf(&task_id);
+    self.task_names.setter(task_key(&task_id)).set_str(&task_id);
+    if let Some(namespace) = parent_namespace(&task_id) {
+      self.namespace_tasks.setter(String::from(namespace)).grow().set_str(&task_id);
+    }
+    self.log_action(&task_id, ACTION_REGISTER, U64::ZERO, Address::ZERO);
+
+    evm::log(TaskRegistered {
+      task_id,
+      registrar: msg_sender(),
+      timestamp: block::timestamp(),
+    });
+    Ok(())
+  }
+
+  /// Lists `task_id` under `category`, which must be an active category the
+  /// task is not already in.
+  fn categorize(&mut self, task_id: &str, category: String) -> Result<(), RegistryError> {
+    if !self.categories.get(category.clone()) {
+      return Err(RegistryError::UnknownCategory(UnknownCategory { category }));
+    }
+    let mut task = self.task_mut(task_id)?;
+    if task.in_category.get(category.clone()) {
+      return Err(RegistryError::CategoryAlreadyAssigned(CategoryAlreadyAssigned {
+        task_id: String::from(task_id),
+        category,
+      }));
+    }
+    task.in_category.insert(category.clone(), true);
+    task.categories.grow().set_str(&category);
+    self.category_tasks.setter(category.clone()).grow().set_str(task_id);
+
+    evm::log(TaskCategorized {
+      task_id: String::from(task_id),
+      category,
+    });
+    Ok(())
+  }
+
+  /// Namespaced ids need a namespace admin; top-level ids are the owner's.
+  fn assert_may_register(&self, task_id: &str) -> Result<(), RegistryError> {
+    match parent_namespace(task_id) {
+      Some(namespace) => self.assert_namespace_admin(namespace),
+      None => self.assert_owner(),
+    }
+  }
+
+  /// Passes for the owner and for the admin of `namespace` or any namespace above it.
+  fn assert_namespace_admin(&self, namespace: &str) -> Result<(), RegistryError> {
+    let sender = msg_sender();
+    if sender == self.owner.get() {
+      return Ok(());
+    }
+    let mut current = Some(namespace);
+    while let Some(ns) = current {
+      if self.namespace_admins.get(String::from(ns)) == sender {
+        return Ok(());
+      }
+      current = parent_namespace(ns);
+    }
+    Err(RegistryError::NotNamespaceAdmin(NotNamespaceAdmin {
+      namespace: String::from(namespace),
+      account: sender,
+    }))
+  }
+
+  fn assert_l1_controller(&self) -> Result<(), RegistryError> {
+    let sender = msg_sender();
+    let controller = self.l1_controller.get();
+    if controller == Address::ZERO || sender != l1_to_l2_alias(controller) {
+      return Err(RegistryError::NotL1Controller(NotL1Controller { account: sender }));
+    }
+    Ok(())
+  }
+
+  /// Resolves a `bytes32` task id back to the task's name.
+  fn task_name(&self, id: FixedBytes<32>) -> Result<String, RegistryError> {
+    let name = self.task_names.getter(id).get_string();
+    if name.is_empty() {
+      return Err(RegistryError::TaskIdNotFound(TaskIdNotFound { id }));
+    }
+    Ok(name)
+  }
+
+  fn bump_reputation(&mut self, account: Address, counter: impl FnOnce(&mut Reputation) -> &mut StorageU64) {
+    let mut reputation = self.reputations.setter(account);
+    let counter = counter(&mut reputation);
+    counter.set(counter.get() + U64::from(1));
+  }
+
+  /// Recomputes `task_id`'s leaf of the state tree, the Merkle root over
+  /// every task in registration order. Leaf `i` is
+  /// `keccak(abi.encode(keccak(task_id), version_tree_root))` for task `i`.
+  /// Call whenever the task's version tree root changes.
+  fn update_state_leaf(&mut self, task_id: &str) {
+    let task = self.tasks.getter(task_key(task_id));
+    let index = task.state_index.get().to::<u64>();
+    let leaf = crypto::keccak((task_key(task_id), task.version_tree_root()).abi_encode());
+    let key = |height: usize, i: u64| (U256::from(height) << 64) | U256::from(i);
+    let (path, root) = merkle::update(
+      |height, i| self.state_tree.get(key(height, i)),
+      self.task_index.len() as u64,
+      index,
+      leaf,
+    );
+    for (height, node) in path.into_iter().enumerate() {
+      self.state_tree.insert(key(height, index >> height), node);
+    }
+    self.state_tree_root.set(root);
+  }
+
+  /// Appends the current state root to the checkpoint log, returning its index.
+  fn append_checkpoint(&mut self) -> (U64, FixedBytes<32>) {
+    let state_root = self.state_root();
+    let task_count = self.task_index.len() as u64;
+    let index = U64::from(self.checkpoints.len());
+    let mut checkpoint = self.checkpoints.grow();
+    checkpoint.state_root.set(state_root);
+    checkpoint.task_count.set(U64::from(task_count));
+    checkpoint.block_number.set(U64::from(block::number()));
+    checkpoint.timestamp.set(U64::from(block::timestamp()));
+
+    evm::log(CheckpointRecorded {
+      index: index.to::<u64>(),
+      state_root,
+      task_count,
+      block_number: block::number(),
+    });
+    (index, state_root)
+  }
+
+  /// Tells pinners, with a single log, that the version they depend on was yanked.
+  fn log_pinned_yank(&self, task_id: &str, version: U64) {
+    let pin_count = self.tasks.getter(task_key(task_id)).versions.getter(version).pinners.len();
+    if pin_count != 0 {
+      evm::log(PinnedVersionYanked {
+        task_id: String::from(task_id),
+        version: version.to::<u64>(),
+        pin_count: pin_count as u64,
+      });
+    }
+  }
+
+  /// Appends an entry, attributed to the caller, to the task's audit log.
+  fn log_action(&mut self, task_id: &str, action: u8, version: U64, subject: Address) {
+    let mut task = self.tasks.setter(task_key(task_id));
+    let mut entry = task.action_log.grow();
+    entry.action.set(U8::from(action));
+    entry.actor.set(msg_sender());
+    entry.version.set(version);
+    entry.subject.set(subject);
+    entry.block_number.set(U64::from(block::number()));
+  }
+
+  /// Registry-wide bookkeeping for a freshly written version: the global
+  /// count, the running `version_root` and the reverse hash index.
+  fn index_version(&mut self, task_id: &str, version: U64, hash: FixedBytes<32>) {
+    self.total_versions.set(self.total_versions.get() + U64::from(1));
+
+    let version_root = crypto::keccak(
+      (
+        self.version_root.get(),
+        crypto::keccak(task_id.as_bytes()),
+        version.to::<u64>(),
+        hash,
+      )
+        .abi_encode(),
+    );
+    self.version_root.set(version_root);
+
+    let mut location = self.hash_index.setter(hash);
+    if location.version.get() == U64::ZERO {
+      location.task_id.set_str(task_id);
+      location.version.set(version);
+    }
+  }
+
+  /// Appends a new version to `task_id`; callers are responsible for the pause check.
+  fn publish(&mut self, task_id: String, mut new_version: NewVersion) -> Result<(), RegistryError> {
+    self.assert_publisher(&task_id, new_version.publisher)?;
+    self.assert_staked(new_version.publisher)?;
+    self.assert_not_frozen(&task_id)?;
+    self.assert_not_archived(&task_id)?;
+    self.assert_cooled_down(&task_id, new_version.publisher)?;
+    if new_version.hash_algorithm > HASH_POSEIDON {
+      return Err(RegistryError::UnsupportedHashAlgorithm(UnsupportedHashAlgorithm {
+        hash_algorithm: new_version.hash_algorithm,
+      }));
+    }
+    // Poseidon digests are consumed as circuit inputs, so they must be field elements.
+    if new_version.hash_algorithm == HASH_POSEIDON {
+      field_element(new_version.hash)?;
+    }
+    if let Some((parent_task_id, parent_version)) = &mut new_version.parent {
+      *parent_task_id = self.canonical_id(parent_task_id);
+      if !self.task(parent_task_id)?.has_version(*parent_version) {
+        return Err(version_not_found(parent_task_id, *parent_version));
+      }
+    }
+    for (dataset_id, revision) in &new_version.datasets {
+      self.dataset_hash(dataset_id, *revision)?;
+    }
+    for (dependency_task_id, dependency_version) in &mut new_version.dependencies {
+      *dependency_task_id = self.canonical_id(dependency_task_id);
+      if !self.task(dependency_task_id)?.has_version(*dependency_version) {
+        return Err(version_not_found(dependency_task_id, *dependency_version));
+      }
+    }
+    let mut task = self.task_mut(&task_id)?;
+    // A delta needs a previous version to be a diff from.
+    if new_version.delta_hash != FixedBytes::ZERO && task.version_count.get() == U64::ZERO {
+      return Err(RegistryError::NoVersions(NoVersions { task_id }));
+    }
+    if task.approval_threshold.get() != U32::ZERO && !new_version.approved {
+      return Err(RegistryError::ApprovalRequired(ApprovalRequired { task_id }));
+    }
+    if task.published_hashes.get(new_version.hash) && task.strict_mode.get() {
+      return Err(RegistryError::DuplicateHash(DuplicateHash {
+        task_id,
+        hash: new_version.hash,
+      }));
+    }
+    task.published_hashes.insert(new_version.hash, true);
+    if let Some((major, minor, patch)) = new_version.semver {
+      let highest = (
+        task.semver_major.get().to::<u32>(),
+        task.semver_minor.get().to::<u32>(),
+        task.semver_patch.get().to::<u32>(),
+      );
+      if (major, minor, patch) <= highest {
+        return Err(RegistryError::SemverNotIncreasing(SemverNotIncreasing {
+          task_id,
+          major,
+          minor,
+          patch,
+        }));
+      }
+    }
+
+    let new_ver = task.version_count.get() + U64::from(1);
+    let timestamp = block::timestamp();
+    let activates_at = U64::from(timestamp).saturating_add(task.activation_delay.get());
+    let NewVersion {
+      publisher,
+      hash,
+      hash_algorithm,
+      artifact_uri,
+      config_hash,
+      parent,
+      datasets,
+      dependencies,
+      license,
+      semver,
+      author,
+      delta_hash,
+      ..
+    } = new_version;
+
+    let prev_record_hash = task.record_hash(new_ver - U64::from(1));
+    let mut info = task.versions.setter(new_ver);
+    info.hash.set(hash);
+    info.timestamp.set(U64::from(timestamp));
+    info.publisher.set(publisher);
+    info.block_number.set(U64::from(block::number()));
+    info.hash_algorithm.set(U8::from(hash_algorithm));
+    info.activates_at.set(activates_at);
+    // Skip writes that would only store zeroes in fresh slots.
+    if !artifact_uri.is_empty() {
+      info.artifact_uri.set_str(&artifact_uri);
+    }
+    if config_hash != FixedBytes::ZERO {
+      info.config_hash.set(config_hash);
+    }
+    if author != Address::ZERO {
+      info.author.set(author);
+    }
+    if delta_hash != FixedBytes::ZERO {
+      info.delta_hash.set(delta_hash);
+    }
+    if prev_record_hash != FixedBytes::ZERO {
+      info.prev_record_hash.set(prev_record_hash);
+    }
+    if let Some((parent_task_id, parent_version)) = &parent {
+      info.parent_task_id.set_str(parent_task_id);
+      info.parent_version.set(*parent_version);
+    }
+    for (dataset_id, revision) in &datasets {
+      let mut input = info.training_inputs.grow();
+      input.dataset_id.set_str(dataset_id);
+      input.revision.set(*revision);
+    }
+    if let Some((license, flags)) = &license {
+      info.license.set_str(license);
+      info.license_flags.set(U8::from(*flags));
+    }
+    for (dependency_task_id, dependency_version) in &dependencies {
+      let mut edge = info.dependencies.grow();
+      edge.task_id.set_str(dependency_task_id);
+      edge.version.set(*dependency_version);
+    }
+    if let Some((major, minor, patch)) = semver {
+      info.semver_major.set(U32::from(major));
+      info.semver_minor.set(U32::from(minor));
+      info.semver_patch.set(U32::from(patch));
+      task.semver_major.set(U32::from(major));
+      task.semver_minor.set(U32::from(minor));
+      task.semver_patch.set(U32::from(patch));
+      task.latest_in_major.insert(U32::from(major), new_ver);
+    }
+    task.version_count.set(new_ver);
+    task.latest_version.set(new_ver);
+    task.cache_latest();
+    task.append_version_leaf(new_ver, hash);
+    let operation_nonce = task.operation_nonces.get(msg_sender());
+    self.update_state_leaf(&task_id);
+    self.bump_reputation(publisher, |r| &mut r.publishes);
+    self.index_version(&task_id, new_ver, hash);
+    self.log_action(&task_id, ACTION_PUBLISH, new_ver, Address::ZERO);
+    let canonical_id = self.canonical_id(&task_id);
+    for (dependency_task_id, dependency_version) in &dependencies {
+      let mut dependency = self.tasks.setter(task_key(dependency_task_id));
+      let mut info = dependency.versions.setter(*dependency_version);
+      let mut edge = info.dependents.grow();
+      edge.task_id.set_str(&canonical_id);
+      edge.version.set(new_ver);
+    }
+
+    evm::log(VersionPublished {
+      task_id: task_id.clone(),
+      version: new_ver.to::<u64>(),
+      hash,
+      publisher,
+      timestamp,
+      artifact_uri,
+      hash_algorithm,
+      activates_at: activates_at.to::<u64>(),
+      config_hash,
+      operation_nonce: operation_nonce.to::<u64>(),
+    });
+    if author != Address::ZERO {
+      evm::log(VersionAuthored {
+        task_id: task_id.clone(),
+        version: new_ver.to::<u64>(),
+        author,
+      });
+    }
+    if delta_hash != FixedBytes::ZERO {
+      evm::log(VersionDeltaCommitted {
+        task_id: task_id.clone(),
+        version: new_ver.to::<u64>(),
+        delta_hash,
+      });
+    }
+    if let Some((parent_task_id, parent_version)) = parent {
+      evm::log(ParentLinked {
+        task_id: task_id.clone(),
+        version: new_ver.to::<u64>(),
+        parent_task_id,
+        parent_version: parent_version.to::<u64>(),
+      });
+    }
+    for (dataset_id, revision) in datasets {
+      evm::log(DatasetLinked {
+        task_id: task_id.clone(),
+        version: new_ver.to::<u64>(),
+        dataset_id,
+        revision: revision.to::<u64>(),
+      });
+    }
+    for (dependency_task_id, dependency_version) in dependencies {
+      evm::log(DependencyLinked {
+        task_id: task_id.clone(),
+        version: new_ver.to::<u64>(),
+        dependency_task_id,
+        dependency_version: dependency_version.to::<u64>(),
+      });
+    }
+    if let Some((license, flags)) = license {
+      evm::log(LicenseAssigned {
+        task_id: task_id.clone(),
+        version: new_ver.to::<u64>(),
+        license,
+        commercial_use: flags & LICENSE_COMMERCIAL_USE != 0,
+        redistribution: flags & LICENSE_REDISTRIBUTION != 0,
+        fine_tuning: flags & LICENSE_FINE_TUNING != 0,
+      });
+    }
+    if let Some((major, minor, patch)) = semver {
+      evm::log(SemverAssigned {
+        task_id: task_id.clone(),
+        version: new_ver.to::<u64>(),
+        major,
+        minor,
+        patch,
+      });
+    }
+    self.call_hooks(&task_id, new_ver, hash, publisher);
+    Ok(())
+  }
+
+  /// Notifies the task's hooks of a publish. Each runs under its own gas limit
+  /// and a failing hook is only logged, so no hook can block a publish.
+  fn call_hooks(&mut self, task_id: &str, version: U64, hash: FixedBytes<32>, publisher: Address) {
+    let hooks: Vec<(Address, u64)> = {
+      let task = self.tasks.getter(task_key(task_id));
+      (0..task.hooks.len())
+        .filter_map(|i| task.hooks.getter(i))
+        .map(|hook| (hook.target.get(), hook.gas_limit.get().to::<u64>()))
+        .collect()
+    };
+    for (target, gas_limit) in hooks {
+      let result = IPublishHook::new(target).on_version_published(
+        call::Call::new_in(self).gas(gas_limit),
+        String::from(task_id),
+        version.to::<u64>(),
+        hash,
+        publisher,
+      );
+      if result.is_err() {
+        evm::log(HookFailed {
+          task_id: String::from(task_id),
+          version: version.to::<u64>(),
+          hook: target,
+        });
+      }
+    }
+  }
+}
+
+#[public]
+impl Registry {
+
+  /// Claims ownership for the transaction origin. Can only succeed once.
+  pub fn init(&mut self) -> Result<(), RegistryError> {
+    if self.initialized.get() {
+      return Err(RegistryError::AlreadyInitialized(AlreadyInitialized {}));
+    }
+    self.initialized.set(true);
+
+    let origin = origin();
+    let previous_owner = self.owner.get();
+    self.owner.set(origin);
+
+    evm::log(OwnershipChanged {
+      previous_owner,
+      new_owner: origin,
+    });
+    Ok(())
+  }
+
+  pub fn owner(&self) -> Address {
+    self.owner.get()
+  }
+
+  pub fn pending_owner(&self) -> Address {
+    self.pending_owner.get()
+  }
+
+  /// Starts a two-step handover; ownership only moves once `new_owner`
+  /// calls `accept_ownership`. Passing the zero address cancels a pending transfer.
+  pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.pending_owner.set(new_owner);
+
+    evm::log(OwnershipTransferStarted {
+      previous_owner: self.owner.get(),
+      new_owner,
+    });
+    Ok(())
+  }
+
+  pub fn accept_ownership(&mut self) -> Result<(), RegistryError> {
+    let sender = msg_sender();
+    if sender != self.pending_owner.get() {
+      return Err(RegistryError::NotPendingOwner(NotPendingOwner { account: sender }));
+    }
+
+    let previous_owner = self.owner.get();
+    self.owner.set(sender);
+    self.pending_owner.set(Address::ZERO);
+
+    evm::log(OwnershipChanged {
+      previous_owner,
+      new_owner: sender,
+    });
+    Ok(())
+  }
+
+  pub fn paused(&self) -> bool {
+    self.paused.get()
+  }
+
+  /// Emergency stop: blocks every task-level mutation until `unpause`.
+  /// Reads and ownership management keep working so the owner key can still be
+  /// rotated, and funds already owed to someone stay withdrawable.
+  pub fn pause(&mut self) -> Result<(), RegistryError> {
+    self.assert_owner_or_guardian()?;
+    self.assert_not_paused()?;
+    self.paused.set(true);
+
+    evm::log(Paused {
+      account: msg_sender(),
+    });
+    Ok(())
+  }
+
+  pub fn unpause(&mut self) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    if !self.paused.get() {
+      return Err(RegistryError::ExpectedPause(ExpectedPause {}));
+    }
+    self.paused.set(false);
+
+    evm::log(Unpaused {
+      account: msg_sender(),
+    });
+    Ok(())
+  }
+
+  pub fn guardian(&self) -> Address {
+    self.guardian.get()
+  }
+
+  /// Sets the guardian, who can `pause` and `yank_version` but not unpause,
+  /// publish or manage ownership. Zero disables the role.
+  pub fn set_guardian(&mut self, guardian: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    let previous_guardian = self.guardian.get();
+    self.guardian.set(guardian);
+
+    evm::log(GuardianChanged {
+      previous_guardian,
+      new_guardian: guardian,
+    });
+    Ok(())
+  }
+
+  /// Returns `(threshold, delay, guardian_count)` of the owner recovery scheme.
+  pub fn get_recovery_policy(&self) -> (u32, U64, u32) {
+    (
+      self.recovery_threshold.get().to::<u32>(),
+      self.recovery_delay.get(),
+      self.recovery_guardian_count.get().to::<u32>(),
+    )
+  }
+
+  /// Adds or removes a recovery guardian. Removing one never takes the
+  /// guardian count below the threshold.
+  pub fn set_recovery_guardian(&mut self, guardian: Address, enabled: bool) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    if self.recovery_guardians.get(guardian) == enabled {
+      return Ok(());
+    }
+    let count = self.recovery_guardian_count.get();
+    let count = if enabled { count + U32::from(1) } else { count - U32::from(1) };
+    let threshold = self.recovery_threshold.get();
+    if threshold > count {
+      return Err(RegistryError::InvalidRecoveryThreshold(InvalidRecoveryThreshold {
+        threshold: threshold.to::<u32>(),
+        guardian_count: count.to::<u32>(),
+      }));
+    }
+    self.recovery_guardians.insert(guardian, enabled);
+    self.recovery_guardian_count.set(count);
+
+    evm::log(RecoveryGuardianChanged { guardian, enabled });
+    Ok(())
+  }
+
+  pub fn set_recovery_policy(&mut self, threshold: u32, delay: U64) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    let guardian_count = self.recovery_guardian_count.get().to::<u32>();
+    if threshold > guardian_count {
+      return Err(RegistryError::InvalidRecoveryThreshold(InvalidRecoveryThreshold {
+        threshold,
+        guardian_count,
+      }));
+    }
+    self.recovery_threshold.set(U32::from(threshold));
+    self.recovery_delay.set(delay);
+
+    evm::log(RecoveryPolicyChanged {
+      threshold,
+      delay: delay.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Returns `(new_owner, approvals, executable_at)` of the pending recovery;
+  /// `new_owner` is zero if there is none.
+  pub fn get_recovery(&self) -> (Address, u32, U64) {
+    (
+      self.recovery.new_owner.get(),
+      self.recovery.approvals.get().to::<u32>(),
+      self.recovery.executable_at.get(),
+    )
+  }
+
+  /// Proposes replacing the owner with `new_owner`, counting as the proposing
+  /// guardian's approval. Works while paused, like the rest of the flow.
+  pub fn propose_recovery(&mut self, new_owner: Address) -> Result<(), RegistryError> {
+    let proposer = self.assert_recovery_guardian()?;
+    if new_owner == Address::ZERO {
+      return Err(RegistryError::InvalidRecipient(InvalidRecipient { to: new_owner }));
+    }
+    let pending = self.recovery.new_owner.get();
+    if pending != Address::ZERO {
+      return Err(RegistryError::RecoveryPending(RecoveryPending { new_owner: pending }));
+    }
+    let round = self.recovery.round.get() + U64::from(1);
+    self.recovery.round.set(round);
+    self.recovery.new_owner.set(new_owner);
+    self.recovery.approvals.set(U32::ZERO);
+    self.recovery.executable_at.set(U64::ZERO);
+
+    evm::log(RecoveryProposed { new_owner, proposer });
+    self.approve_recovery()
+  }
+
+  /// Approves the pending recovery. The approval that meets the threshold
+  /// starts the delay.
+  pub fn approve_recovery(&mut self) -> Result<(), RegistryError> {
+    let guardian = self.assert_recovery_guardian()?;
+    let new_owner = self.recovery.new_owner.get();
+    if new_owner == Address::ZERO {
+      return Err(RegistryError::NoRecoveryPending(NoRecoveryPending {}));
+    }
+    let round = self.recovery.round.get();
+    if self.recovery.approved_round.get(guardian) == round {
+      return Err(RegistryError::RecoveryAlreadyApproved(RecoveryAlreadyApproved { guardian }));
+    }
+    self.recovery.approved_round.insert(guardian, round);
+    let approvals = self.recovery.approvals.get() + U32::from(1);
+    self.recovery.approvals.set(approvals);
+    if approvals >= self.recovery_threshold.get() && self.recovery.executable_at.get() == U64::ZERO {
+      let executable_at = U64::from(block::timestamp()).saturating_add(self.recovery_delay.get());
+      self.recovery.executable_at.set(executable_at);
+    }
+
+    evm::log(RecoveryApproved {
+      new_owner,
+      guardian,
+      approvals: approvals.to::<u32>(),
+      executable_at: self.recovery.executable_at.get().to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Hands ownership to the recovered owner once the delay has passed. Anyone
+  /// may call it.
+  pub fn execute_recovery(&mut self) -> Result<(), RegistryError> {
+    let new_owner = self.recovery.new_owner.get();
+    if new_owner == Address::ZERO {
+      return Err(RegistryError::NoRecoveryPending(NoRecoveryPending {}));
+    }
+    let executable_at = self.recovery.executable_at.get();
+    let ready = executable_at != U64::ZERO
+      && self.recovery.approvals.get() >= self.recovery_threshold.get()
+      && U64::from(block::timestamp()) >= executable_at;
+    if !ready {
+      return Err(RegistryError::RecoveryNotReady(RecoveryNotReady {
+        executable_at: executable_at.to::<u64>(),
+      }));
+    }
+    self.recovery.new_owner.set(Address::ZERO);
+    let previous_owner = self.owner.get();
+    self.owner.set(new_owner);
+    self.pending_owner.set(Address::ZERO);
+
+    evm::log(OwnershipChanged {
+      previous_owner,
+      new_owner,
+    });
+    Ok(())
+  }
+
+  /// Lets the current owner, who evidently still holds the key, abort a recovery.
+  pub fn cancel_recovery(&mut self) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    let new_owner = self.recovery.new_owner.get();
+    if new_owner == Address::ZERO {
+      return Err(RegistryError::NoRecoveryPending(NoRecoveryPending {}));
+    }
+    self.recovery.new_owner.set(Address::ZERO);
+
+    evm::log(RecoveryCancelled { new_owner });
+    Ok(())
+  }
+
+  #[payable]
+  pub fn register_task(&mut self, task_id: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_may_register(&task_id)?;
+    self.collect_fee(Fee::Registration(1))?;
+    self.register(task_id)
+  }
+
+  /// `register_task` that also lists the task under `categories`.
+  #[payable]
+  pub fn register_task_with_categories(
+    &mut self,
+    task_id: String,
+    categories: Vec<String>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_may_register(&task_id)?;
+    self.collect_fee(Fee::Registration(1))?;
+    self.register(task_id.clone())?;
+    for category in categories {
+      self.categorize(&task_id, category)?;
+    }
+    Ok(())
+  }
+
+  /// Registers several tasks in one transaction, for the registration fee
+  /// times their count. Reverts as a whole if any id is taken or not the
+  /// caller's to register.
+  #[payable]
+  pub fn register_tasks(&mut self, task_ids: Vec<String>) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    for task_id in &task_ids {
+      self.assert_may_register(task_id)?;
+    }
+    self.collect_fee(Fee::Registration(task_ids.len()))?;
+    for task_id in task_ids {
+      self.register(task_id)?;
+    }
+    Ok(())
+  }
+
+  /// `register_tasks` that also publishes each task's first version, paying
+  /// both fees per entry.
+  #[payable]
+  pub fn register_tasks_with_hashes(
+    &mut self,
+    entries: Vec<(String, FixedBytes<32>)>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    for (task_id, _) in &entries {
+      self.assert_may_register(task_id)?;
+    }
+    self.collect_fee(Fee::Onboarding(entries.len()))?;
+    for (task_id, hash) in entries {
+      self.register(task_id.clone())?;
+      self.publish(task_id, NewVersion::new(hash))?;
+    }
+    Ok(())
+  }
+
+  pub fn task_count(&self) -> U64 {
+    U64::from(self.task_index.len())
+  }
+
+  /// Returns the ids among the `limit` tasks from `offset`, in registration
+  /// order, that are not archived; a page can therefore come back short.
+  pub fn task_ids(&self, offset: U64, limit: U64) -> Vec<String> {
+    page(self.task_index.len(), offset, limit)
+      .filter_map(|i| self.task_index.getter(i))
+      .map(|id| id.get_string())
+      .filter(|id| !self.tasks.getter(task_key(id)).archived.get())
+      .collect()
+  }
+
+  /// `task_ids` including archived tasks.
+  pub fn all_task_ids(&self, offset: U64, limit: U64) -> Vec<String> {
+    page(self.task_index.len(), offset, limit)
+      .filter_map(|i| self.task_index.getter(i))
+      .map(|id| id.get_string())
+      .collect()
+  }
+
+  #[payable]
+  pub fn publish_new_version(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    self.publish(task_id, NewVersion::new(hash))
+  }
+
+  /// `publish_new_version` carrying the model author's signature over
+  /// `get_release_digest(task_id, hash)`. The recovered author is recorded on
+  /// the version; it need not be the sender or a publisher.
+  #[payable]
+  pub fn publish_new_version_with_author_sig(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    author_signature: Bytes,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    let author = recover_signer(release_digest(&task_id, hash), &author_signature)
+      .ok_or(RegistryError::InvalidSignature(InvalidSignature {}))?;
+    self.publish(task_id, NewVersion {
+      author,
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// EIP-712 digest of `Release(string taskId,bytes32 hash)` that authors sign
+  /// for `publish_new_version_with_author_sig`.
+  pub fn get_release_digest(&self, task_id: String, hash: FixedBytes<32>) -> FixedBytes<32> {
+    release_digest(&task_id, hash)
+  }
+
+  /// Release author of a version, or zero if it was published unsigned.
+  pub fn get_author(&self, task_id: String, version: U64) -> Result<Address, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).author.get())
+  }
+
+  /// Same as `publish_new_version`, additionally committing to the diff from
+  /// the previous version's weights. Fails with `NoVersions` on a task's
+  /// first publish, since there is nothing to diff against.
+  #[payable]
+  pub fn publish_new_version_with_delta(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    delta_hash: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    self.publish(task_id, NewVersion {
+      delta_hash,
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// Record hash of the previous version as stored at publish; zero for version 1.
+  pub fn get_prev_record_hash(&self, task_id: String, version: U64) -> Result<FixedBytes<32>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).prev_record_hash.get())
+  }
+
+  /// Record hash of a version itself, the value a mirror anchors the chain to.
+  pub fn get_record_hash(&self, task_id: String, version: U64) -> Result<FixedBytes<32>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.record_hash(version))
+  }
+
+  /// Delta commitment of a version, or zero if it was published without one.
+  pub fn get_delta_hash(&self, task_id: String, version: U64) -> Result<FixedBytes<32>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).delta_hash.get())
+  }
+
+  /// Walks back from `version` through consecutive delta-committed versions.
+  /// Returns the base version the chain starts from (the nearest one without
+  /// a delta) and the deltas that lead from it to `version`, in apply order.
+  pub fn get_delta_chain(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<(U64, Vec<FixedBytes<32>>), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut base = version;
+    let mut deltas = Vec::new();
+    loop {
+      let delta_hash = task.versions.getter(base).delta_hash.get();
+      if delta_hash == FixedBytes::ZERO {
+        break;
+      }
+      deltas.push(delta_hash);
+      base -= U64::from(1);
+    }
+    deltas.reverse();
+    Ok((base, deltas))
+  }
+
+  /// Same as `publish_new_version`, additionally recording where the artifact
+  /// can be fetched from (e.g. an IPFS CID or HTTPS URL).
+  #[payable]
+  pub fn publish_new_version_with_uri(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    artifact_uri: String,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    self.publish(task_id, NewVersion {
+      artifact_uri,
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// Publishes a digest produced by something other than SHA-256, so verifiers
+  /// know how to recompute it. See the `HASH_*` constants for valid codes.
+  #[payable]
+  pub fn publish_new_version_with_algorithm(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    hash_algorithm: u8,
+    artifact_uri: String,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    self.publish(task_id, NewVersion {
+      hash_algorithm,
+      artifact_uri,
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// Publishes a version with its provenance: a commitment to the training
+  /// configuration (hyperparameters and code revision), the base model it was
+  /// fine-tuned from, and the `(dataset_id, revision)` pairs it was trained on.
+  /// A zero `config_hash` or an empty `parent_task_id` leaves that part unset;
+  /// a parent must already exist and may belong to the same task.
+  #[payable]
+  pub fn publish_new_version_with_metadata(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    config_hash: FixedBytes<32>,
+    parent_task_id: String,
+    parent_version: U64,
+    datasets: Vec<(String, U64)>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    self.publish(task_id, NewVersion {
+      config_hash,
+      parent: (!parent_task_id.is_empty()).then_some((parent_task_id, parent_version)),
+      datasets,
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// Publishes a version under an SPDX-style `license` with the given usage
+  /// permissions, which consumer contracts can check through `license_of`.
+  #[payable]
+  pub fn publish_new_version_with_license(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    license: String,
+    commercial_use: bool,
+    redistribution: bool,
+    fine_tuning: bool,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    let flags = [
+      (commercial_use, LICENSE_COMMERCIAL_USE),
+      (redistribution, LICENSE_REDISTRIBUTION),
+      (fine_tuning, LICENSE_FINE_TUNING),
+    ]
+    .into_iter()
+    .filter(|(allowed, _)| *allowed)
+    .fold(0, |flags, (_, bit)| flags | bit);
+    self.publish(task_id, NewVersion {
+      license: Some((license, flags)),
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// Publishes a version that depends on the given `(task_id, version)` pairs,
+  /// e.g. the tokenizer and embedder a pipeline runs with. Each must exist.
+  #[payable]
+  pub fn publish_new_version_with_dependencies(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    dependencies: Vec<(String, U64)>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    self.publish(task_id, NewVersion {
+      dependencies,
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// Publishes a version labelled `major.minor.patch`, which must be higher than
+  /// any semantic version the task already has. Versions keep their sequential
+  /// numbers; the label is additional.
+  #[payable]
+  pub fn publish_new_version_semver(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    major: u32,
+    minor: u32,
+    patch: u32,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    self.publish(task_id, NewVersion {
+      semver: Some((major, minor, patch)),
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// Publishes one version per `(task_id, hash)` entry. Any failure reverts the
+  /// whole batch, so either every task advances or none do.
+  #[payable]
+  pub fn publish_new_versions(
+    &mut self,
+    entries: Vec<(String, FixedBytes<32>)>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(entries.len()))?;
+    for (task_id, hash) in entries {
+      self.publish(task_id, NewVersion::new(hash))?;
+    }
+    Ok(())
+  }
+
+  /// First half of the front-running resistant flow: seals
+  /// `keccak(hash || salt)` without revealing the hash itself.
+  pub fn commit_version(&mut self, task_id: String, commitment: FixedBytes<32>) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_publisher(&task_id, msg_sender())?;
+    self.assert_not_frozen(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let mut sealed = task.commitments.setter(commitment);
+    if sealed.committer.get() != Address::ZERO {
+      return Err(RegistryError::CommitmentExists(CommitmentExists { task_id, commitment }));
+    }
+    sealed.committer.set(msg_sender());
+    sealed.block_number.set(U64::from(block::number()));
+
+    evm::log(VersionCommitted {
+      task_id,
+      commitment,
+      committer: msg_sender(),
+    });
+    Ok(())
+  }
+
+  /// Second half: publishes `hash` once it matches a commitment made by the
+  /// same sender in an earlier block.
+  #[payable]
+  pub fn reveal_version(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    salt: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    let new_version = NewVersion::new(hash);
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(new_version.hash.as_slice());
+    preimage[32..].copy_from_slice(salt.as_slice());
+    let commitment = crypto::keccak(preimage);
+
+    // `publish` counts the call.
+    let mut task = self.task_write(&task_id)?;
+    let sealed = task.commitments.getter(commitment);
+    if sealed.committer.get() != msg_sender() {
+      return Err(RegistryError::UnknownCommitment(UnknownCommitment { task_id, commitment }));
+    }
+    if sealed.block_number.get() >= U64::from(block::number()) {
+      return Err(RegistryError::RevealTooEarly(RevealTooEarly { task_id, commitment }));
+    }
+    task.commitments.delete(commitment);
+
+    self.publish(task_id, new_version)
+  }
+
+  pub fn task_admin(&self, task_id: String) -> Result<Address, RegistryError> {
+    Ok(self.task(&task_id)?.admin.get())
+  }
+
+  pub fn is_publisher(&self, task_id: String, account: Address) -> Result<bool, RegistryError> {
+    Ok(self.task(&task_id)?.publishers.get(account))
+  }
+
+  pub fn set_task_admin(&mut self, task_id: String, new_admin: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let previous_admin = task.admin.get();
+    task.admin.set(new_admin);
+    task.approved.set(Address::ZERO);
+    self.log_action(&task_id, ACTION_ADMIN_CHANGE, U64::ZERO, new_admin);
+
+    evm::log(TaskAdminChanged {
+      task_id,
+      previous_admin,
+      new_admin,
+    });
+    Ok(())
+  }
+
+  pub fn grant_publisher(&mut self, task_id: String, publisher: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    task.publishers.insert(publisher, true);
+    self.log_action(&task_id, ACTION_PUBLISHER_GRANT, U64::ZERO, publisher);
+
+    evm::log(PublisherGranted {
+      task_id,
+      publisher,
+      granted_by: msg_sender(),
+    });
+    Ok(())
+  }
+
+  pub fn revoke_publisher(&mut self, task_id: String, publisher: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    task.publishers.delete(publisher);
+    self.log_action(&task_id, ACTION_PUBLISHER_REVOKE, U64::ZERO, publisher);
+
+    evm::log(PublisherRevoked {
+      task_id,
+      publisher,
+      revoked_by: msg_sender(),
+    });
+    Ok(())
+  }
+
+  /// Returns the record of the version currently served as the task's latest.
+  pub fn get_latest(&self, task_id: String) -> Result<VersionRecord, RegistryError> {
+    let task = self.task(&task_id)?;
+    let show_uri = task.uri_visible_to(msg_sender());
+    if let Some(record) = task.cached_latest(show_uri) {
+      return Ok(record);
+    }
+    let latest = task.active_version();
+    if latest == U64::ZERO {
+      return Err(RegistryError::NoVersions(NoVersions { task_id }));
+    }
+    Ok(task.versions.getter(latest).record(latest, show_uri))
+  }
+
+  /// `get_latest`, but reverts with `StaleVersion` once the version is older
+  /// than the task's `max_age`, for consumers that would rather fail than
+  /// serve an outdated model.
+  pub fn get_latest_fresh(&self, task_id: String) -> Result<VersionRecord, RegistryError> {
+    let (record, stale) = self.get_latest_with_staleness(task_id.clone())?;
+    if stale {
+      return Err(RegistryError::StaleVersion(StaleVersion {
+        task_id: task_id.clone(),
+        version: record.0.to::<u64>(),
+        timestamp: record.2.to::<u64>(),
+        max_age: self.task(&task_id)?.max_age.get().to::<u64>(),
+      }));
+    }
+    Ok(record)
+  }
+
+  /// `get_latest` plus whether the version is older than the task's `max_age`.
+  /// Never reverts for staleness.
+  pub fn get_latest_with_staleness(&self, task_id: String) -> Result<(VersionRecord, bool), RegistryError> {
+    let record = self.get_latest(task_id.clone())?;
+    let stale = self.task(&task_id)?.is_stale(record.2);
+    Ok((record, stale))
+  }
+
+  /// Returns a `LatestSummary` for each task in order. Tasks with no
+  /// versions yet yield a zeroed entry instead of reverting the whole call.
+  pub fn get_latest_many(
+    &self,
+    task_ids: Vec<String>,
+  ) -> Result<Vec<LatestSummary>, RegistryError> {
+    let mut out = Vec::with_capacity(task_ids.len());
+    for task_id in task_ids {
+      let task = self.task(&task_id)?;
+      let latest = task.active_version();
+      let info = task.versions.getter(latest);
+      let show_uri = task.uri_visible_to(msg_sender());
+      out.push((
+        latest,
+        info.hash.get(),
+        info.timestamp.get(),
+        if show_uri { info.artifact_uri.get_string() } else { String::new() },
+      ));
+    }
+    Ok(out)
+  }
+
+  /// Returns the full record for a specific, previously published version.
+  pub fn get_version(&self, task_id: String, version: U64) -> Result<VersionRecord, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).record(version, task.uri_visible_to(msg_sender())))
+  }
+
+  /// Returns up to `limit` consecutive versions starting at `from_version`.
+  pub fn get_versions_range(
+    &self,
+    task_id: String,
+    from_version: U64,
+    limit: U64,
+  ) -> Result<Vec<VersionRecord>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(from_version) {
+      return Err(version_not_found(&task_id, from_version));
+    }
+
+    let last = task
+      .version_count
+      .get()
+      .min(from_version.saturating_add(limit).saturating_sub(U64::from(1)));
+    let show_uri = task.uri_visible_to(msg_sender());
+    let mut out = Vec::new();
+    let mut version = from_version;
+    while version <= last {
+      out.push(task.versions.getter(version).record(version, show_uri));
+      version += U64::from(1);
+    }
+    Ok(out)
+  }
+
+  /// Returns the `(task_id, version)` under which `hash` was first published.
+  pub fn lookup_hash(&self, hash: FixedBytes<32>) -> Result<(String, U64), RegistryError> {
+    let location = self.hash_index.getter(hash);
+    if location.version.get() == U64::ZERO {
+      return Err(RegistryError::HashNotFound(HashNotFound { hash }));
+    }
+    Ok((location.task_id.get_string(), location.version.get()))
+  }
+
+  /// Number of versions published for the task; versions are numbered `1..=count`.
+  pub fn get_version_count(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.version_count.get())
+  }
+
+  /// Marks a version as unsafe to consume. `successor_version` points consumers
+  /// at a replacement and must be another live version, or zero for none.
+  pub fn yank_version(
+    &mut self,
+    task_id: String,
+    version: U64,
+    reason: String,
+    successor_version: U64,
+  ) -> Result<(), RegistryError> {
+    // The guardian can yank on any task, including while the registry is paused.
+    if msg_sender() != self.guardian.get() {
+      self.assert_not_paused()?;
+      self.assert_task_admin(&task_id)?;
+    }
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    if successor_version != U64::ZERO
+      && (successor_version == version
+        || !task.has_version(successor_version)
+        || task.versions.getter(successor_version).yanked.get())
+    {
+      return Err(RegistryError::InvalidSuccessor(InvalidSuccessor {
+        task_id,
+        successor_version: successor_version.to::<u64>(),
+      }));
+    }
+
+    let operation_nonce = task.operation_nonces.get(msg_sender());
+    let mut info = task.versions.setter(version);
+    if info.yanked.get() {
+      return Err(RegistryError::VersionAlreadyYanked(VersionAlreadyYanked {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    info.yanked.set(true);
+    info.yank_reason.set_str(&reason);
+    info.successor_version.set(successor_version);
+
+    evm::log(VersionYanked {
+      task_id: task_id.clone(),
+      version: version.to::<u64>(),
+      reason,
+      successor_version: successor_version.to::<u64>(),
+      operation_nonce: operation_nonce.to::<u64>(),
+    });
+    self.log_action(&task_id, ACTION_YANK, version, Address::ZERO);
+    self.log_pinned_yank(&task_id, version);
+    Ok(())
+  }
+
+  /// Returns `(yanked, reason, successor_version)` for a version.
+  pub fn get_yank_status(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<(bool, String, U64), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+
+    let info = task.versions.getter(version);
+    Ok((info.yanked.get(), info.yank_reason.get_string(), info.successor_version.get()))
+  }
+
+  /// Flags a version as on its way out, with a hash of the reason and the
+  /// date support ends, so consumers can plan a migration. Calling it again
+  /// replaces the record; `sunset_at` must be zero or in the future.
+  pub fn deprecate_version(
+    &mut self,
+    task_id: String,
+    version: U64,
+    reason_hash: FixedBytes<32>,
+    sunset_at: U64,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    if sunset_at != U64::ZERO && sunset_at <= U64::from(block::timestamp()) {
+      return Err(RegistryError::InvalidSunset(InvalidSunset {
+        sunset_at: sunset_at.to::<u64>(),
+      }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    info.deprecated.set(true);
+    info.deprecation_reason.set(reason_hash);
+    info.sunset_at.set(sunset_at);
+
+    evm::log(VersionDeprecated {
+      task_id,
+      version: version.to::<u64>(),
+      reason_hash,
+      sunset_at: sunset_at.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Returns `(deprecated, reason_hash, sunset_at)` for a version.
+  pub fn get_deprecation(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<(bool, FixedBytes<32>, U64), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+
+    let info = task.versions.getter(version);
+    Ok((info.deprecated.get(), info.deprecation_reason.get(), info.sunset_at.get()))
+  }
+
+  /// Like `get_latest`, but skips yanked versions and returns the newest live one.
+  pub fn get_latest_unyanked(&self, task_id: String) -> Result<VersionRecord, RegistryError> {
+    let task = self.task(&task_id)?;
+    let now = U64::from(block::timestamp());
+    let mut version = task.latest_version.get();
+    // Pruned versions read back as zeroes, which would pass for live.
+    while version > task.pruned_through.get() {
+      let info = task.versions.getter(version);
+      if info.is_active(now) && !info.yanked.get() {
+        return Ok(info.record(version, task.uri_visible_to(msg_sender())));
+      }
+      version -= U64::from(1);
+    }
+    Err(RegistryError::NoVersions(NoVersions { task_id }))
+  }
+
+  pub fn get_activation_delay(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.activation_delay.get())
+  }
+
+  /// Sets how long new versions of the task wait before `get_latest` serves them.
+  /// Only affects versions published afterwards.
+  pub fn set_activation_delay(&mut self, task_id: String, activation_delay: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_owner()?;
+    self.task_mut(&task_id)?.activation_delay.set(activation_delay);
+
+    evm::log(ActivationDelayChanged {
+      task_id,
+      activation_delay: activation_delay.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Returns `(activates_at, cancelled)` for a version.
+  pub fn get_activation_status(&self, task_id: String, version: U64) -> Result<(U64, bool), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+
+    let info = task.versions.getter(version);
+    Ok((info.activates_at.get(), info.cancelled.get()))
+  }
+
+  /// Owner override that discards a version still inside its review window.
+  /// History keeps the record; it just never becomes latest.
+  pub fn cancel_pending_version(&mut self, task_id: String, version: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_owner()?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    if info.cancelled.get() || info.activates_at.get() <= U64::from(block::timestamp()) {
+      return Err(RegistryError::VersionNotPending(VersionNotPending {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    info.cancelled.set(true);
+    task.cache_latest();
+
+    evm::log(PendingVersionCancelled {
+      task_id,
+      version: version.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Points `get_latest` back at an earlier, known-good version without touching
+  /// history. The next publish moves latest forward again as usual.
+  pub fn rollback_latest(&mut self, task_id: String, to_version: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.assert_not_frozen(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let from_version = task.active_version();
+    let target_ok = task.has_version(to_version) && to_version < from_version && {
+      let info = task.versions.getter(to_version);
+      info.is_active(U64::from(block::timestamp())) && !info.yanked.get()
+    };
+    if !target_ok {
+      return Err(RegistryError::InvalidRollbackTarget(InvalidRollbackTarget {
+        task_id,
+        version: to_version.to::<u64>(),
+      }));
+    }
+    task.latest_version.set(to_version);
+    task.cache_latest();
+    self.log_action(&task_id, ACTION_ROLLBACK, to_version, Address::ZERO);
+
+    evm::log(Rollback {
+      task_id,
+      from_version: from_version.to::<u64>(),
+      to_version: to_version.to::<u64>(),
+      admin: msg_sender(),
+    });
+    Ok(())
+  }
+
+  /// Points `tag` at `version`; a zero version removes the tag.
+  pub fn set_tag(&mut self, task_id: String, tag: String, version: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if version != U64::ZERO && !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    task.tags.insert(tag.clone(), version);
+
+    evm::log(TagSet {
+      task_id,
+      tag,
+      version: version.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  pub fn get_by_tag(&self, task_id: String, tag: String) -> Result<VersionRecord, RegistryError> {
+    let task = self.task(&task_id)?;
+    let version = task.tags.get(tag.clone());
+    if version == U64::ZERO {
+      return Err(RegistryError::TagNotFound(TagNotFound { task_id, tag }));
+    }
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).record(version, task.uri_visible_to(msg_sender())))
+  }
+
+  pub fn is_frozen(&self, task_id: String) -> Result<bool, RegistryError> {
+    Ok(self.task(&task_id)?.frozen.get())
+  }
+
+  /// Permanently finalizes a task, e.g. after a regulatory audit. There is no unfreeze.
+  pub fn freeze_task(&mut self, task_id: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.assert_not_frozen(&task_id)?;
+    self.task_mut(&task_id)?.frozen.set(true);
+
+    evm::log(TaskFrozen {
+      task_id,
+      frozen_by: msg_sender(),
+    });
+    Ok(())
+  }
+
+  /// Returns `(archived, tombstone_reason)`.
+  pub fn get_archive_status(&self, task_id: String) -> Result<(bool, String), RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok((task.archived.get(), task.tombstone_reason.get_string()))
+  }
+
+  /// Retires a task: publishing stops and it drops out of `task_ids`, while
+  /// its versions stay readable. Undone with `unarchive_task`.
+  pub fn archive_task(&mut self, task_id: String, reason: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.assert_not_archived(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    task.archived.set(true);
+    task.tombstone_reason.set_str(&reason);
+
+    evm::log(TaskArchived {
+      task_id,
+      reason,
+      archived_by: msg_sender(),
+    });
+    Ok(())
+  }
+
+  pub fn unarchive_task(&mut self, task_id: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.archived.get() {
+      return Err(RegistryError::TaskNotArchived(TaskNotArchived { task_id }));
+    }
+    task.archived.set(false);
+    task.tombstone_reason.set_str("");
+
+    evm::log(TaskUnarchived {
+      task_id,
+      unarchived_by: msg_sender(),
+    });
+    Ok(())
+  }
+
+  /// Returns `(registration_fee, publish_fee)` in wei.
+  pub fn fees(&self) -> (U256, U256) {
+    (self.registration_fee.get(), self.publish_fee.get())
+  }
+
+  pub fn collected_fees(&self) -> U256 {
+    self.collected_fees.get()
+  }
+
+  pub fn set_fee(&mut self, registration_fee: U256, publish_fee: U256) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.registration_fee.set(registration_fee);
+    self.publish_fee.set(publish_fee);
+
+    evm::log(FeesUpdated {
+      registration_fee,
+      publish_fee,
+    });
+    Ok(())
+  }
+
+  /// Sends every collected fee to `to`. The balance is zeroed before the
+  /// transfer so a reentrant recipient cannot withdraw twice.
+  pub fn withdraw_fees(&mut self, to: Address) -> Result<(), RegistryError> {
+    // Not pausable: fees already collected stay withdrawable.
+    self.assert_owner()?;
+    let amount = self.collected_fees.get();
+    self.collected_fees.set(U256::ZERO);
+    if call::transfer_eth(to, amount).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to, amount }));
+    }
+
+    evm::log(FeesWithdrawn { to, amount });
+    Ok(())
+  }
+
+  /// Returns `(gated, price_per_second)` for the task.
+  pub fn get_gating(&self, task_id: String) -> Result<(bool, U256), RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok((task.gated.get(), task.subscription_price.get()))
+  }
+
+  pub fn set_gating(
+    &mut self,
+    task_id: String,
+    gated: bool,
+    price_per_second: U256,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    task.gated.set(gated);
+    task.subscription_price.set(price_per_second);
+
+    evm::log(GatingUpdated {
+      task_id,
+      gated,
+      price_per_second,
+    });
+    Ok(())
+  }
+
+  /// Buys `duration` seconds of access to a gated task's artifact URIs,
+  /// extending any subscription that is still running. Calls sending no ETH
+  /// pay in the payment token, if one is configured and the task prices
+  /// subscriptions in it.
+  #[payable]
+  pub fn subscribe(&mut self, task_id: String, duration: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    let subscriber = msg_sender();
+    if duration == U64::ZERO {
+      return Err(RegistryError::InvalidDuration(InvalidDuration { duration: 0 }));
+    }
+    let token = self.payment_token.get();
+    let task = self.task(&task_id)?;
+    if !task.gated.get() {
+      return Err(RegistryError::TaskNotGated(TaskNotGated { task_id }));
+    }
+    // Without a token price the subscription can only be bought with ETH.
+    let in_token = self.pays_in_token() && !task.token_subscription_price.get().is_zero();
+    let price = if in_token { task.token_subscription_price.get() } else { task.subscription_price.get() };
+    let required = price.saturating_mul(U256::from(duration));
+
+    let paid = if in_token {
+      self.pull_token(token, subscriber, required)?;
+      required
+    } else {
+      let provided = msg::value();
+      if provided < required {
+        return Err(RegistryError::InsufficientFee(InsufficientFee { required, provided }));
+      }
+      provided
+    };
+
+    let mut task = self.task_mut(&task_id)?;
+    let now = U64::from(block::timestamp());
+    let expires_at = task.subscriptions.get(subscriber).max(now).saturating_add(duration);
+    task.subscriptions.insert(subscriber, expires_at);
+    if !in_token {
+      let total_paid = task.subscription_paid.get(subscriber) + paid;
+      task.subscription_paid.insert(subscriber, total_paid);
+    }
+    let parent_task_id = task.royalty_parent.get_string();
+    let royalty = paid * U256::from(task.royalty_bps.get()) / U256::from(BPS);
+
+    let revenue_token = if in_token { token } else { Address::ZERO };
+    self.credit_subscription_revenue(&task_id, revenue_token, paid - royalty);
+    if !royalty.is_zero() {
+      self.credit_subscription_revenue(&parent_task_id, revenue_token, royalty);
+      evm::log(RoyaltyPaid {
+        task_id: task_id.clone(),
+        parent_task_id,
+        token: revenue_token,
+        amount: royalty,
+      });
+    }
+
+    evm::log(Subscribed {
+      task_id,
+      subscriber,
+      expires_at: expires_at.to::<u64>(),
+      paid,
+    });
+    Ok(())
+  }
+
+  /// Timestamp at which `account`'s access to the task expires; zero if never subscribed.
+  pub fn subscription_expiry(&self, task_id: String, account: Address) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.subscriptions.get(account))
+  }
+
+  pub fn is_subscriber(&self, task_id: String, account: Address) -> Result<bool, RegistryError> {
+    let expiry = self.task(&task_id)?.subscriptions.get(account);
+    Ok(expiry > U64::from(block::timestamp()))
+  }
+
+  /// Grants `consumer` access to the task's gated artifacts until `expires_at`,
+  /// replacing any earlier grant; no payment is involved.
+  pub fn issue_capability(
+    &mut self,
+    task_id: String,
+    consumer: Address,
+    expires_at: U64,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    if expires_at <= U64::from(block::timestamp()) {
+      return Err(RegistryError::InvalidExpiry(InvalidExpiry {
+        expires_at: expires_at.to::<u64>(),
+      }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    task.capabilities.insert(consumer, expires_at);
+
+    evm::log(CapabilityIssued {
+      task_id,
+      consumer,
+      expires_at: expires_at.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  pub fn revoke_capability(&mut self, task_id: String, consumer: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    task.capabilities.delete(consumer);
+
+    evm::log(CapabilityRevoked { task_id, consumer });
+    Ok(())
+  }
+
+  /// Whether `consumer` holds an unexpired capability for the task. Only the
+  /// admin's grant counts, not subscriptions; see `is_subscriber`.
+  pub fn has_capability(&self, task_id: String, consumer: Address) -> Result<bool, RegistryError> {
+    let expiry = self.task(&task_id)?.capabilities.get(consumer);
+    Ok(expiry > U64::from(block::timestamp()))
+  }
+
+  /// Timestamp at which `consumer`'s capability expires; zero if none was issued.
+  pub fn capability_expiry(&self, task_id: String, consumer: Address) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.capabilities.get(consumer))
+  }
+
+  pub fn subscription_revenue(&self, task_id: String) -> Result<U256, RegistryError> {
+    Ok(self.task(&task_id)?.subscription_revenue.get())
+  }
+
+  /// Pays out the task's accumulated subscription revenue to `to`.
+  pub fn withdraw_subscription_revenue(&mut self, task_id: String, to: Address) -> Result<(), RegistryError> {
+    // Not pausable: revenue already earned stays withdrawable.
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let amount = task.subscription_revenue.get();
+    task.subscription_revenue.set(U256::ZERO);
+    if call::transfer_eth(to, amount).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to, amount }));
+    }
+
+    evm::log(SubscriptionRevenueWithdrawn { task_id, to, amount });
+    Ok(())
+  }
+
+  /// Nonce the next signature-based publish for the task must sign over.
+  pub fn publish_nonce(&self, task_id: String) -> Result<U256, RegistryError> {
+    Ok(self.task(&task_id)?.publish_nonce.get())
+  }
+
+  /// Gasless publish: a relayer submits an EIP-712 `Publish` message signed by an
+  /// authorized publisher, who is recorded as the version's publisher. The nonce
+  /// is per task, so each signature can be used once and only in order.
+  #[payable]
+  pub fn publish_with_sig(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    deadline: U256,
+    v: u8,
+    r: FixedBytes<32>,
+    s: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    let mut new_version = NewVersion::new(hash);
+    let digest = self.consume_publish_digest(&task_id, new_version.hash, deadline)?;
+    new_version.publisher = ecrecover(digest, v, r, s)
+      .ok_or(RegistryError::InvalidSignature(InvalidSignature {}))?;
+
+    self.publish(task_id, new_version)
+  }
+
+  /// Variant of `publish_with_sig` for an explicitly named `signer`, which may be
+  /// a contract wallet (e.g. a Safe) validated through ERC-1271. EOAs pass the
+  /// usual 65-byte `r || s || v` signature.
+  #[payable]
+  pub fn publish_with_signer_sig(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    signer: Address,
+    deadline: U256,
+    signature: Bytes,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.collect_fee(Fee::Publish(1))?;
+    let mut new_version = NewVersion::new(hash);
+    let digest = self.consume_publish_digest(&task_id, new_version.hash, deadline)?;
+    if !is_valid_signature(signer, digest, &signature) {
+      return Err(RegistryError::InvalidSignature(InvalidSignature {}));
+    }
+    new_version.publisher = signer;
+
+    self.publish(task_id, new_version)
+  }
+
+  /// Binds a Groth16 verification key to a version. Keys are immutable once set,
+  /// and must hash to the version's published `vk_hash` if there is one.
+  pub fn set_groth16_key(&mut self, task_id: String, version: U64, vk: Bytes) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    if !info.groth16_vk.is_empty() {
+      return Err(RegistryError::VerificationKeyAlreadySet(VerificationKeyAlreadySet {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let published = info.vk_hash.get();
+    if groth16::input_count(&vk).is_none() || (published != FixedBytes::ZERO && crypto::keccak(&vk) != published) {
+      return Err(RegistryError::InvalidVerificationKey(InvalidVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    info.groth16_vk.set_bytes(&vk);
+
+    evm::log(Groth16KeySet {
+      task_id,
+      version: version.to::<u64>(),
+      vk_digest: crypto::keccak(&vk),
+    });
+    Ok(())
+  }
+
+  pub fn get_groth16_key(&self, task_id: String, version: U64) -> Result<Bytes, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).groth16_vk.get_bytes().into())
+  }
+
+  /// Checks a proof of an inference and records it when valid: through the
+  /// task's external verifier if one is set, otherwise as Groth16 against the
+  /// version's key. By convention the first two public inputs are the input
+  /// and output commitments, which end up in the attestation log. Invalid
+  /// proofs, including a verifier that reverts, return `false` rather than
+  /// reverting, so the attempt still shows up in the event log.
+  pub fn verify_inference(
+    &mut self,
+    task_id: String,
+    version: U64,
+    proof: Bytes,
+    public_inputs: Vec<U256>,
+  ) -> Result<bool, RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    if !self.task(&task_id)?.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    self.assert_breaker_closed(&task_id, version)?;
+    let verifier = self.task(&task_id)?.external_verifier.get();
+    let valid = if verifier != Address::ZERO {
+      let packed: Vec<u8> = public_inputs.iter().flat_map(|input| input.to_be_bytes::<32>()).collect();
+      IProofVerifier::new(verifier)
+        .verify(call::Call::new(), proof.0.into(), packed.into())
+        .unwrap_or(false)
+    } else {
+      let vk = self.task(&task_id)?.versions.getter(version).groth16_vk.get_bytes();
+      if vk.is_empty() {
+        return Err(RegistryError::NoVerificationKey(NoVerificationKey {
+          task_id,
+          version: version.to::<u64>(),
+        }));
+      }
+      groth16::verify(&vk, &proof, &public_inputs)
+    };
+
+    let mut task = self.task_mut(&task_id)?;
+    let mut info = task.versions.setter(version);
+    let inference_id = crypto::keccak(public_inputs.abi_encode());
+    if valid && !info.verified_inferences.get(inference_id) {
+      info.verified_inferences.insert(inference_id, true);
+      let count = info.verified_count.get() + U64::from(1);
+      info.verified_count.set(count);
+
+      let commitment = |i: usize| public_inputs.get(i).map(|x| B256::from(*x)).unwrap_or_default();
+      let mut attestation = info.attestations.grow();
+      attestation.input_commitment.set(commitment(0));
+      attestation.output_commitment.set(commitment(1));
+      attestation.prover.set(msg_sender());
+      attestation.block_number.set(U64::from(block::number()));
+    }
+
+    if !valid {
+      self.record_proof_failure(&task_id, version);
+    }
+
+    evm::log(InferenceVerified {
+      task_id,
+      version: version.to::<u64>(),
+      inference_id,
+      prover: msg_sender(),
+      valid,
+    });
+    Ok(valid)
+  }
+
+  pub fn get_external_verifier(&self, task_id: String) -> Result<Address, RegistryError> {
+    Ok(self.task(&task_id)?.external_verifier.get())
+  }
+
+  /// Routes the task's `verify_inference` calls to a contract implementing
+  /// `IProofVerifier`, for proof systems other than Groth16. Zero switches
+  /// back to the built-in verifier and per-version keys.
+  pub fn set_external_verifier(&mut self, task_id: String, verifier: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let previous_verifier = task.external_verifier.get();
+    task.external_verifier.set(verifier);
+
+    evm::log(ExternalVerifierChanged {
+      task_id,
+      previous_verifier,
+      new_verifier: verifier,
+    });
+    Ok(())
+  }
+
+  /// `verify_inference` for versions published with `HASH_POSEIDON`: the
+  /// version's hash is appended as the last public input, so circuits can bind
+  /// the proof to the committed weights without callers supplying them.
+  pub fn verify_committed_inference(
+    &mut self,
+    task_id: String,
+    version: U64,
+    proof: Bytes,
+    mut public_inputs: Vec<U256>,
+  ) -> Result<bool, RegistryError> {
+    let task_id = self.canonical_id(&task_id);
+    let commitment = {
+      let task = self.task(&task_id)?;
+      if !task.has_version(version) {
+        return Err(version_not_found(&task_id, version));
+      }
+      let info = task.versions.getter(version);
+      if info.hash_algorithm.get() != U8::from(HASH_POSEIDON) {
+        return Err(RegistryError::NotPoseidonCommitted(NotPoseidonCommitted {
+          task_id,
+          version: version.to::<u64>(),
+        }));
+      }
+      info.hash.get()
+    };
+    public_inputs.push(field_element(commitment)?);
+    self.verify_inference(task_id, version, proof, public_inputs)
+  }
+
+  /// Poseidon commitment to `inputs`, chained pairwise from zero, as a
+  /// `HASH_POSEIDON` version hash. Every input must be a field element.
+  pub fn poseidon_commitment(&self, inputs: Vec<U256>) -> Result<FixedBytes<32>, RegistryError> {
+    if let Some(value) = inputs.iter().find(|input| **input >= groth16::SCALAR_FIELD) {
+      return Err(RegistryError::InvalidFieldElement(InvalidFieldElement { value: *value }));
+    }
+    Ok(poseidon::chain(&inputs).unwrap_or_default().into())
+  }
+
+  /// Whether an inference, identified by the keccak of its ABI-encoded public
+  /// inputs, has a verified proof against the version.
+  pub fn is_inference_verified(
+    &self,
+    task_id: String,
+    version: U64,
+    inference_id: FixedBytes<32>,
+  ) -> Result<bool, RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok(task.versions.getter(version).verified_inferences.get(inference_id))
+  }
+
+  pub fn verified_inference_count(&self, task_id: String, version: U64) -> Result<U64, RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok(task.versions.getter(version).verified_count.get())
+  }
+
+  /// Publishes the digest and location of the version's verification key.
+  /// Can be set once per version; a zero hash, or one that is not the keccak of
+  /// an already bound Groth16 key, is rejected.
+  pub fn set_verification_key(
+    &mut self,
+    task_id: String,
+    version: U64,
+    vk_hash: FixedBytes<32>,
+    vk_uri: String,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    let bound = info.groth16_vk.get_bytes();
+    if vk_hash == FixedBytes::ZERO || (!bound.is_empty() && crypto::keccak(&bound) != vk_hash) {
+      return Err(RegistryError::InvalidVerificationKey(InvalidVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    if info.vk_hash.get() != FixedBytes::ZERO {
+      return Err(RegistryError::VerificationKeyAlreadySet(VerificationKeyAlreadySet {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    info.vk_hash.set(vk_hash);
+    info.vk_uri.set_str(&vk_uri);
+
+    evm::log(VerificationKeySet {
+      task_id,
+      version: version.to::<u64>(),
+      vk_hash,
+      vk_uri,
+    });
+    Ok(())
+  }
+
+  /// Returns `(vk_hash, vk_uri)` for a version.
+  pub fn get_verification_key(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<(FixedBytes<32>, String), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    if info.vk_hash.get() == FixedBytes::ZERO {
+      return Err(RegistryError::NoVerificationKey(NoVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    Ok((info.vk_hash.get(), info.vk_uri.get_string()))
+  }
+
+  pub fn attestation_count(&self, task_id: String, version: U64) -> Result<U64, RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok(U64::from(task.versions.getter(version).attestations.len()))
+  }
+
+  /// Returns up to `limit` attestations for a version, oldest first, starting at `offset`.
+  pub fn get_attestations(
+    &self,
+    task_id: String,
+    version: U64,
+    offset: U64,
+    limit: U64,
+  ) -> Result<Vec<AttestationRecord>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+
+    Ok(
+      page(info.attestations.len(), offset, limit)
+        .filter_map(|i| info.attestations.getter(i))
+        .map(|a| {
+          (
+            a.input_commitment.get(),
+            a.output_commitment.get(),
+            a.prover.get(),
+            a.block_number.get(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  pub fn verifier_router(&self) -> Address {
+    self.verifier_router.get()
+  }
+
+  pub fn set_verifier_router(&mut self, router: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    let previous_router = self.verifier_router.get();
+    self.verifier_router.set(router);
+
+    evm::log(VerifierRouterChanged {
+      previous_router,
+      new_router: router,
+    });
+    Ok(())
+  }
+
+  pub fn trusted_forwarder(&self) -> Address {
+    self.trusted_forwarder.get()
+  }
+
+  /// ERC-2771 discovery: whether calls from `forwarder` are taken as relayed.
+  pub fn is_trusted_forwarder(&self, forwarder: Address) -> bool {
+    forwarder != Address::ZERO && forwarder == self.trusted_forwarder.get()
+  }
+
+  /// Zero disables relaying; calls from the old forwarder are then its own.
+  pub fn set_trusted_forwarder(&mut self, forwarder: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    let previous_forwarder = self.trusted_forwarder.get();
+    self.trusted_forwarder.set(forwarder);
+
+    evm::log(TrustedForwarderChanged {
+      previous_forwarder,
+      new_forwarder: forwarder,
+    });
+    Ok(())
+  }
+
+  /// Binds the zkVM image id whose receipts attest inferences for a version.
+  /// Can be set once per version; a zero id is rejected.
+  pub fn set_image_id(&mut self, task_id: String, version: U64, image_id: FixedBytes<32>) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    if image_id == FixedBytes::ZERO {
+      return Err(RegistryError::InvalidVerificationKey(InvalidVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let mut info = task.versions.setter(version);
+    if info.image_id.get() != FixedBytes::ZERO {
+      return Err(RegistryError::VerificationKeyAlreadySet(VerificationKeyAlreadySet {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    info.image_id.set(image_id);
+
+    evm::log(ImageIdSet {
+      task_id,
+      version: version.to::<u64>(),
+      image_id,
+    });
+    Ok(())
+  }
+
+  pub fn get_image_id(&self, task_id: String, version: U64) -> Result<FixedBytes<32>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let image_id = task.versions.getter(version).image_id.get();
+    if image_id == FixedBytes::ZERO {
+      return Err(RegistryError::NoVerificationKey(NoVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    Ok(image_id)
+  }
+
+  /// Checks a zkVM receipt for `journal` through the verifier router and, when
+  /// valid, records its SHA-256 journal digest against the version. `image_id`
+  /// must match the one registered for the version. Like `verify_inference`,
+  /// a rejected seal returns `false` instead of reverting.
+  pub fn verify_receipt(
+    &mut self,
+    task_id: String,
+    version: U64,
+    image_id: FixedBytes<32>,
+    journal: Bytes,
+    seal: Bytes,
+  ) -> Result<bool, RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    let router = self.verifier_router.get();
+    if router == Address::ZERO {
+      return Err(RegistryError::NoVerifierRouter(NoVerifierRouter {}));
+    }
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    self.assert_breaker_closed(&task_id, version)?;
+    let registered = self.task(&task_id)?.versions.getter(version).image_id.get();
+    if registered == FixedBytes::ZERO {
+      return Err(RegistryError::NoVerificationKey(NoVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    if image_id != registered {
+      return Err(RegistryError::ImageIdMismatch(ImageIdMismatch {
+        task_id,
+        version: version.to::<u64>(),
+        image_id,
+      }));
+    }
+
+    let journal_digest = sha256(&journal).unwrap_or_default();
+    let valid = journal_digest != B256::ZERO
+      && IReceiptVerifier::new(router)
+        .verify(call::Call::new(), seal.0.into(), image_id, journal_digest)
+        .is_ok();
+
+    if valid {
+      let mut task = self.task_mut(&task_id)?;
+      task.versions.setter(version).verified_journals.insert(journal_digest, true);
+    } else {
+      self.record_proof_failure(&task_id, version);
+    }
+
+    evm::log(ReceiptVerified {
+      task_id,
+      version: version.to::<u64>(),
+      journal_digest,
+      prover: msg_sender(),
+      valid,
+    });
+    Ok(valid)
+  }
+
+  /// Whether a receipt committing to the journal with this SHA-256 digest has
+  /// been verified against the version.
+  pub fn is_journal_verified(
+    &self,
+    task_id: String,
+    version: U64,
+    journal_digest: FixedBytes<32>,
+  ) -> Result<bool, RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok(task.versions.getter(version).verified_journals.get(journal_digest))
+  }
+
+  /// Binds the aggregation circuit's Groth16 key to a version. Immutable once set.
+  pub fn set_aggregation_key(&mut self, task_id: String, version: U64, vk: Bytes) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    if !info.aggregation_vk.is_empty() {
+      return Err(RegistryError::VerificationKeyAlreadySet(VerificationKeyAlreadySet {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    if groth16::input_count(&vk) != Some(3) {
+      return Err(RegistryError::InvalidVerificationKey(InvalidVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    info.aggregation_vk.set_bytes(&vk);
+
+    evm::log(AggregationKeySet {
+      task_id,
+      version: version.to::<u64>(),
+      vk_digest: crypto::keccak(&vk),
+    });
+    Ok(())
+  }
+
+  /// Checks one aggregated proof covering `batch_size` inferences and, when
+  /// valid, stores `batch_root`, the Merkle root over the batch's
+  /// `batch_leaf(input, output)` commitments. Invalid proofs return `false`.
+  pub fn verify_batch(
+    &mut self,
+    task_id: String,
+    version: U64,
+    proof: Bytes,
+    batch_root: FixedBytes<32>,
+    batch_size: U64,
+  ) -> Result<bool, RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    if !self.task(&task_id)?.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    self.assert_breaker_closed(&task_id, version)?;
+    let mut task = self.task_mut(&task_id)?;
+    let mut info = task.versions.setter(version);
+    let vk = info.aggregation_vk.get_bytes();
+    if vk.is_empty() {
+      return Err(RegistryError::NoVerificationKey(NoVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+
+    let root = U256::from_be_bytes(batch_root.0);
+    let public_inputs = [root >> 128, root & U256::from(u128::MAX), U256::from(batch_size)];
+    let valid = batch_size > U64::ZERO && groth16::verify(&vk, &proof, &public_inputs);
+    if valid {
+      info.batch_roots.insert(batch_root, batch_size);
+    } else {
+      self.record_proof_failure(&task_id, version);
+    }
+
+    evm::log(BatchVerified {
+      task_id,
+      version: version.to::<u64>(),
+      batch_root,
+      batch_size: batch_size.to::<u64>(),
+      prover: msg_sender(),
+      valid,
+    });
+    Ok(valid)
+  }
+
+  /// Number of inferences covered by a verified batch root; zero if unknown.
+  pub fn batch_size(&self, task_id: String, version: U64, batch_root: FixedBytes<32>) -> Result<U64, RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok(task.versions.getter(version).batch_roots.get(batch_root))
+  }
+
+  /// Whether the inference `(input_commitment, output_commitment)` belongs to a
+  /// verified batch, given its sorted-pair Merkle `proof` up to `batch_root`.
+  pub fn is_batch_member(
+    &self,
+    task_id: String,
+    version: U64,
+    batch_root: FixedBytes<32>,
+    input_commitment: FixedBytes<32>,
+    output_commitment: FixedBytes<32>,
+    proof: Vec<FixedBytes<32>>,
+  ) -> Result<bool, RegistryError> {
+    let task = self.task(&task_id)?;
+    if task.versions.getter(version).batch_roots.get(batch_root) == U64::ZERO {
+      return Ok(false);
+    }
+    let leaf = batch_leaf(input_commitment, output_commitment);
+    Ok(merkle_root(leaf, &proof) == batch_root)
+  }
+
+  pub fn get_challenge_period(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.challenge_period.get())
+  }
+
+  pub fn set_challenge_period(&mut self, task_id: String, challenge_period: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_owner()?;
+    self.task_mut(&task_id)?.challenge_period.set(challenge_period);
+
+    evm::log(ChallengePeriodChanged {
+      task_id,
+      challenge_period: challenge_period.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  pub fn challenge_bond(&self) -> U256 {
+    self.challenge_bond.get()
+  }
+
+  pub fn set_challenge_bond(&mut self, challenge_bond: U256) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.challenge_bond.set(challenge_bond);
+
+    evm::log(ChallengeBondChanged { challenge_bond });
+    Ok(())
+  }
+
+  pub fn arbiter(&self) -> Address {
+    self.arbiter.get()
+  }
+
+  pub fn set_arbiter(&mut self, arbiter: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    let previous_arbiter = self.arbiter.get();
+    self.arbiter.set(arbiter);
+
+    evm::log(ArbiterChanged {
+      previous_arbiter,
+      new_arbiter: arbiter,
+    });
+    Ok(())
+  }
+
+  /// Disputes a version within its challenge period by posting at least the
+  /// challenge bond. The version is held back from `get_latest` until resolved.
+  #[payable]
+  pub fn challenge_version(
+    &mut self,
+    task_id: String,
+    version: U64,
+    evidence_hash: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let required = self.challenge_bond.get();
+    let bond = msg::value();
+    if bond < required {
+      return Err(RegistryError::InsufficientFee(InsufficientFee { required, provided: bond }));
+    }
+
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let challenge_period = task.challenge_period.get();
+    let mut info = task.versions.setter(version);
+    let deadline = info.timestamp.get().saturating_add(challenge_period);
+    if challenge_period == U64::ZERO || info.yanked.get() || U64::from(block::timestamp()) >= deadline {
+      return Err(RegistryError::ChallengeWindowClosed(ChallengeWindowClosed {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    if info.challenge.status.get() == U8::from(CHALLENGE_OPEN) {
+      return Err(RegistryError::ChallengeAlreadyOpen(ChallengeAlreadyOpen {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+
+    let challenger = msg_sender();
+    let challenge = &mut info.challenge;
+    challenge.status.set(U8::from(CHALLENGE_OPEN));
+    challenge.challenger.set(challenger);
+    challenge.bond.set(bond);
+    challenge.evidence_hash.set(evidence_hash);
+    let publisher = info.publisher.get();
+    task.cache_latest();
+    let mut stake = self.stakes.setter(publisher);
+    let open_challenges = stake.open_challenges.get() + U64::from(1);
+    stake.open_challenges.set(open_challenges);
+
+    evm::log(VersionChallenged {
+      task_id,
+      version: version.to::<u64>(),
+      challenger,
+      evidence_hash,
+      bond,
+    });
+    Ok(())
+  }
+
+  /// Settles an open challenge; callable by the task admin or the arbiter.
+  /// Upholding yanks the version, slashes the publisher's stake and pays it to
+  /// the challenger along with their bond; rejecting slashes the bond into the
+  /// collected fees.
+  pub fn resolve_challenge(&mut self, task_id: String, version: U64, upheld: bool) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let resolver = msg_sender();
+    if resolver != self.arbiter.get() {
+      self.assert_task_admin(&task_id)?;
+    }
+
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let operation_nonce = task.operation_nonces.get(resolver);
+    let mut info = task.versions.setter(version);
+    if info.challenge.status.get() != U8::from(CHALLENGE_OPEN) {
+      return Err(RegistryError::NoOpenChallenge(NoOpenChallenge {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let challenger = info.challenge.challenger.get();
+    let publisher = info.publisher.get();
+    let auditors: Vec<Address> = (0..info.audits.len())
+      .filter_map(|i| info.audits.getter(i))
+      .map(|audit| audit.auditor.get())
+      .collect();
+    let bond = info.challenge.bond.get();
+    info.challenge.bond.set(U256::ZERO);
+
+    if upheld {
+      info.challenge.status.set(U8::from(CHALLENGE_UPHELD));
+      let newly_yanked = !info.yanked.get();
+      if newly_yanked {
+        let reason = String::from("challenge upheld");
+        info.yanked.set(true);
+        info.yank_reason.set_str(&reason);
+        evm::log(VersionYanked {
+          task_id: task_id.clone(),
+          version: version.to::<u64>(),
+          reason,
+          successor_version: 0,
+          operation_nonce: operation_nonce.to::<u64>(),
+        });
+      }
+      if newly_yanked {
+        self.log_action(&task_id, ACTION_YANK, version, Address::ZERO);
+        self.log_pinned_yank(&task_id, version);
+      }
+      // Consumers who paid for the version are made whole from the slashed
+      // stake first; the challenger gets the bond and whatever is left.
+      let slashed = self.slash_stake(publisher, &task_id, version);
+      let mut task = self.task_write(&task_id)?;
+      let mut info = task.versions.setter(version);
+      let refund_pool = slashed.min(info.total_payments.get());
+      let pooled = info.refund_pool.get() + refund_pool;
+      info.refund_pool.set(pooled);
+      self.bump_reputation(challenger, |r| &mut r.upheld_challenges);
+      self.bump_reputation(publisher, |r| &mut r.lost_disputes);
+      for auditor in auditors {
+        self.bump_reputation(auditor, |r| &mut r.overturned_attestations);
+      }
+      let amount = bond + slashed - refund_pool;
+      if call::transfer_eth(challenger, amount).is_err() {
+        return Err(RegistryError::TransferFailed(TransferFailed { to: challenger, amount }));
+      }
+    } else {
+      info.challenge.status.set(U8::from(CHALLENGE_REJECTED));
+      self.collected_fees.set(self.collected_fees.get() + bond);
+      self.bump_reputation(challenger, |r| &mut r.lost_disputes);
+      for auditor in auditors {
+        self.bump_reputation(auditor, |r| &mut r.upheld_attestations);
+      }
+    }
+    let mut stake = self.stakes.setter(publisher);
+    let open_challenges = stake.open_challenges.get().saturating_sub(U64::from(1));
+    stake.open_challenges.set(open_challenges);
+    self.tasks.setter(task_key(&task_id)).cache_latest();
+
+    evm::log(ChallengeResolved {
+      task_id,
+      version: version.to::<u64>(),
+      upheld,
+      resolver,
+    });
+    Ok(())
+  }
+
+  /// Returns `(paid, claimable)`: what `consumer` paid for inference against the
+  /// version, and the refund it can still claim after an upheld challenge.
+  pub fn get_refund(&self, task_id: String, version: U64, consumer: Address) -> Result<(U256, U256), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    let paid = info.consumer_payments.get(consumer);
+    if info.refunded.get(consumer) || paid.is_zero() {
+      return Ok((paid, U256::ZERO));
+    }
+    Ok((paid, paid * info.refund_pool.get() / info.total_payments.get()))
+  }
+
+  /// Pays out the caller's share of a version's refund pool, which is funded
+  /// when a challenge against the version is upheld. Claimable once.
+  pub fn claim_refund(&mut self, task_id: String, version: U64) -> Result<U256, RegistryError> {
+    // Not pausable, so consumers can recover refunds in the middle of an incident.
+    let consumer = msg_sender();
+    let (_, amount) = self.get_refund(task_id.clone(), version, consumer)?;
+    if amount.is_zero() {
+      return Err(RegistryError::NoRefundDue(NoRefundDue {
+        task_id,
+        version: version.to::<u64>(),
+        account: consumer,
+      }));
+    }
+    self.task_mut(&task_id)?.versions.setter(version).refunded.insert(consumer, true);
+    if call::transfer_eth(consumer, amount).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to: consumer, amount }));
+    }
+
+    evm::log(RefundClaimed {
+      task_id,
+      version: version.to::<u64>(),
+      consumer,
+      amount,
+    });
+    Ok(amount)
+  }
+
+  /// Returns `(status, challenger, bond, evidence_hash, deadline)` for a version's
+  /// latest challenge, where `deadline` closes its challenge period.
+  pub fn get_challenge(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<(u8, Address, U256, FixedBytes<32>, U64), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    let challenge = &info.challenge;
+    Ok((
+      challenge.status.get().to::<u8>(),
+      challenge.challenger.get(),
+      challenge.bond.get(),
+      challenge.evidence_hash.get(),
+      info.timestamp.get().saturating_add(task.challenge_period.get()),
+    ))
+  }
+
+  /// Returns `(min_stake, unstake_cooldown)`.
+  pub fn staking(&self) -> (U256, U64) {
+    (self.min_stake.get(), self.unstake_cooldown.get())
+  }
+
+  pub fn set_staking(&mut self, min_stake: U256, unstake_cooldown: U64) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.min_stake.set(min_stake);
+    self.unstake_cooldown.set(unstake_cooldown);
+
+    evm::log(StakingUpdated {
+      min_stake,
+      unstake_cooldown: unstake_cooldown.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Returns `(amount, unlock_at)` for `account`'s stake.
+  pub fn get_stake(&self, account: Address) -> (U256, U64) {
+    let stake = self.stakes.getter(account);
+    (stake.amount.get(), stake.unlock_at.get())
+  }
+
+  /// Adds `msg::value()` to the caller's stake. Staking during a pending
+  /// unstake cancels it.
+  #[payable]
+  pub fn stake(&mut self) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let account = msg_sender();
+    let amount = msg::value();
+    let mut stake = self.stakes.setter(account);
+    let total = stake.amount.get() + amount;
+    stake.amount.set(total);
+    stake.unlock_at.set(U64::ZERO);
+
+    evm::log(Staked { account, amount, total });
+    Ok(())
+  }
+
+  /// Starts the cooldown after which the caller can withdraw their stake.
+  pub fn request_unstake(&mut self) -> Result<(), RegistryError> {
+    // Not pausable, like `unstake`.
+    let account = msg_sender();
+    let unlock_at = U64::from(block::timestamp()).saturating_add(self.unstake_cooldown.get());
+    let mut stake = self.stakes.setter(account);
+    if stake.amount.get().is_zero() {
+      return Err(RegistryError::InsufficientStake(InsufficientStake {
+        account,
+        required: U256::from(1),
+        staked: U256::ZERO,
+      }));
+    }
+    stake.unlock_at.set(unlock_at);
+
+    evm::log(UnstakeRequested {
+      account,
+      unlock_at: unlock_at.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Withdraws the caller's whole remaining stake once the cooldown has passed
+  /// and no challenge against the caller's versions is still open.
+  pub fn unstake(&mut self) -> Result<(), RegistryError> {
+    // Not pausable: a pause must never trap a publisher's stake.
+    let account = msg_sender();
+    let mut stake = self.stakes.setter(account);
+    let unlock_at = stake.unlock_at.get();
+    if unlock_at == U64::ZERO || U64::from(block::timestamp()) < unlock_at {
+      return Err(RegistryError::UnstakeNotReady(UnstakeNotReady {
+        unlock_at: unlock_at.to::<u64>(),
+      }));
+    }
+    let open_challenges = stake.open_challenges.get();
+    if open_challenges != U64::ZERO {
+      return Err(RegistryError::StakeUnderChallenge(StakeUnderChallenge {
+        account,
+        open_challenges: open_challenges.to::<u64>(),
+      }));
+    }
+    let amount = stake.amount.get();
+    stake.amount.set(U256::ZERO);
+    stake.unlock_at.set(U64::ZERO);
+    if call::transfer_eth(account, amount).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to: account, amount }));
+    }
+
+    evm::log(Unstaked { account, amount });
+    Ok(())
+  }
+
+  pub fn is_auditor(&self, account: Address) -> bool {
+    self.auditors.get(account)
+  }
+
+  pub fn grant_auditor(&mut self, auditor: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.auditors.insert(auditor, true);
+
+    evm::log(AuditorGranted { auditor });
+    Ok(())
+  }
+
+  pub fn revoke_auditor(&mut self, auditor: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.auditors.delete(auditor);
+
+    evm::log(AuditorRevoked { auditor });
+    Ok(())
+  }
+
+  /// Records a registered auditor's report on a version. Reports are
+  /// append-only; revoking an auditor keeps their past reports.
+  pub fn attest(
+    &mut self,
+    task_id: String,
+    version: U64,
+    attestation_type: String,
+    report_hash: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let auditor = msg_sender();
+    if !self.auditors.get(auditor) {
+      return Err(RegistryError::NotAuditor(NotAuditor { account: auditor }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    let mut audit = info.audits.grow();
+    audit.auditor.set(auditor);
+    audit.attestation_type.set_str(&attestation_type);
+    audit.report_hash.set(report_hash);
+    audit.timestamp.set(U64::from(block::timestamp()));
+
+    evm::log(VersionAudited {
+      task_id,
+      version: version.to::<u64>(),
+      auditor,
+      attestation_type,
+      report_hash,
+    });
+    Ok(())
+  }
+
+  /// Returns every audit of a version, oldest first.
+  pub fn get_audits(&self, task_id: String, version: U64) -> Result<Vec<AuditRecord>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+
+    Ok(
+      (0..info.audits.len())
+        .filter_map(|i| info.audits.getter(i))
+        .map(|a| {
+          (
+            a.auditor.get(),
+            a.attestation_type.get_string(),
+            a.report_hash.get(),
+            a.timestamp.get(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  /// Returns `(publishes, upheld_challenges, lost_disputes, upheld_attestations,
+  /// overturned_attestations)` for `account`. Each audit of a challenged version
+  /// counts once per resolved challenge.
+  pub fn reputation_of(&self, account: Address) -> (U64, U64, U64, U64, U64) {
+    let reputation = self.reputations.getter(account);
+    (
+      reputation.publishes.get(),
+      reputation.upheld_challenges.get(),
+      reputation.lost_disputes.get(),
+      reputation.upheld_attestations.get(),
+      reputation.overturned_attestations.get(),
+    )
+  }
+
+  /// Sets the caller's identity profile, replacing any earlier one.
+  pub fn set_profile(
+    &mut self,
+    display_name_hash: FixedBytes<32>,
+    did: String,
+    website_hash: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let account = msg_sender();
+    let mut profile = self.profiles.setter(account);
+    profile.display_name_hash.set(display_name_hash);
+    profile.did.set_str(&did);
+    profile.website_hash.set(website_hash);
+    profile.updated_at.set(U64::from(block::timestamp()));
+
+    evm::log(ProfileUpdated {
+      account,
+      display_name_hash,
+      did,
+      website_hash,
+    });
+    Ok(())
+  }
+
+  pub fn clear_profile(&mut self) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let account = msg_sender();
+    let mut profile = self.profiles.setter(account);
+    profile.display_name_hash.set(FixedBytes::ZERO);
+    profile.did.set_str("");
+    profile.website_hash.set(FixedBytes::ZERO);
+    profile.updated_at.set(U64::ZERO);
+
+    evm::log(ProfileCleared { account });
+    Ok(())
+  }
+
+  /// Returns `(display_name_hash, did, website_hash, updated_at)`; all zero
+  /// for accounts without a profile.
+  pub fn get_profile(&self, account: Address) -> (FixedBytes<32>, String, FixedBytes<32>, U64) {
+    let profile = self.profiles.getter(account);
+    (
+      profile.display_name_hash.get(),
+      profile.did.get_string(),
+      profile.website_hash.get(),
+      profile.updated_at.get(),
+    )
+  }
+
+  /// Resolves who published a version: `(publisher, display_name_hash, did, website_hash)`.
+  pub fn get_publisher_profile(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<(Address, FixedBytes<32>, String, FixedBytes<32>), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let publisher = task.versions.getter(version).publisher.get();
+    let (display_name_hash, did, website_hash, _) = self.get_profile(publisher);
+    Ok((publisher, display_name_hash, did, website_hash))
+  }
+
+  /// Returns the ancestry of a version as `(task_id, version)` pairs, from its
+  /// direct parent up to the root model. Empty for root models.
+  pub fn get_lineage(&self, task_id: String, version: U64) -> Result<Vec<(String, U64)>, RegistryError> {
+    if !self.task(&task_id)?.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+
+    let mut lineage = Vec::new();
+    let (mut task_id, mut version) = (self.canonical_id(&task_id), version);
+    loop {
+      let task = self.tasks.getter(task_key(&task_id));
+      let info = task.versions.getter(version);
+      let parent_version = info.parent_version.get();
+      if parent_version == U64::ZERO {
+        return Ok(lineage);
+      }
+      task_id = info.parent_task_id.get_string();
+      version = parent_version;
+      lineage.push((task_id.clone(), version));
+    }
+  }
+
+  /// Appends a revision to a dataset's hash history and returns its number.
+  /// The first commitment to an id claims it for the caller.
+  pub fn commit_dataset(&mut self, dataset_id: String, hash: FixedBytes<32>) -> Result<U64, RegistryError> {
+    self.assert_not_paused()?;
+    let committer = msg_sender();
+    let mut dataset = self.datasets.setter(dataset_id.clone());
+    if dataset.hashes.is_empty() {
+      dataset.owner.set(committer);
+    } else if dataset.owner.get() != committer {
+      return Err(RegistryError::NotDatasetOwner(NotDatasetOwner {
+        dataset_id,
+        account: committer,
+      }));
+    }
+    dataset.hashes.push(hash);
+    let revision = U64::from(dataset.hashes.len());
+
+    evm::log(DatasetCommitted {
+      dataset_id,
+      revision: revision.to::<u64>(),
+      hash,
+      committer,
+    });
+    Ok(revision)
+  }
+
+  /// Returns `(owner, revision_count)` for a dataset.
+  pub fn get_dataset_info(&self, dataset_id: String) -> (Address, U64) {
+    let dataset = self.datasets.getter(dataset_id);
+    (dataset.owner.get(), U64::from(dataset.hashes.len()))
+  }
+
+  pub fn get_dataset_hash(&self, dataset_id: String, revision: U64) -> Result<FixedBytes<32>, RegistryError> {
+    self.dataset_hash(&dataset_id, revision)
+  }
+
+  /// Returns `(dataset_id, revision, hash)` for each dataset the version was
+  /// trained on.
+  pub fn get_training_inputs(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<Vec<(String, U64, FixedBytes<32>)>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+
+    (0..info.training_inputs.len())
+      .filter_map(|i| info.training_inputs.getter(i))
+      .map(|input| {
+        let dataset_id = input.dataset_id.get_string();
+        let revision = input.revision.get();
+        let hash = self.dataset_hash(&dataset_id, revision)?;
+        Ok((dataset_id, revision, hash))
+      })
+      .collect()
+  }
+
+  pub fn is_evaluator(&self, task_id: String, account: Address) -> Result<bool, RegistryError> {
+    Ok(self.task(&task_id)?.evaluators.get(account))
+  }
+
+  pub fn grant_evaluator(&mut self, task_id: String, evaluator: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.evaluators.get(evaluator) {
+      task.evaluators.insert(evaluator, true);
+      self.log_action(&task_id, ACTION_EVALUATOR_GRANT, U64::ZERO, evaluator);
+    }
+
+    evm::log(EvaluatorGranted { task_id, evaluator });
+    Ok(())
+  }
+
+  pub fn revoke_evaluator(&mut self, task_id: String, evaluator: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if task.evaluators.get(evaluator) {
+      task.evaluators.delete(evaluator);
+      self.log_action(&task_id, ACTION_EVALUATOR_REVOKE, U64::ZERO, evaluator);
+    }
+
+    evm::log(EvaluatorRevoked { task_id, evaluator });
+    Ok(())
+  }
+
+  /// Stores benchmark scores for a version as 18-decimal fixed-point values.
+  /// Callable by the version's publisher or one of the task's evaluators.
+  pub fn record_metrics(
+    &mut self,
+    task_id: String,
+    version: U64,
+    metric_keys: Vec<String>,
+    metric_values: Vec<U256>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    if metric_keys.len() != metric_values.len() {
+      return Err(RegistryError::MetricsLengthMismatch(MetricsLengthMismatch {
+        keys: U256::from(metric_keys.len()),
+        values: U256::from(metric_values.len()),
+      }));
+    }
+    let recorder = msg_sender();
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let is_evaluator = task.evaluators.get(recorder);
+    let mut info = task.versions.setter(version);
+    if recorder != info.publisher.get() && !is_evaluator {
+      return Err(RegistryError::NotEvaluator(NotEvaluator {
+        task_id,
+        account: recorder,
+      }));
+    }
+
+    for (key, value) in metric_keys.into_iter().zip(metric_values) {
+      let mut metric = info.metrics.setter(key.clone());
+      let first = !metric.recorded.get();
+      metric.recorded.set(true);
+      metric.value.set(value);
+      if first {
+        info.metric_keys.grow().set_str(&key);
+      }
+
+      evm::log(MetricRecorded {
+        task_id: task_id.clone(),
+        version: version.to::<u64>(),
+        key,
+        value,
+        recorder,
+      });
+    }
+    Ok(())
+  }
+
+  pub fn get_metric(&self, task_id: String, version: U64, key: String) -> Result<U256, RegistryError> {
+    let task = self.task(&task_id)?;
+    let info = task.versions.getter(version);
+    let metric = info.metrics.getter(key.clone());
+    if !metric.recorded.get() {
+      return Err(RegistryError::MetricNotFound(MetricNotFound {
+        task_id,
+        version: version.to::<u64>(),
+        key,
+      }));
+    }
+    Ok(metric.value.get())
+  }
+
+  /// Returns every `(key, value)` metric recorded for a version.
+  pub fn get_metrics(&self, task_id: String, version: U64) -> Result<Vec<(String, U256)>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+
+    Ok(
+      (0..info.metric_keys.len())
+        .filter_map(|i| info.metric_keys.getter(i))
+        .map(|key| {
+          let key = key.get_string();
+          let value = info.metrics.getter(key.clone()).value.get();
+          (key, value)
+        })
+        .collect(),
+    )
+  }
+
+  /// Sets or replaces a version's model card; callable by the task admin or the
+  /// version's publisher.
+  pub fn set_model_card(
+    &mut self,
+    task_id: String,
+    version: U64,
+    framework: u8,
+    parameter_count: U64,
+    io_schema_hash: FixedBytes<32>,
+    license_code: u16,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    if framework == FRAMEWORK_UNSET || framework > FRAMEWORK_TFLITE {
+      return Err(RegistryError::UnsupportedFramework(UnsupportedFramework { framework }));
+    }
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    if msg_sender() != task.versions.getter(version).publisher.get() {
+      self.assert_task_admin(&task_id)?;
+    }
+
+    let mut task = self.task_mut(&task_id)?;
+    let mut info = task.versions.setter(version);
+    let card = &mut info.model_card;
+    card.framework.set(U8::from(framework));
+    card.parameter_count.set(parameter_count);
+    card.io_schema_hash.set(io_schema_hash);
+    card.license_code.set(U16::from(license_code));
+
+    evm::log(ModelCardSet {
+      task_id,
+      version: version.to::<u64>(),
+      framework,
+      parameter_count: parameter_count.to::<u64>(),
+      io_schema_hash,
+      license_code,
+    });
+    Ok(())
+  }
+
+  /// Returns a version's `ModelCardRecord`; `framework` is `FRAMEWORK_UNSET`
+  /// if none has been set.
+  pub fn get_model_card(&self, task_id: String, version: U64) -> Result<ModelCardRecord, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    let card = &info.model_card;
+    Ok((
+      card.framework.get().to::<u8>(),
+      card.parameter_count.get(),
+      card.io_schema_hash.get(),
+      card.license_code.get().to::<u16>(),
+    ))
+  }
+
+  /// The task's admin, viewed as the holder of the task's token.
+  pub fn owner_of_task(&self, task_id: String) -> Result<Address, RegistryError> {
+    Ok(self.task(&task_id)?.admin.get())
+  }
+
+  /// Lets `approved` transfer the task once, like ERC-721 `approve`. Callable
+  /// by the task's owner or one of its operators; the zero address clears it.
+  pub fn approve_task(&mut self, task_id: String, approved: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    let sender = msg_sender();
+    let owner = self.task(&task_id)?.admin.get();
+    if sender != owner && !self.operators.getter(owner).get(sender) {
+      return Err(RegistryError::NotTaskApproved(NotTaskApproved {
+        task_id,
+        account: sender,
+      }));
+    }
+    self.task_mut(&task_id)?.approved.set(approved);
+
+    evm::log(TaskApproval {
+      task_id,
+      owner,
+      approved,
+    });
+    Ok(())
+  }
+
+  pub fn get_approved_task(&self, task_id: String) -> Result<Address, RegistryError> {
+    Ok(self.task(&task_id)?.approved.get())
+  }
+
+  /// Lets `operator` transfer and approve all of the caller's tasks.
+  pub fn set_approval_for_all(&mut self, operator: Address, approved: bool) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let owner = msg_sender();
+    self.operators.setter(owner).insert(operator, approved);
+
+    evm::log(ApprovalForAll {
+      owner,
+      operator,
+      approved,
+    });
+    Ok(())
+  }
+
+  pub fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+    self.operators.getter(owner).get(operator)
+  }
+
+  /// Hands the task, and with it the admin role, to `new_owner`. Callable by
+  /// the owner, the approved account or an operator, as with ERC-721
+  /// `transferFrom`. Publisher grants stay with the task.
+  pub fn transfer_task(&mut self, task_id: String, new_owner: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    if new_owner == Address::ZERO {
+      return Err(RegistryError::InvalidRecipient(InvalidRecipient { to: new_owner }));
+    }
+    let sender = msg_sender();
+    let task = self.task(&task_id)?;
+    let owner = task.admin.get();
+    if sender != owner && sender != task.approved.get() && !self.operators.getter(owner).get(sender) {
+      return Err(RegistryError::NotTaskApproved(NotTaskApproved {
+        task_id,
+        account: sender,
+      }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    task.admin.set(new_owner);
+    task.approved.set(Address::ZERO);
+    self.log_action(&task_id, ACTION_ADMIN_CHANGE, U64::ZERO, new_owner);
+
+    evm::log(TaskAdminChanged {
+      task_id: task_id.clone(),
+      previous_admin: owner,
+      new_admin: new_owner,
+    });
+    evm::log(TaskTransferred {
+      task_id,
+      from: owner,
+      to: new_owner,
+    });
+    Ok(())
+  }
+
+  /// Returns `(payment_token, registration_fee, publish_fee)` for token payments.
+  pub fn token_fees(&self) -> (Address, U256, U256) {
+    (
+      self.payment_token.get(),
+      self.token_registration_fee.get(),
+      self.token_publish_fee.get(),
+    )
+  }
+
+  /// Accepts `token` for fees and subscriptions at the given prices, in the
+  /// token's own units. The zero address turns token payments off.
+  pub fn set_payment_token(
+    &mut self,
+    token: Address,
+    registration_fee: U256,
+    publish_fee: U256,
+  ) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.payment_token.set(token);
+    self.token_registration_fee.set(registration_fee);
+    self.token_publish_fee.set(publish_fee);
+
+    evm::log(PaymentTokenUpdated {
+      token,
+      registration_fee,
+      publish_fee,
+    });
+    Ok(())
+  }
+
+  pub fn collected_token_fees(&self, token: Address) -> U256 {
+    self.token_fees.get(token)
+  }
+
+  pub fn withdraw_token_fees(&mut self, token: Address, to: Address) -> Result<(), RegistryError> {
+    // Not pausable, like `withdraw_fees`.
+    self.assert_owner()?;
+    let amount = self.token_fees.get(token);
+    self.token_fees.insert(token, U256::ZERO);
+    self.push_token(token, to, amount)?;
+
+    evm::log(TokenFeesWithdrawn { token, to, amount });
+    Ok(())
+  }
+
+  pub fn get_token_subscription_price(&self, task_id: String) -> Result<U256, RegistryError> {
+    Ok(self.task(&task_id)?.token_subscription_price.get())
+  }
+
+  pub fn set_token_subscription_price(&mut self, task_id: String, price_per_second: U256) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.task_mut(&task_id)?.token_subscription_price.set(price_per_second);
+
+    evm::log(TokenSubscriptionPriceUpdated {
+      task_id,
+      price_per_second,
+    });
+    Ok(())
+  }
+
+  pub fn token_subscription_revenue(&self, task_id: String, token: Address) -> Result<U256, RegistryError> {
+    Ok(self.task(&task_id)?.token_subscription_revenue.get(token))
+  }
+
+  pub fn withdraw_token_subscription_revenue(
+    &mut self,
+    task_id: String,
+    token: Address,
+    to: Address,
+  ) -> Result<(), RegistryError> {
+    // Not pausable, like `withdraw_subscription_revenue`.
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let amount = task.token_subscription_revenue.get(token);
+    task.token_subscription_revenue.insert(token, U256::ZERO);
+    self.push_token(token, to, amount)?;
+
+    evm::log(TokenSubscriptionRevenueWithdrawn {
+      task_id,
+      token,
+      to,
+      amount,
+    });
+    Ok(())
+  }
+
+  /// Returns `(parent_task_id, royalty_bps)`; the parent is empty when no
+  /// royalty is configured.
+  pub fn get_royalty(&self, task_id: String) -> Result<(String, u16), RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok((task.royalty_parent.get_string(), task.royalty_bps.get().to::<u16>()))
+  }
+
+  /// Returns the pending `(parent_task_id, royalty_bps)` offer; the parent is
+  /// empty when there is none.
+  pub fn get_royalty_offer(&self, task_id: String) -> Result<(String, u16), RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok((task.royalty_offer_parent.get_string(), task.royalty_offer_bps.get().to::<u16>()))
+  }
+
+  pub fn max_royalty_bps(&self) -> u16 {
+    self.max_royalty_bps.get().to::<u16>()
+  }
+
+  pub fn set_max_royalty_bps(&mut self, max_royalty_bps: u16) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    if max_royalty_bps > BPS {
+      return Err(RegistryError::InvalidMaxRoyalty(InvalidMaxRoyalty { max_royalty_bps }));
+    }
+    self.max_royalty_bps.set(U16::from(max_royalty_bps));
+
+    evm::log(MaxRoyaltyChanged { max_royalty_bps });
+    Ok(())
+  }
+
+  /// Offers `royalty_bps` of every subscription payment for `task_id` to the
+  /// task `version` was fine-tuned from, as recorded in its lineage. Task
+  /// admin only; the share takes effect once the parent's admin accepts it
+  /// with `accept_royalty`, and may not exceed `max_royalty_bps`. A zero share
+  /// drops both the active royalty and any pending offer at once. A royalty
+  /// already owed to another parent must be dropped first.
+  pub fn set_royalty(&mut self, task_id: String, version: U64, royalty_bps: u16) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    self.assert_task_admin(&task_id)?;
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    if royalty_bps == 0 {
+      let mut task = self.task_mut(&task_id)?;
+      task.royalty_parent.set_str("");
+      task.royalty_bps.set(U16::ZERO);
+      task.royalty_offer_parent.set_str("");
+      task.royalty_offer_bps.set(U16::ZERO);
+
+      evm::log(RoyaltyUpdated {
+        task_id,
+        parent_task_id: String::new(),
+        royalty_bps,
+      });
+      return Ok(());
+    }
+    let parent_task_id = task.versions.getter(version).parent_task_id.get_string();
+    let current_parent = task.royalty_parent.get_string();
+    if parent_task_id.is_empty()
+      || parent_task_id == self.canonical_id(&task_id)
+      || royalty_bps > self.max_royalty_bps()
+      || (!current_parent.is_empty() && current_parent != parent_task_id)
+    {
+      return Err(RegistryError::InvalidRoyalty(InvalidRoyalty {
+        task_id,
+        parent_task_id,
+        royalty_bps,
+      }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    task.royalty_offer_parent.set_str(&parent_task_id);
+    task.royalty_offer_bps.set(U16::from(royalty_bps));
+
+    evm::log(RoyaltyOffered {
+      task_id,
+      parent_task_id,
+      royalty_bps,
+    });
+    Ok(())
+  }
+
+  /// Activates the royalty `task_id` offered, callable by the admin of the
+  /// offered parent. `royalty_bps` must match the offer, so a changed offer
+  /// cannot be accepted by accident. The parent's admin withdraws the share.
+  pub fn accept_royalty(&mut self, task_id: String, royalty_bps: u16) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    let task = self.task(&task_id)?;
+    let parent_task_id = task.royalty_offer_parent.get_string();
+    let offered = task.royalty_offer_bps.get().to::<u16>();
+    if parent_task_id.is_empty() || offered != royalty_bps || royalty_bps > self.max_royalty_bps() {
+      return Err(RegistryError::InvalidRoyalty(InvalidRoyalty {
+        task_id,
+        parent_task_id,
+        royalty_bps,
+      }));
+    }
+    self.assert_task_admin(&parent_task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    task.royalty_parent.set_str(&parent_task_id);
+    task.royalty_bps.set(U16::from(royalty_bps));
+    task.royalty_offer_parent.set_str("");
+    task.royalty_offer_bps.set(U16::ZERO);
+
+    evm::log(RoyaltyUpdated {
+      task_id,
+      parent_task_id,
+      royalty_bps,
+    });
+    Ok(())
+  }
+
+  pub fn job_timeout(&self) -> U64 {
+    self.job_timeout.get()
+  }
+
+  pub fn set_job_timeout(&mut self, job_timeout: U64) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.job_timeout.set(job_timeout);
+
+    evm::log(JobTimeoutChanged {
+      job_timeout: job_timeout.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Minimum value `request_inference` must attach for a version.
+  pub fn get_inference_price(&self, task_id: String, version: U64) -> Result<U256, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).inference_price.get())
+  }
+
+  /// Sets the minimum escrow for inference jobs against a version. Jobs
+  /// already posted keep the payment they were opened with.
+  pub fn set_inference_price(&mut self, task_id: String, version: U64, price: U256) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    task.versions.setter(version).inference_price.set(price);
+
+    evm::log(InferencePriceSet {
+      task_id,
+      version: version.to::<u64>(),
+      price,
+    });
+    Ok(())
+  }
+
+  /// Posts an inference job for a version with the attached value in escrow
+  /// and returns its id. The value must cover the version's inference price.
+  /// Any prover may fulfil the job until `job_timeout` elapses.
+  #[payable]
+  pub fn request_inference(
+    &mut self,
+    task_id: String,
+    version: U64,
+    input_commitment: FixedBytes<32>,
+  ) -> Result<U64, RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let requester = msg_sender();
+    let payment = msg::value();
+    // Jobs always escrow something, so refunding one is never free to trigger.
+    let price = task.versions.getter(version).inference_price.get().max(U256::from(1));
+    if payment < price {
+      return Err(RegistryError::InsufficientFee(InsufficientFee {
+        required: price,
+        provided: payment,
+      }));
+    }
+    let deadline = U64::from(block::timestamp()).saturating_add(self.job_timeout.get());
+    let job_id = U64::from(self.jobs.len());
+
+    let mut job = self.jobs.grow();
+    job.task_id.set_str(&task_id);
+    job.version.set(version);
+    job.requester.set(requester);
+    job.input_commitment.set(input_commitment);
+    job.payment.set(payment);
+    job.deadline.set(deadline);
+
+    evm::log(InferenceRequested {
+      job_id: job_id.to::<u64>(),
+      task_id,
+      version: version.to::<u64>(),
+      requester,
+      input_commitment,
+      payment,
+      deadline: deadline.to::<u64>(),
+    });
+    Ok(job_id)
+  }
+
+  /// Fulfils an open job with a Groth16 proof, checked as by `verify_inference`;
+  /// the first three public inputs must be the job's input commitment,
+  /// `output_commitment` and the caller's address, so a proof copied from the
+  /// mempool is useless to anyone else. A valid proof releases the escrow to
+  /// the caller; an invalid one returns `false` and leaves the job open.
+  pub fn fulfill_inference(
+    &mut self,
+    job_id: U64,
+    output_commitment: FixedBytes<32>,
+    proof: Bytes,
+    public_inputs: Vec<U256>,
+  ) -> Result<bool, RegistryError> {
+    let job = index(job_id).and_then(|i| self.jobs.getter(i)).ok_or_else(|| job_not_found(job_id))?;
+    if job.status.get() != U8::from(JOB_OPEN) {
+      return Err(RegistryError::JobNotOpen(JobNotOpen {
+        job_id: job_id.to::<u64>(),
+      }));
+    }
+    let deadline = job.deadline.get();
+    if U64::from(block::timestamp()) > deadline {
+      return Err(RegistryError::JobExpired(JobExpired {
+        job_id: job_id.to::<u64>(),
+        deadline: deadline.to::<u64>(),
+      }));
+    }
+    let prover = msg_sender();
+    let commitment = |i: usize| public_inputs.get(i).map(|x| B256::from(*x));
+    if commitment(0) != Some(job.input_commitment.get())
+      || commitment(1) != Some(output_commitment)
+      || public_inputs.get(2) != Some(&U256::from_be_slice(prover.as_slice()))
+    {
+      return Err(RegistryError::InvalidJobFulfillment(InvalidJobFulfillment {
+        job_id: job_id.to::<u64>(),
+      }));
+    }
+    let task_id = job.task_id.get_string();
+    let version = job.version.get();
+
+    if !self.verify_inference(task_id.clone(), version, proof, public_inputs)? {
+      return Ok(false);
+    }
+
+    let mut job = index(job_id).and_then(|i| self.jobs.setter(i)).ok_or_else(|| job_not_found(job_id))?;
+    let amount = job.payment.get();
+    let requester = job.requester.get();
+    job.status.set(U8::from(JOB_FULFILLED));
+    job.prover.set(prover);
+    job.output_commitment.set(output_commitment);
+    // `verify_inference` already counted the call.
+    let mut task = self.task_write(&task_id)?;
+    let mut info = task.versions.setter(version);
+    // Payments after an upheld dispute would dilute the refund shares.
+    if info.challenge.status.get() != U8::from(CHALLENGE_UPHELD) {
+      let paid = info.consumer_payments.get(requester) + amount;
+      info.consumer_payments.insert(requester, paid);
+      let total_payments = info.total_payments.get() + amount;
+      info.total_payments.set(total_payments);
+    }
+    if call::transfer_eth(prover, amount).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to: prover, amount }));
+    }
+
+    evm::log(InferenceFulfilled {
+      job_id: job_id.to::<u64>(),
+      prover,
+      output_commitment,
+    });
+    Ok(true)
+  }
+
+  /// Returns an expired, unfulfilled job's escrow to its requester. Anyone may
+  /// trigger it.
+  pub fn refund_inference(&mut self, job_id: U64) -> Result<(), RegistryError> {
+    let mut job = index(job_id).and_then(|i| self.jobs.setter(i)).ok_or_else(|| job_not_found(job_id))?;
+    if job.status.get() != U8::from(JOB_OPEN) {
+      return Err(RegistryError::JobNotOpen(JobNotOpen {
+        job_id: job_id.to::<u64>(),
+      }));
+    }
+    let deadline = job.deadline.get();
+    if U64::from(block::timestamp()) <= deadline {
+      return Err(RegistryError::JobNotExpired(JobNotExpired {
+        job_id: job_id.to::<u64>(),
+        deadline: deadline.to::<u64>(),
+      }));
+    }
+    let requester = job.requester.get();
+    let amount = job.payment.get();
+    job.status.set(U8::from(JOB_REFUNDED));
+    if call::transfer_eth(requester, amount).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to: requester, amount }));
+    }
+
+    evm::log(InferenceRefunded {
+      job_id: job_id.to::<u64>(),
+      requester,
+      amount,
+    });
+    Ok(())
+  }
+
+  pub fn job_count(&self) -> U64 {
+    U64::from(self.jobs.len())
+  }
+
+  pub fn get_job(&self, job_id: U64) -> Result<JobRecord, RegistryError> {
+    let job = index(job_id).and_then(|i| self.jobs.getter(i)).ok_or_else(|| job_not_found(job_id))?;
+    Ok((
+      job.task_id.get_string(),
+      job.version.get(),
+      job.requester.get(),
+      job.input_commitment.get(),
+      job.payment.get(),
+      job.deadline.get(),
+      job.status.get().to::<u8>(),
+      job.prover.get(),
+      job.output_commitment.get(),
+    ))
+  }
+
+  pub fn is_oracle(&self, account: Address) -> bool {
+    self.oracles.get(account)
+  }
+
+  pub fn grant_oracle(&mut self, oracle: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.oracles.insert(oracle, true);
+
+    evm::log(OracleGranted { oracle });
+    Ok(())
+  }
+
+  pub fn revoke_oracle(&mut self, oracle: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.oracles.delete(oracle);
+
+    evm::log(OracleRevoked { oracle });
+    Ok(())
+  }
+
+  /// Queues an off-chain evaluation of a version with the suite identified by
+  /// `suite_hash` and returns the request id. Callable by the task admin.
+  pub fn request_evaluation(
+    &mut self,
+    task_id: String,
+    version: U64,
+    suite_hash: FixedBytes<32>,
+  ) -> Result<U64, RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    self.assert_task_admin(&task_id)?;
+    if !self.task(&task_id)?.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let requester = msg_sender();
+    let request_id = U64::from(self.evaluations.len());
+
+    let mut request = self.evaluations.grow();
+    request.task_id.set_str(&task_id);
+    request.version.set(version);
+    request.requester.set(requester);
+    request.suite_hash.set(suite_hash);
+
+    evm::log(EvaluationRequested {
+      request_id: request_id.to::<u64>(),
+      task_id,
+      version: version.to::<u64>(),
+      requester,
+      suite_hash,
+    });
+    Ok(request_id)
+  }
+
+  /// Oracle callback answering a pending evaluation request. `task_id` and
+  /// `version` must match the request, so a result cannot land on the wrong model.
+  pub fn fulfill_evaluation(
+    &mut self,
+    task_id: String,
+    version: U64,
+    request_id: U64,
+    results_hash: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let oracle = msg_sender();
+    if !self.oracles.get(oracle) {
+      return Err(RegistryError::NotOracle(NotOracle { account: oracle }));
+    }
+    let mut request = index(request_id)
+      .and_then(|i| self.evaluations.setter(i))
+      .ok_or_else(|| evaluation_not_found(request_id))?;
+    if request.task_id.get_string() != task_id || request.version.get() != version {
+      return Err(evaluation_not_found(request_id));
+    }
+    if request.fulfilled.get() {
+      return Err(RegistryError::EvaluationAlreadyFulfilled(EvaluationAlreadyFulfilled {
+        request_id: request_id.to::<u64>(),
+      }));
+    }
+    request.fulfilled.set(true);
+    request.oracle.set(oracle);
+    request.results_hash.set(results_hash);
+    request.fulfilled_at.set(U64::from(block::timestamp()));
+
+    evm::log(EvaluationFulfilled {
+      request_id: request_id.to::<u64>(),
+      task_id,
+      version: version.to::<u64>(),
+      oracle,
+      results_hash,
+    });
+    Ok(())
+  }
+
+  pub fn evaluation_count(&self) -> U64 {
+    U64::from(self.evaluations.len())
+  }
+
+  pub fn get_evaluation(&self, request_id: U64) -> Result<EvaluationRecord, RegistryError> {
+    let request = index(request_id)
+      .and_then(|i| self.evaluations.getter(i))
+      .ok_or_else(|| evaluation_not_found(request_id))?;
+    Ok((
+      request.task_id.get_string(),
+      request.version.get(),
+      request.requester.get(),
+      request.suite_hash.get(),
+      request.fulfilled.get(),
+      request.oracle.get(),
+      request.results_hash.get(),
+      request.fulfilled_at.get(),
+    ))
+  }
+
+  /// Running commitment over every version published so far.
+  pub fn version_root(&self) -> FixedBytes<32> {
+    self.version_root.get()
+  }
+
+  pub fn l1_checkpoint_target(&self) -> Address {
+    self.l1_checkpoint_target.get()
+  }
+
+  pub fn set_l1_checkpoint_target(&mut self, target: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    let previous_target = self.l1_checkpoint_target.get();
+    self.l1_checkpoint_target.set(target);
+
+    evm::log(L1CheckpointTargetChanged {
+      previous_target,
+      new_target: target,
+    });
+    Ok(())
+  }
+
+  /// Records a global checkpoint and commits
+  /// `keccak(task_count, version_root, state_root)` to L1 through ArbSys. The
+  /// message carries `abi.encode(digest, task_count, version_root, state_root)`
+  /// to the configured target and can be executed on L1 once its batch is confirmed.
+  pub fn checkpoint_to_l1(&mut self) -> Result<FixedBytes<32>, RegistryError> {
+    self.assert_owner()?;
+    let target = self.l1_checkpoint_target.get();
+    if target == Address::ZERO {
+      return Err(RegistryError::NoCheckpointTarget(NoCheckpointTarget {}));
+    }
+    let task_count = self.task_index.len() as u64;
+    let version_root = self.version_root.get();
+    let (_, state_root) = self.append_checkpoint();
+    let digest = crypto::keccak((task_count, version_root, state_root).abi_encode());
+    let data = (digest, task_count, version_root, state_root).abi_encode();
+
+    let message_id = IArbSys::new(ARBSYS)
+      .send_tx_to_l_1(call::Call::new_in(self), target, data.into())
+      .map_err(|_| RegistryError::CheckpointFailed(CheckpointFailed {}))?;
+    self.last_checkpoint_digest.set(digest);
+    self.last_checkpoint_at.set(U64::from(block::timestamp()));
+
+    evm::log(CheckpointSentToL1 {
+      digest,
+      task_count,
+      version_root,
+      state_root,
+      message_id,
+    });
+    Ok(digest)
+  }
+
+  /// Returns `(digest, timestamp)` of the last checkpoint sent to L1.
+  pub fn last_checkpoint(&self) -> (FixedBytes<32>, U64) {
+    (self.last_checkpoint_digest.get(), self.last_checkpoint_at.get())
+  }
+
+  pub fn l1_controller(&self) -> Address {
+    self.l1_controller.get()
+  }
+
+  /// Sets the L1 governance contract. `controller` is its L1 address; calls
+  /// are accepted from its aliased L2 address.
+  pub fn set_l1_controller(&mut self, controller: Address) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    let previous_controller = self.l1_controller.get();
+    self.l1_controller.set(controller);
+
+    evm::log(L1ControllerChanged {
+      previous_controller,
+      new_controller: controller,
+    });
+    Ok(())
+  }
+
+  /// `register_task` for L1 governance, sent as a retryable ticket. No fee is
+  /// charged; the controller's L2 alias becomes the task admin.
+  pub fn register_task_from_l1(&mut self, task_id: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_l1_controller()?;
+    self.register(task_id)
+  }
+
+  /// `publish_new_version_with_uri` for L1 governance, sent as a retryable
+  /// ticket. The controller's alias must be able to publish to the task, which
+  /// it can for tasks it registered. No fee is charged.
+  pub fn publish_from_l1(&mut self, task_id: String, hash: FixedBytes<32>, artifact_uri: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_l1_controller()?;
+    self.publish(task_id, NewVersion {
+      artifact_uri,
+      ..NewVersion::new(hash)
+    })
+  }
+
+  /// Runs a batch of this contract's own entrypoints in one transaction, each
+  /// through a delegate call so `msg_sender()` is preserved. Returns each
+  /// call's raw return data; the first failure reverts the whole batch with its
+  /// revert data. Not payable, since every inner call would see the same value.
+  pub fn multicall(&mut self, calls: Vec<Bytes>) -> Result<Vec<Bytes>, Vec<u8>> {
+    let this = contract::address();
+    let forwarded_sender = forwarder::forwarded_sender();
+    let mut results = Vec::with_capacity(calls.len());
+    for data in calls {
+      // Relayed batches stay relayed: each inner call carries the sender suffix too.
+      let data = match forwarded_sender {
+        Some(sender) => [data.as_slice(), sender.as_slice()].concat(),
+        None => data.to_vec(),
+      };
+      // SAFETY: the target is this contract itself, so the callee upholds the
+      // same storage invariants as the caller.
+      let result = unsafe { call::delegate_call(call::Call::new_in(self), this, &data) };
+      match result {
+        Ok(output) => results.push(output.into()),
+        Err(call::Error::Revert(reason)) => return Err(reason),
+        Err(call::Error::AbiDecodingFailed(_)) => return Err(Vec::new()),
+      }
+    }
+    Ok(results)
+  }
+
+  /// The `bytes32` id of a task name, `keccak(task_id)`, for the `*_by_id` entrypoints.
+  pub fn task_id_of(&self, task_id: String) -> FixedBytes<32> {
+    task_key(&task_id)
+  }
+
+  pub fn task_name_of(&self, id: FixedBytes<32>) -> Result<String, RegistryError> {
+    self.task_name(id)
+  }
+
+  /// `publish_new_version` keyed by `bytes32` task id.
+  #[payable]
+  pub fn publish_new_version_by_id(&mut self, id: FixedBytes<32>, hash: FixedBytes<32>) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.task_name(id)?;
+    self.collect_fee(Fee::Publish(1))?;
+    self.publish(task_id, NewVersion::new(hash))
+  }
+
+  /// `get_latest` keyed by `bytes32` task id.
+  pub fn get_latest_by_id(&self, id: FixedBytes<32>) -> Result<VersionRecord, RegistryError> {
+    self.get_latest(self.task_name(id)?)
+  }
+
+  /// `get_version` keyed by `bytes32` task id.
+  pub fn get_version_by_id(&self, id: FixedBytes<32>, version: U64) -> Result<VersionRecord, RegistryError> {
+    self.get_version(self.task_name(id)?, version)
+  }
+
+  /// `get_version_count` keyed by `bytes32` task id, read straight from the
+  /// task without resolving its name.
+  pub fn get_version_count_by_id(&self, id: FixedBytes<32>) -> Result<U64, RegistryError> {
+    let task = self.tasks.getter(id);
+    if !task.registered.get() {
+      return Err(RegistryError::TaskIdNotFound(TaskIdNotFound { id }));
+    }
+    Ok(task.version_count.get())
+  }
+
+  pub fn namespace_admin(&self, namespace: String) -> Address {
+    self.namespace_admins.get(namespace)
+  }
+
+  /// Hands `namespace` to `admin`. Callable by the owner or the admin of a
+  /// namespace above it, so org admins can delegate team namespaces.
+  pub fn set_namespace_admin(&mut self, namespace: String, admin: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    match parent_namespace(&namespace) {
+      Some(parent) => self.assert_namespace_admin(parent)?,
+      None => self.assert_owner()?,
+    }
+    let previous_admin = self.namespace_admins.get(namespace.clone());
+    self.namespace_admins.insert(namespace.clone(), admin);
+
+    evm::log(NamespaceAdminChanged {
+      namespace,
+      previous_admin,
+      new_admin: admin,
+    });
+    Ok(())
+  }
+
+  /// Returns up to `limit` ids of tasks registered directly in `namespace`,
+  /// in registration order, starting at `offset`.
+  pub fn tasks_in_namespace(&self, namespace: String, offset: U64, limit: U64) -> Vec<String> {
+    let tasks = self.namespace_tasks.getter(namespace);
+    page(tasks.len(), offset, limit)
+      .filter_map(|i| tasks.getter(i))
+      .map(|task_id| task_id.get_string())
+      .collect()
+  }
+
+  /// Makes `alias` resolve to `task_id` in every getter, e.g. to keep a task's
+  /// old name working after a rename. Claiming the alias takes the same rights
+  /// as registering it. Aliases are permanent and cannot shadow a registered
+  /// task; writes still take the canonical id.
+  pub fn add_alias(&mut self, alias: String, task_id: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    self.assert_task_admin(&task_id)?;
+    self.assert_may_register(&alias)?;
+    if self.tasks.getter(task_key(&alias)).registered.get() || !self.aliases.getter(alias.clone()).is_empty() {
+      return Err(RegistryError::AliasExists(AliasExists { alias }));
+    }
+    self.aliases.setter(alias.clone()).set_str(&task_id);
+
+    evm::log(AliasAdded { alias, task_id });
+    Ok(())
+  }
+
+  /// The canonical id `task_id` resolves to; itself if it is not an alias.
+  pub fn resolve_task_id(&self, task_id: String) -> String {
+    self.canonical_id(&task_id)
+  }
+
+  pub fn is_strict_mode(&self, task_id: String) -> Result<bool, RegistryError> {
+    Ok(self.task(&task_id)?.strict_mode.get())
+  }
+
+  /// Toggles duplicate-hash rejection for the task. Hashes published before
+  /// strict mode was enabled count as well.
+  pub fn set_strict_mode(&mut self, task_id: String, enabled: bool) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.task_mut(&task_id)?.strict_mode.set(enabled);
+
+    evm::log(StrictModeChanged { task_id, enabled });
+    Ok(())
+  }
+
+  /// Returns the newest version published at or before `timestamp`, found by
+  /// binary search over the non-decreasing publish timestamps. Activation
+  /// delays and rollbacks are not taken into account, and pruned versions are
+  /// out of range.
+  pub fn get_version_at(&self, task_id: String, timestamp: U64) -> Result<VersionRecord, RegistryError> {
+    let task = self.task(&task_id)?;
+    let first = task.pruned_through.get() + U64::from(1);
+    // Invariant: versions below `lo` were published at or before `timestamp`,
+    // versions from `hi` on after it.
+    let (mut lo, mut hi) = (first, task.version_count.get() + U64::from(1));
+    while lo < hi {
+      let mid = lo + (hi - lo) / U64::from(2);
+      if task.versions.getter(mid).timestamp.get() <= timestamp {
+        lo = mid + U64::from(1);
+      } else {
+        hi = mid;
+      }
+    }
+    let version = lo - U64::from(1);
+    if version < first {
+      return Err(RegistryError::NoVersionAt(NoVersionAt {
+        task_id,
+        timestamp: timestamp.to::<u64>(),
+      }));
+    }
+    Ok(task.versions.getter(version).record(version, task.uri_visible_to(msg_sender())))
+  }
+
+  pub fn total_tasks(&self) -> U64 {
+    U64::from(self.task_index.len())
+  }
+
+  pub fn total_versions(&self) -> U64 {
+    self.total_versions.get()
+  }
+
+  pub fn versions_of(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.version_count.get())
+  }
+
+  /// Timestamp of the last write to the task: a publish, a role or config
+  /// change, a verification, and so on.
+  pub fn last_activity(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.last_activity.get())
+  }
+
+  /// Returns `(major, minor, patch)` for a version; all zero if it was
+  /// published without one.
+  pub fn get_semver(&self, task_id: String, version: U64) -> Result<(u32, u32, u32), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    Ok((
+      info.semver_major.get().to::<u32>(),
+      info.semver_minor.get().to::<u32>(),
+      info.semver_patch.get().to::<u32>(),
+    ))
+  }
+
+  /// Returns the newest version in the `major` line, for consumers pinned to it.
+  pub fn get_latest_in_major(&self, task_id: String, major: u32) -> Result<VersionRecord, RegistryError> {
+    let task = self.task(&task_id)?;
+    let version = task.latest_in_major.get(U32::from(major));
+    if version == U64::ZERO {
+      return Err(RegistryError::NoVersionInMajor(NoVersionInMajor { task_id, major }));
+    }
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).record(version, task.uri_visible_to(msg_sender())))
+  }
+
+  pub fn get_max_age(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.max_age.get())
+  }
+
+  pub fn set_max_age(&mut self, task_id: String, max_age: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.task_mut(&task_id)?.max_age.set(max_age);
+
+    evm::log(MaxAgeChanged {
+      task_id,
+      max_age: max_age.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  pub fn get_publish_cooldown(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.publish_cooldown.get())
+  }
+
+  /// Sets the minimum seconds between two publishes to the task, e.g. to stop
+  /// a runaway CI job. The registry owner can still publish inside the window.
+  pub fn set_publish_cooldown(&mut self, task_id: String, cooldown: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.task_mut(&task_id)?.publish_cooldown.set(cooldown);
+
+    evm::log(PublishCooldownChanged {
+      task_id,
+      cooldown: cooldown.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Declares that the calling contract depends on `version` of the task,
+  /// replacing any earlier pin. Yanking a pinned version logs
+  /// `PinnedVersionYanked`, after which `get_pinners` says who is affected.
+  pub fn pin(&mut self, task_id: String, version: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let consumer = msg_sender();
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let previous = task.pins.get(consumer);
+    if previous != version {
+      task.pins.insert(consumer, version);
+      if previous != U64::ZERO {
+        task.release_pin(previous, consumer);
+      }
+      task.add_pin(version, consumer);
+    }
+
+    evm::log(VersionPinned {
+      task_id,
+      version: version.to::<u64>(),
+      consumer,
+    });
+    Ok(())
+  }
+
+  pub fn unpin(&mut self, task_id: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let consumer = msg_sender();
+    let mut task = self.task_mut(&task_id)?;
+    let version = task.pins.get(consumer);
+    if version == U64::ZERO {
+      return Ok(());
+    }
+    task.pins.delete(consumer);
+    task.release_pin(version, consumer);
+
+    evm::log(VersionUnpinned {
+      task_id,
+      version: version.to::<u64>(),
+      consumer,
+    });
+    Ok(())
+  }
+
+  /// Version `consumer` is pinned to; zero if none.
+  pub fn get_pin(&self, task_id: String, consumer: Address) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.pins.get(consumer))
+  }
+
+  /// Number of consumers currently pinned to a version.
+  pub fn get_pin_count(&self, task_id: String, version: U64) -> Result<U64, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(U64::from(task.versions.getter(version).pinners.len()))
+  }
+
+  /// Returns up to `limit` of the consumers pinned to a version, from `offset`.
+  pub fn get_pinners(
+    &self,
+    task_id: String,
+    version: U64,
+    offset: U64,
+    limit: U64,
+  ) -> Result<Vec<Address>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    Ok(page(info.pinners.len(), offset, limit).filter_map(|i| info.pinners.get(i)).collect())
+  }
+
+  /// Marks a version as encrypted, recording where the ciphertext lives and a
+  /// commitment to its key. Callable once by the task admin or the publisher.
+  pub fn set_encrypted_artifact(
+    &mut self,
+    task_id: String,
+    version: U64,
+    encrypted_uri: String,
+    key_commitment: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_version_manager(&task_id, version)?;
+    if key_commitment == FixedBytes::ZERO {
+      return Err(RegistryError::NotEncrypted(NotEncrypted {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    let mut info = task.versions.setter(version);
+    if info.key_commitment.get() != FixedBytes::ZERO {
+      return Err(RegistryError::EncryptionAlreadySet(EncryptionAlreadySet {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    info.encrypted_uri.set_str(&encrypted_uri);
+    info.key_commitment.set(key_commitment);
+
+    evm::log(EncryptedArtifactSet {
+      task_id,
+      version: version.to::<u64>(),
+      encrypted_uri,
+      key_commitment,
+    });
+    Ok(())
+  }
+
+  /// Returns `(encrypted_uri, key_commitment)`; reverts with `NotEncrypted`
+  /// for public versions.
+  pub fn get_encrypted_artifact(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<(String, FixedBytes<32>), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    if info.key_commitment.get() == FixedBytes::ZERO {
+      return Err(RegistryError::NotEncrypted(NotEncrypted {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    Ok((info.encrypted_uri.get_string(), info.key_commitment.get()))
+  }
+
+  /// Records that the decryption key of an encrypted version was delivered to
+  /// `consumer`, wrapped to its key, as `wrapped_key_hash`. A later grant
+  /// replaces the earlier one, e.g. after the consumer rotates keys.
+  pub fn grant_access(
+    &mut self,
+    task_id: String,
+    version: U64,
+    consumer: Address,
+    wrapped_key_hash: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_version_manager(&task_id, version)?;
+    let mut task = self.task_mut(&task_id)?;
+    let mut info = task.versions.setter(version);
+    if info.key_commitment.get() == FixedBytes::ZERO {
+      return Err(RegistryError::NotEncrypted(NotEncrypted {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    info.key_grants.insert(consumer, wrapped_key_hash);
+
+    evm::log(AccessGranted {
+      task_id,
+      version: version.to::<u64>(),
+      consumer,
+      wrapped_key_hash,
+    });
+    Ok(())
+  }
+
+  /// Hash of the wrapped key granted to `consumer`; zero if none.
+  pub fn get_access_grant(
+    &self,
+    task_id: String,
+    version: U64,
+    consumer: Address,
+  ) -> Result<FixedBytes<32>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    Ok(task.versions.getter(version).key_grants.get(consumer))
+  }
+
+  /// Semantic version of the deployed feature set.
+  pub fn contract_version(&self) -> String {
+    String::from(CONTRACT_VERSION)
+  }
+
+  /// ERC-165 interface detection over the `IREGISTRY_*_ID` interfaces.
+  pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> bool {
+    [IERC165_ID, IREGISTRY_CORE_ID, IREGISTRY_PAYMENTS_ID, IREGISTRY_VERIFICATION_ID].contains(&interface_id.0)
+  }
+
+  /// Serializes up to `limit` tasks from position `cursor` of `task_ids`,
+  /// with their settings and unpruned versions, as an ABI-encoded
+  /// `ExportedTask[]`; `ExportedSettings` lists what is left out. Returns
+  /// the chunk and the cursor to continue from; it equals `task_count` when done.
+  pub fn export_state(&self, cursor: U64, limit: U64) -> (Bytes, U64) {
+    let range = page(self.task_index.len(), cursor, limit);
+    let next_cursor = U64::from(range.end);
+    let tasks: Vec<ExportedTask> = range
+      .filter_map(|i| self.task_index.get(i))
+      .map(|task_id| {
+        let task = self.tasks.getter(task_key(&task_id.get_string()));
+        let pruned_through = task.pruned_through.get();
+        let first = pruned_through + U64::from(1);
+        let versions = (first.to::<u64>()..=task.version_count.get().to::<u64>())
+          .map(|version| {
+            let info = task.versions.getter(U64::from(version));
+            ExportedVersion {
+              hash: info.hash.get(),
+              publisher: info.publisher.get(),
+              timestamp: info.timestamp.get().to::<u64>(),
+              block_number: info.block_number.get().to::<u64>(),
+              hash_algorithm: info.hash_algorithm.get().to::<u8>(),
+              yanked: info.yanked.get(),
+              config_hash: info.config_hash.get(),
+              artifact_uri: info.artifact_uri.get_string(),
+            }
+          })
+          .collect();
+        ExportedTask {
+          task_id: task_id.get_string(),
+          admin: task.admin.get(),
+          latest_version: task.latest_version.get().to::<u64>(),
+          pruned_through: pruned_through.to::<u64>(),
+          pruned_root: task.pruned_root.get(),
+          pruned_frontier: (0..merkle::DEPTH).map(|height| task.pruned_tree.get(height).unwrap_or_default()).collect(),
+          prev_record_hash: task.versions.getter(first).prev_record_hash.get(),
+          settings: task.exported_settings(),
+          versions,
+        }
+      })
+      .collect();
+    (Bytes::from(tasks.abi_encode()), next_cursor)
+  }
+
+  /// Replays a chunk produced by a previous deployment's `export_state`,
+  /// keeping the original publishers and timestamps. Owner only, and only
+  /// until `close_import`; a task that already exists reverts the chunk.
+  pub fn import_state(&mut self, chunk: Bytes) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    if self.import_closed.get() {
+      return Err(RegistryError::ImportClosed(ImportClosed {}));
+    }
+    let tasks = <Vec<ExportedTask>>::abi_decode(&chunk, true)
+      .map_err(|_| RegistryError::InvalidStateChunk(InvalidStateChunk {}))?;
+
+    let task_count = tasks.len() as u64;
+    let mut version_count = 0u64;
+    for ExportedTask {
+      task_id,
+      admin,
+      latest_version,
+      pruned_through,
+      pruned_root,
+      pruned_frontier,
+      prev_record_hash,
+      settings,
+      versions,
+    } in tasks
+    {
+      let count = pruned_through + versions.len() as u64;
+      let latest_pruned = latest_version != 0 && latest_version <= pruned_through;
+      // A task that never pruned carries a zero root rather than the empty tree's.
+      let expected_root = if pruned_through == 0 {
+        FixedBytes::ZERO
+      } else {
+        merkle::root(|height| pruned_frontier.get(height).copied().unwrap_or_default(), pruned_through)
+      };
+      if latest_version > count
+        || latest_pruned
+        || pruned_frontier.len() != merkle::DEPTH
+        || expected_root != pruned_root
+      {
+        return Err(RegistryError::InvalidStateChunk(InvalidStateChunk {}));
+      }
+      self.register(task_id.clone())?;
+      let mut task = self.task_mut(&task_id)?;
+      task.admin.set(admin);
+      task.version_count.set(U64::from(count));
+      task.latest_version.set(U64::from(latest_version));
+      task.pruned_through.set(U64::from(pruned_through));
+      task.pruned_root.set(pruned_root);
+      for (height, node) in pruned_frontier.iter().enumerate() {
+        // The version tree's frontier after the pruned versions is the same.
+        if let Some(mut slot) = task.pruned_tree.setter(height) {
+          slot.set(*node);
+        }
+        if let Some(mut slot) = task.version_tree.setter(height) {
+          slot.set(*node);
+        }
+      }
+      for (i, exported) in versions.iter().enumerate() {
+        let version = U64::from(pruned_through + i as u64 + 1);
+        let prev_record_hash = if i == 0 { prev_record_hash } else { task.record_hash(version - U64::from(1)) };
+        let mut info = task.versions.setter(version);
+        info.hash.set(exported.hash);
+        info.prev_record_hash.set(prev_record_hash);
+        info.publisher.set(exported.publisher);
+        info.timestamp.set(U64::from(exported.timestamp));
+        info.activates_at.set(U64::from(exported.timestamp));
+        info.block_number.set(U64::from(exported.block_number));
+        info.hash_algorithm.set(U8::from(exported.hash_algorithm));
+        info.yanked.set(exported.yanked);
+        info.config_hash.set(exported.config_hash);
+        info.artifact_uri.set_str(&exported.artifact_uri);
+        task.published_hashes.insert(exported.hash, true);
+        task.append_version_leaf(version, exported.hash);
+      }
+      task.import_settings(&settings);
+      task.cache_latest();
+      self.update_state_leaf(&task_id);
+      for category in settings.categories {
+        self.categorize(&task_id, category)?;
+      }
+      for (i, exported) in versions.iter().enumerate() {
+        self.index_version(&task_id, U64::from(pruned_through + i as u64 + 1), exported.hash);
+      }
+      version_count += versions.len() as u64;
+    }
+
+    evm::log(StateImported { task_count, version_count });
+    Ok(())
+  }
+
+  /// Permanently disables `import_state` once a migration is complete.
+  pub fn close_import(&mut self) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.import_closed.set(true);
+
+    evm::log(StateImportClosed {});
+    Ok(())
+  }
+
+  pub fn is_approver(&self, task_id: String, account: Address) -> Result<bool, RegistryError> {
+    Ok(self.task(&task_id)?.approvers.get(account))
+  }
+
+  pub fn grant_approver(&mut self, task_id: String, approver: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.approvers.get(approver) {
+      task.approvers.insert(approver, true);
+      let approver_count = task.approver_count.get() + U32::from(1);
+      task.approver_count.set(approver_count);
+      self.log_action(&task_id, ACTION_APPROVER_GRANT, U64::ZERO, approver);
+    }
+
+    evm::log(ApproverGranted { task_id, approver });
+    Ok(())
+  }
+
+  /// Reverts with `InvalidThreshold` if the remaining approvers could no
+  /// longer meet the threshold.
+  pub fn revoke_approver(&mut self, task_id: String, approver: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.approvers.get(approver) {
+      return Ok(());
+    }
+    let approver_count = task.approver_count.get() - U32::from(1);
+    let threshold = task.approval_threshold.get();
+    if approver_count < threshold {
+      return Err(RegistryError::InvalidThreshold(InvalidThreshold {
+        task_id,
+        threshold: threshold.to::<u32>(),
+        approver_count: approver_count.to::<u32>(),
+      }));
+    }
+    task.approvers.delete(approver);
+    task.approver_count.set(approver_count);
+    self.log_action(&task_id, ACTION_APPROVER_REVOKE, U64::ZERO, approver);
+
+    evm::log(ApproverRevoked { task_id, approver });
+    Ok(())
+  }
+
+  /// Returns `(threshold, proposal_ttl, approver_count)`.
+  pub fn get_approval_policy(&self, task_id: String) -> Result<(u32, U64, u32), RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok((
+      task.approval_threshold.get().to::<u32>(),
+      task.proposal_ttl.get(),
+      task.approver_count.get().to::<u32>(),
+    ))
+  }
+
+  /// Requires `threshold` approvals before a proposed version is published;
+  /// zero restores direct publishing. Proposals already open keep the expiry
+  /// they were created with.
+  pub fn set_approval_policy(
+    &mut self,
+    task_id: String,
+    threshold: u32,
+    proposal_ttl: U64,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let approver_count = task.approver_count.get().to::<u32>();
+    if threshold > approver_count {
+      return Err(RegistryError::InvalidThreshold(InvalidThreshold {
+        task_id,
+        threshold,
+        approver_count,
+      }));
+    }
+    task.approval_threshold.set(U32::from(threshold));
+    task.proposal_ttl.set(proposal_ttl);
+
+    evm::log(ApprovalPolicyChanged {
+      task_id,
+      threshold,
+      proposal_ttl: proposal_ttl.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Opens a proposal to publish `hash`, paying the publish fee up front.
+  /// Returns the proposal id.
+  #[payable]
+  pub fn propose_version(
+    &mut self,
+    task_id: String,
+    hash: FixedBytes<32>,
+    artifact_uri: String,
+  ) -> Result<U64, RegistryError> {
+    self.assert_not_paused()?;
+    let proposer = msg_sender();
+    self.assert_publisher(&task_id, proposer)?;
+    self.collect_fee(Fee::Publish(1))?;
+    let mut task = self.task_mut(&task_id)?;
+    let proposal_ttl = task.proposal_ttl.get();
+    let expires_at = if proposal_ttl == U64::ZERO {
+      U64::ZERO
+    } else {
+      U64::from(block::timestamp()).saturating_add(proposal_ttl)
+    };
+
+    let mut proposal = task.proposals.grow();
+    proposal.proposer.set(proposer);
+    proposal.hash.set(hash);
+    proposal.expires_at.set(expires_at);
+    proposal.artifact_uri.set_str(&artifact_uri);
+    let proposal_id = U64::from(task.proposals.len());
+
+    evm::log(VersionProposed {
+      task_id,
+      proposal_id: proposal_id.to::<u64>(),
+      hash,
+      proposer,
+      expires_at: expires_at.to::<u64>(),
+    });
+    Ok(proposal_id)
+  }
+
+  /// Signs off on a proposal. The approval that meets the threshold publishes
+  /// the version, attributed to the proposer.
+  pub fn approve_version(&mut self, task_id: String, proposal_id: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let approver = msg_sender();
+    let now = U64::from(block::timestamp());
+    let mut task = self.task_write(&task_id)?;
+    if !task.approvers.get(approver) {
+      return Err(RegistryError::NotApprover(NotApprover {
+        task_id,
+        account: approver,
+      }));
+    }
+    let threshold = task.approval_threshold.get();
+    let proposal = match proposal_id.checked_sub(U64::from(1)) {
+      Some(index) => task.proposals.setter(index),
+      None => None,
+    };
+    let Some(mut proposal) = proposal else {
+      return Err(RegistryError::ProposalNotFound(ProposalNotFound {
+        task_id,
+        proposal_id: proposal_id.to::<u64>(),
+      }));
+    };
+    if proposal.version.get() != U64::ZERO
+      || (proposal.expires_at.get() != U64::ZERO && now > proposal.expires_at.get())
+    {
+      return Err(RegistryError::ProposalClosed(ProposalClosed {
+        task_id,
+        proposal_id: proposal_id.to::<u64>(),
+      }));
+    }
+    if proposal.approved_by.get(approver) {
+      return Err(RegistryError::AlreadyApproved(AlreadyApproved {
+        task_id,
+        proposal_id: proposal_id.to::<u64>(),
+        approver,
+      }));
+    }
+    proposal.approved_by.insert(approver, true);
+    let approvals = proposal.approvals.get() + U32::from(1);
+    proposal.approvals.set(approvals);
+    let proposer = proposal.proposer.get();
+    let hash = proposal.hash.get();
+    let artifact_uri = proposal.artifact_uri.get_string();
+
+    evm::log(VersionApproved {
+      task_id: task_id.clone(),
+      proposal_id: proposal_id.to::<u64>(),
+      approver,
+      approvals: approvals.to::<u32>(),
+    });
+    // Either this or `publish` counts the call.
+    if approvals < threshold {
+      self.task_mut(&task_id)?;
+      return Ok(());
+    }
+
+    self.publish(task_id.clone(), NewVersion {
+      publisher: proposer,
+      artifact_uri,
+      approved: true,
+      ..NewVersion::new(hash)
+    })?;
+    let mut task = self.task_write(&task_id)?;
+    let version = task.version_count.get();
+    if let Some(mut proposal) = task.proposals.setter(proposal_id - U64::from(1)) {
+      proposal.version.set(version);
+    }
+    Ok(())
+  }
+
+  /// Returns `(proposer, hash, expires_at, approvals, version)`; `version` is
+  /// zero until the proposal has been approved.
+  pub fn get_proposal(
+    &self,
+    task_id: String,
+    proposal_id: U64,
+  ) -> Result<(Address, FixedBytes<32>, U64, u32, U64), RegistryError> {
+    let task = self.task(&task_id)?;
+    let proposal = match proposal_id.checked_sub(U64::from(1)) {
+      Some(index) => task.proposals.getter(index),
+      None => None,
+    };
+    let Some(proposal) = proposal else {
+      return Err(RegistryError::ProposalNotFound(ProposalNotFound {
+        task_id,
+        proposal_id: proposal_id.to::<u64>(),
+      }));
+    };
+    Ok((
+      proposal.proposer.get(),
+      proposal.hash.get(),
+      proposal.expires_at.get(),
+      proposal.approvals.get().to::<u32>(),
+      proposal.version.get(),
+    ))
+  }
+
+  /// Number of calls through which `account` has written to the task; a call
+  /// advances it once however many steps it takes. Batch calls count once per
+  /// entry.
+  pub fn nonce_of(&self, task_id: String, account: Address) -> Result<U64, RegistryError> {
+    Ok(self.task(&task_id)?.operation_nonces.get(account))
+  }
+
+  /// Root of the task's version Merkle tree, updated on every publish. Light
+  /// clients check individual versions against it with `verify_version_proof`.
+  pub fn get_version_tree_root(&self, task_id: String) -> Result<FixedBytes<32>, RegistryError> {
+    Ok(self.task(&task_id)?.version_tree_root())
+  }
+
+  /// Whether `proof`, the sibling nodes from the leaf up, shows that `version`
+  /// of the task has `hash` under the current root.
+  pub fn verify_version_proof(
+    &self,
+    task_id: String,
+    version: U64,
+    hash: FixedBytes<32>,
+    proof: Vec<FixedBytes<32>>,
+  ) -> Result<bool, RegistryError> {
+    let task = self.task(&task_id)?;
+    // Pruned versions are still leaves of the tree.
+    if version == U64::ZERO || version > task.version_count.get() {
+      return Ok(false);
+    }
+    let version = version.to::<u64>();
+    let root = merkle::root_from_proof(merkle::leaf(version, hash), version - 1, &proof);
+    Ok(root == Some(task.version_tree_root()))
+  }
+
+  /// Root over every task's version tree root (see `get_version_tree_root`),
+  /// as `record_checkpoint` would store it now. Kept up to date on every
+  /// registration and publish.
+  pub fn state_root(&self) -> FixedBytes<32> {
+    if self.task_index.is_empty() {
+      return merkle::root(|_| FixedBytes::ZERO, 0);
+    }
+    self.state_tree_root.get()
+  }
+
+  /// Appends the current state root to the checkpoint log. The owner may do
+  /// so at any time; anyone else once `checkpoint_interval` has passed since
+  /// the last checkpoint. Returns the new checkpoint's index.
+  pub fn record_checkpoint(&mut self) -> Result<U64, RegistryError> {
+    if msg_sender() != self.owner.get() {
+      let interval = self.checkpoint_interval.get();
+      if interval == U64::ZERO {
+        return Err(RegistryError::NotOwner(NotOwner { account: msg_sender() }));
+      }
+      let last = self.checkpoints.len().checked_sub(1).and_then(|i| self.checkpoints.getter(i));
+      let next_at = last.map_or(U64::ZERO, |checkpoint| checkpoint.timestamp.get().saturating_add(interval));
+      if U64::from(block::timestamp()) < next_at {
+        return Err(RegistryError::CheckpointTooSoon(CheckpointTooSoon {
+          next_at: next_at.to::<u64>(),
+        }));
+      }
+    }
+    Ok(self.append_checkpoint().0)
+  }
+
+  pub fn checkpoint_count(&self) -> U64 {
+    U64::from(self.checkpoints.len())
+  }
+
+  /// Returns `(state_root, task_count, block_number, timestamp)` of a checkpoint.
+  pub fn get_checkpoint(&self, index: U64) -> Result<(FixedBytes<32>, U64, U64, U64), RegistryError> {
+    let checkpoint = self::index(index)
+      .and_then(|i| self.checkpoints.getter(i))
+      .ok_or(RegistryError::CheckpointNotFound(CheckpointNotFound {
+        index: index.to::<u64>(),
+      }))?;
+    Ok((
+      checkpoint.state_root.get(),
+      checkpoint.task_count.get(),
+      checkpoint.block_number.get(),
+      checkpoint.timestamp.get(),
+    ))
+  }
+
+  pub fn checkpoint_interval(&self) -> U64 {
+    self.checkpoint_interval.get()
+  }
+
+  pub fn set_checkpoint_interval(&mut self, interval: U64) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.checkpoint_interval.set(interval);
+
+    evm::log(CheckpointIntervalChanged {
+      interval: interval.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Adds `category` to the taxonomy tasks can be listed under.
+  pub fn add_category(&mut self, category: String) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    if self.categories.get(category.clone()) {
+      return Err(RegistryError::CategoryExists(CategoryExists { category }));
+    }
+    self.categories.insert(category.clone(), true);
+    self.category_names.grow().set_str(&category);
+
+    evm::log(CategoryAdded { category });
+    Ok(())
+  }
+
+  /// Stops new assignments to `category`; tasks already listed stay listed.
+  pub fn retire_category(&mut self, category: String) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    if !self.categories.get(category.clone()) {
+      return Err(RegistryError::UnknownCategory(UnknownCategory { category }));
+    }
+    self.categories.insert(category.clone(), false);
+
+    evm::log(CategoryRetired { category });
+    Ok(())
+  }
+
+  /// Active categories, in creation order.
+  pub fn categories(&self) -> Vec<String> {
+    (0..self.category_names.len())
+      .filter_map(|i| self.category_names.getter(i))
+      .map(|category| category.get_string())
+      .filter(|category| self.categories.get(category.clone()))
+      .collect()
+  }
+
+  /// Lists an existing task under one more category.
+  pub fn assign_category(&mut self, task_id: String, category: String) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.categorize(&task_id, category)
+  }
+
+  pub fn get_task_categories(&self, task_id: String) -> Result<Vec<String>, RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok(
+      (0..task.categories.len())
+        .filter_map(|i| task.categories.getter(i))
+        .map(|category| category.get_string())
+        .collect(),
+    )
+  }
+
+  /// Returns the ids among the `limit` tasks from `offset` listed under
+  /// `category`, in assignment order, that are not archived.
+  pub fn tasks_by_category(&self, category: String, offset: U64, limit: U64) -> Vec<String> {
+    let tasks = self.category_tasks.getter(category);
+    page(tasks.len(), offset, limit)
+      .filter_map(|i| tasks.getter(i))
+      .map(|task_id| task_id.get_string())
+      .filter(|task_id| !self.tasks.getter(task_key(task_id)).archived.get())
+      .collect()
+  }
+
+  pub fn get_audit_log_length(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(U64::from(self.task(&task_id)?.action_log.len()))
+  }
+
+  /// Returns up to `limit` entries of the task's audit log from `offset`,
+  /// oldest first.
+  pub fn get_audit_log(&self, task_id: String, offset: U64, limit: U64) -> Result<Vec<ActionRecord>, RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok(
+      page(task.action_log.len(), offset, limit)
+        .filter_map(|i| task.action_log.getter(i))
+        .map(|entry| {
+          (
+            entry.action.get().to::<u8>(),
+            entry.actor.get(),
+            entry.version.get(),
+            entry.subject.get(),
+            entry.block_number.get(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  /// Opens the task's next federated learning round and returns its id. Only
+  /// one round may be open at a time.
+  pub fn open_round(&mut self, task_id: String) -> Result<U64, RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    self.assert_not_archived(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let round_count = U64::from(task.rounds.len());
+    let last_open = round_count
+      .checked_sub(U64::from(1))
+      .and_then(|index| task.rounds.getter(index))
+      .is_some_and(|last| last.finalized_at.get() == U64::ZERO);
+    if last_open {
+      return Err(RegistryError::RoundStillOpen(RoundStillOpen {
+        task_id,
+        round_id: round_count.to::<u64>(),
+      }));
+    }
+
+    let mut round = task.rounds.grow();
+    round.opened_at.set(U64::from(block::timestamp()));
+    let round_id = round_count + U64::from(1);
+
+    evm::log(RoundOpened {
+      task_id,
+      round_id: round_id.to::<u64>(),
+      coordinator: msg_sender(),
+    });
+    Ok(round_id)
+  }
+
+  pub fn is_round_client(&self, task_id: String, account: Address) -> Result<bool, RegistryError> {
+    Ok(self.task(&task_id)?.round_clients.get(account))
+  }
+
+  pub fn grant_round_client(&mut self, task_id: String, client: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.round_clients.get(client) {
+      task.round_clients.insert(client, true);
+      self.log_action(&task_id, ACTION_ROUND_CLIENT_GRANT, U64::ZERO, client);
+    }
+
+    evm::log(RoundClientGranted { task_id, client });
+    Ok(())
+  }
+
+  pub fn revoke_round_client(&mut self, task_id: String, client: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if task.round_clients.get(client) {
+      task.round_clients.delete(client);
+      self.log_action(&task_id, ACTION_ROUND_CLIENT_REVOKE, U64::ZERO, client);
+    }
+
+    evm::log(RoundClientRevoked { task_id, client });
+    Ok(())
+  }
+
+  /// Records the caller's commitment to its local update for an open round.
+  /// Only clients the coordinator granted may contribute, each at most once
+  /// per round.
+  pub fn submit_update_commitment(
+    &mut self,
+    task_id: String,
+    round_id: U64,
+    commitment: FixedBytes<32>,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    let client = msg_sender();
+    if !self.task(&task_id)?.round_clients.get(client) {
+      return Err(RegistryError::NotRoundClient(NotRoundClient { task_id, account: client }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    let round = match round_id.checked_sub(U64::from(1)) {
+      Some(index) => task.rounds.setter(index),
+      None => None,
+    };
+    let Some(mut round) = round else {
+      return Err(RegistryError::RoundNotFound(RoundNotFound {
+        task_id,
+        round_id: round_id.to::<u64>(),
+      }));
+    };
+    if round.finalized_at.get() != U64::ZERO {
+      return Err(RegistryError::RoundClosed(RoundClosed {
+        task_id,
+        round_id: round_id.to::<u64>(),
+      }));
+    }
+    if round.submitted.get(client) {
+      return Err(RegistryError::UpdateAlreadySubmitted(UpdateAlreadySubmitted {
+        task_id,
+        round_id: round_id.to::<u64>(),
+        client,
+      }));
+    }
+    round.submitted.insert(client, true);
+    round.updates.insert(client, commitment);
+    let participant_count = round.participant_count.get() + U64::from(1);
+    round.participant_count.set(participant_count);
+
+    evm::log(UpdateCommitted {
+      task_id,
+      round_id: round_id.to::<u64>(),
+      client,
+      commitment,
+    });
+    Ok(())
+  }
+
+  /// Closes the open round with the hash of the aggregated weights and of the
+  /// proof that they combine the committed updates. Returns the round id.
+  pub fn finalize_round(
+    &mut self,
+    task_id: String,
+    aggregate_hash: FixedBytes<32>,
+    proof_hash: FixedBytes<32>,
+  ) -> Result<U64, RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let round_id = U64::from(task.rounds.len());
+    let round = match round_id.checked_sub(U64::from(1)) {
+      Some(index) => task.rounds.setter(index),
+      None => None,
+    };
+    let Some(mut round) = round.filter(|round| round.finalized_at.get() == U64::ZERO) else {
+      return Err(RegistryError::RoundClosed(RoundClosed {
+        task_id,
+        round_id: round_id.to::<u64>(),
+      }));
+    };
+    round.finalized_at.set(U64::from(block::timestamp()));
+    round.aggregate_hash.set(aggregate_hash);
+    round.proof_hash.set(proof_hash);
+    let participant_count = round.participant_count.get();
+
+    evm::log(RoundFinalized {
+      task_id,
+      round_id: round_id.to::<u64>(),
+      aggregate_hash,
+      proof_hash,
+      participant_count: participant_count.to::<u64>(),
+    });
+    Ok(round_id)
+  }
+
+  pub fn get_round_count(&self, task_id: String) -> Result<U64, RegistryError> {
+    Ok(U64::from(self.task(&task_id)?.rounds.len()))
+  }
+
+  /// Returns `(opened_at, finalized_at, participant_count, aggregate_hash,
+  /// proof_hash)`; `finalized_at` and both hashes are zero while the round is open.
+  pub fn get_round(
+    &self,
+    task_id: String,
+    round_id: U64,
+  ) -> Result<(U64, U64, U64, FixedBytes<32>, FixedBytes<32>), RegistryError> {
+    let task = self.task(&task_id)?;
+    let round = match round_id.checked_sub(U64::from(1)) {
+      Some(index) => task.rounds.getter(index),
+      None => None,
+    };
+    let Some(round) = round else {
+      return Err(RegistryError::RoundNotFound(RoundNotFound {
+        task_id,
+        round_id: round_id.to::<u64>(),
+      }));
+    };
+    Ok((
+      round.opened_at.get(),
+      round.finalized_at.get(),
+      round.participant_count.get(),
+      round.aggregate_hash.get(),
+      round.proof_hash.get(),
+    ))
+  }
+
+  /// Update commitment `client` submitted for a round; zero if none.
+  pub fn get_update_commitment(
+    &self,
+    task_id: String,
+    round_id: U64,
+    client: Address,
+  ) -> Result<FixedBytes<32>, RegistryError> {
+    let task = self.task(&task_id)?;
+    let round = match round_id.checked_sub(U64::from(1)) {
+      Some(index) => task.rounds.getter(index),
+      None => None,
+    };
+    let Some(round) = round else {
+      return Err(RegistryError::RoundNotFound(RoundNotFound {
+        task_id,
+        round_id: round_id.to::<u64>(),
+      }));
+    };
+    Ok(round.updates.get(client))
+  }
+
+  /// Returns `(threshold, window, halt_task)`.
+  pub fn get_circuit_breaker(&self, task_id: String) -> Result<(u32, U64, bool), RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok((
+      task.breaker_threshold.get().to::<u32>(),
+      task.breaker_window.get(),
+      task.breaker_halts_task.get(),
+    ))
+  }
+
+  /// Trips a version's breaker after `threshold` rejected proofs within
+  /// `window` seconds, and with `halt_task` stops verification for the whole
+  /// task too. Only rejections from the task's staff or bonded provers count.
+  /// Zero disables the breaker; a threshold above one needs a nonzero window.
+  pub fn set_circuit_breaker(
+    &mut self,
+    task_id: String,
+    threshold: u32,
+    window: U64,
+    halt_task: bool,
+  ) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    if threshold > 1 && window == U64::ZERO {
+      return Err(RegistryError::InvalidBreakerWindow(InvalidBreakerWindow {
+        threshold,
+        window: 0,
+      }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    task.breaker_threshold.set(U32::from(threshold));
+    task.breaker_window.set(window);
+    task.breaker_halts_task.set(halt_task);
+
+    evm::log(CircuitBreakerChanged {
+      task_id,
+      threshold,
+      window: window.to::<u64>(),
+      halt_task,
+    });
+    Ok(())
+  }
+
+  /// Returns `(failures, window_start, tripped, task_halted)` for a version.
+  pub fn get_breaker_state(&self, task_id: String, version: U64) -> Result<(u32, U64, bool, bool), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    Ok((
+      info.proof_failures.get().to::<u32>(),
+      info.failure_window_start.get(),
+      info.breaker_tripped.get(),
+      task.halted.get(),
+    ))
+  }
+
+  /// Clears a version's tripped breaker and failure count, and lifts the task
+  /// halt if the breaker imposed one.
+  pub fn reset_circuit_breaker(&mut self, task_id: String, version: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    task.halted.set(false);
+    let mut info = task.versions.setter(version);
+    info.breaker_tripped.set(false);
+    info.proof_failures.set(U32::ZERO);
+    info.failure_window_start.set(U64::ZERO);
+
+    evm::log(CircuitBreakerReset {
+      task_id,
+      version: version.to::<u64>(),
+      admin: msg_sender(),
+    });
+    Ok(())
+  }
+
+  pub fn get_adversarial_key(&self, task_id: String) -> Result<Bytes, RegistryError> {
+    Ok(self.task(&task_id)?.adversarial_vk.get_bytes().into())
+  }
+
+  /// Registers the Groth16 key of the task's misclassification circuit, whose
+  /// public inputs are `[hash_hi, hash_lo, input_hi, input_lo, label,
+  /// prediction, finder]`: the version hash and input commitment split into
+  /// 128-bit halves, the input's true label, the model's output for it and the
+  /// claiming address, so a proof seen in the mempool cannot be stolen. The
+  /// key cannot change while any bounty is open.
+  pub fn set_adversarial_key(&mut self, task_id: String, vk: Bytes) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    if groth16::input_count(&vk) != Some(7) {
+      return Err(RegistryError::InvalidVerificationKey(InvalidVerificationKey {
+        task_id,
+        version: 0,
+      }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    if task.open_bounties.get() != U64::ZERO {
+      return Err(RegistryError::BountyKeyFrozen(BountyKeyFrozen { task_id }));
+    }
+    task.adversarial_vk.set_bytes(&vk);
+
+    evm::log(AdversarialKeySet {
+      task_id,
+      vk_digest: crypto::keccak(&vk),
+    });
+    Ok(())
+  }
+
+  /// Adds the attached value to a version's adversarial-example bounty, open
+  /// until `deadline`. A deadline may only be pushed back, never brought
+  /// forward.
+  #[payable]
+  pub fn fund_bounty(&mut self, task_id: String, version: U64, deadline: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let funder = msg_sender();
+    let amount = msg::value();
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    if info.adversarial_finder.get() != Address::ZERO {
+      return Err(RegistryError::BountyClosed(BountyClosed {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    if deadline <= U64::from(block::timestamp()) || deadline < info.bounty_deadline.get() {
+      return Err(RegistryError::InvalidExpiry(InvalidExpiry {
+        expires_at: deadline.to::<u64>(),
+      }));
+    }
+    let opened = info.bounty.get().is_zero() && !amount.is_zero();
+    let total = info.bounty.get() + amount;
+    info.bounty.set(total);
+    info.bounty_deadline.set(deadline);
+    let funded = info.bounty_funds.get(funder) + amount;
+    info.bounty_funds.insert(funder, funded);
+    if opened {
+      let open = task.open_bounties.get() + U64::from(1);
+      task.open_bounties.set(open);
+    }
+
+    evm::log(BountyFunded {
+      task_id,
+      version: version.to::<u64>(),
+      funder,
+      amount,
+      total,
+      deadline: deadline.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Returns the caller's share of a bounty whose deadline passed unclaimed.
+  pub fn refund_bounty(&mut self, task_id: String, version: U64) -> Result<U256, RegistryError> {
+    self.assert_not_paused()?;
+    let funder = msg_sender();
+    let task_id = self.canonical_id(&task_id);
+    let mut task = self.tasks.setter(task_key(&task_id));
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    if info.adversarial_finder.get() != Address::ZERO {
+      return Err(RegistryError::BountyClosed(BountyClosed {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let deadline = info.bounty_deadline.get();
+    if U64::from(block::timestamp()) < deadline {
+      return Err(RegistryError::BountyNotExpired(BountyNotExpired {
+        task_id,
+        version: version.to::<u64>(),
+        deadline: deadline.to::<u64>(),
+      }));
+    }
+    let amount = info.bounty_funds.get(funder);
+    if amount.is_zero() {
+      return Ok(amount);
+    }
+    info.bounty_funds.delete(funder);
+    let remaining = info.bounty.get() - amount;
+    info.bounty.set(remaining);
+    if remaining.is_zero() {
+      let open = task.open_bounties.get() - U64::from(1);
+      task.open_bounties.set(open);
+    }
+    if call::transfer_eth(funder, amount).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to: funder, amount }));
+    }
+
+    evm::log(BountyRefunded {
+      task_id,
+      version: version.to::<u64>(),
+      funder,
+      amount,
+    });
+    Ok(amount)
+  }
+
+  /// Claims a version's bounty with a proof, against the task's adversarial
+  /// key, that the version outputs `prediction` for the committed input whose
+  /// true label is `label`, bound to the caller. The first valid proof before
+  /// the bounty deadline flags the version and is paid the whole bounty; an
+  /// invalid one returns `false`.
+  pub fn submit_adversarial_example(
+    &mut self,
+    task_id: String,
+    version: U64,
+    input_commitment: FixedBytes<32>,
+    label: U256,
+    prediction: U256,
+    proof: Bytes,
+  ) -> Result<bool, RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    if label == prediction {
+      return Err(RegistryError::NotMisclassified(NotMisclassified { label, prediction }));
+    }
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    if info.adversarial_finder.get() != Address::ZERO {
+      return Err(RegistryError::BountyClosed(BountyClosed {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let deadline = info.bounty_deadline.get();
+    if !info.bounty.get().is_zero() && deadline <= U64::from(block::timestamp()) {
+      return Err(RegistryError::BountyClosed(BountyClosed {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let vk = task.adversarial_vk.get_bytes();
+    if vk.is_empty() {
+      return Err(RegistryError::NoVerificationKey(NoVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let finder = msg_sender();
+    let [hash_hi, hash_lo] = hash_limbs(info.hash.get());
+    let [input_hi, input_lo] = hash_limbs(input_commitment);
+    let finder_input = U256::from_be_slice(finder.as_slice());
+    let public_inputs = [hash_hi, hash_lo, input_hi, input_lo, label, prediction, finder_input];
+    if !groth16::verify(&vk, &proof, &public_inputs) {
+      return Ok(false);
+    }
+
+    let mut task = self.task_mut(&task_id)?;
+    let bounty = task.versions.getter(version).bounty.get();
+    if !bounty.is_zero() {
+      let open = task.open_bounties.get() - U64::from(1);
+      task.open_bounties.set(open);
+    }
+    let mut info = task.versions.setter(version);
+    info.bounty.set(U256::ZERO);
+    info.adversarial_finder.set(finder);
+    info.adversarial_input.set(input_commitment);
+    if bounty != U256::ZERO && call::transfer_eth(finder, bounty).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to: finder, amount: bounty }));
+    }
+
+    evm::log(AdversarialExampleFound {
+      task_id,
+      version: version.to::<u64>(),
+      finder,
+      input_commitment,
+      bounty,
+    });
+    Ok(true)
+  }
+
+  /// Returns `(bounty, deadline, finder, input_commitment)`; `finder` is zero
+  /// while no adversarial example has been proven.
+  pub fn get_bounty(
+    &self,
+    task_id: String,
+    version: U64,
+  ) -> Result<(U256, U64, Address, FixedBytes<32>), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    Ok((
+      info.bounty.get(),
+      info.bounty_deadline.get(),
+      info.adversarial_finder.get(),
+      info.adversarial_input.get(),
+    ))
+  }
+
+  pub fn get_training_key(&self, task_id: String) -> Result<Bytes, RegistryError> {
+    Ok(self.task(&task_id)?.training_vk.get_bytes().into())
+  }
+
+  /// Registers the task's training-circuit key, whose last four public inputs
+  /// are `[hash_hi, hash_lo, data_hi, data_lo]`: the version hash and its
+  /// training-data commitment split into 128-bit halves. Unlike inference keys
+  /// it can be replaced as the training pipeline changes; versions already
+  /// verified keep their status.
+  pub fn set_training_key(&mut self, task_id: String, vk: Bytes) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    if groth16::input_count(&vk).is_none_or(|count| count < 4) {
+      return Err(RegistryError::InvalidVerificationKey(InvalidVerificationKey {
+        task_id,
+        version: 0,
+      }));
+    }
+    self.task_mut(&task_id)?.training_vk.set_bytes(&vk);
+
+    evm::log(TrainingKeySet {
+      task_id,
+      vk_digest: crypto::keccak(&vk),
+    });
+    Ok(())
+  }
+
+  /// Checks a proof that the version was trained on its registered dataset
+  /// revisions against the task's training key. The version hash and
+  /// `get_training_commitment` are appended to `public_inputs`, so the proof
+  /// cannot be replayed for another model or dataset. A valid proof marks the
+  /// version training-verified; an invalid one returns `false`.
+  pub fn submit_training_proof(
+    &mut self,
+    task_id: String,
+    version: U64,
+    proof: Bytes,
+    mut public_inputs: Vec<U256>,
+  ) -> Result<bool, RegistryError> {
+    self.assert_not_paused()?;
+    let task_id = self.canonical_id(&task_id);
+    let data_commitment = self.get_training_commitment(task_id.clone(), version)?;
+    let task = self.task(&task_id)?;
+    public_inputs.extend(hash_limbs(task.versions.getter(version).hash.get()));
+    public_inputs.extend(hash_limbs(data_commitment));
+    let vk = task.training_vk.get_bytes();
+    if vk.is_empty() {
+      return Err(RegistryError::NoVerificationKey(NoVerificationKey {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    if !groth16::verify(&vk, &proof, &public_inputs) {
+      return Ok(false);
+    }
+
+    let inputs_hash = crypto::keccak(public_inputs.abi_encode());
+    let mut task = self.task_mut(&task_id)?;
+    task.versions.setter(version).training_proof.set(inputs_hash);
+
+    evm::log(TrainingVerified {
+      task_id,
+      version: version.to::<u64>(),
+      prover: msg_sender(),
+      inputs_hash,
+    });
+    Ok(true)
+  }
+
+  /// keccak of the hashes of the dataset revisions a version declared, in
+  /// order; training proofs are bound to it.
+  pub fn get_training_commitment(&self, task_id: String, version: U64) -> Result<FixedBytes<32>, RegistryError> {
+    let hashes = self.get_training_inputs(task_id.clone(), version)?;
+    if hashes.is_empty() {
+      return Err(RegistryError::NoTrainingData(NoTrainingData {
+        task_id,
+        version: version.to::<u64>(),
+      }));
+    }
+    let data: Vec<u8> = hashes.iter().flat_map(|(_, _, hash)| hash.0).collect();
+    Ok(crypto::keccak(data))
+  }
+
+  /// Returns `(training_verified, inputs_hash)` for a version.
+  pub fn get_training_status(&self, task_id: String, version: U64) -> Result<(bool, FixedBytes<32>), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let inputs_hash = task.versions.getter(version).training_proof.get();
+    Ok((inputs_hash != FixedBytes::ZERO, inputs_hash))
+  }
+
+  /// `(task_id, version)` pairs the version declared at publish time.
+  pub fn get_dependencies(&self, task_id: String, version: U64) -> Result<Vec<(String, U64)>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    Ok(
+      (0..info.dependencies.len())
+        .filter_map(|i| info.dependencies.getter(i))
+        .map(|edge| (edge.task_id.get_string(), edge.version.get()))
+        .collect(),
+    )
+  }
+
+  /// Returns up to `limit` of the versions that depend on this one, from
+  /// `offset`, in publish order.
+  pub fn get_dependents(
+    &self,
+    task_id: String,
+    version: U64,
+    offset: U64,
+    limit: U64,
+  ) -> Result<Vec<(String, U64)>, RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    Ok(
+      page(info.dependents.len(), offset, limit)
+        .filter_map(|i| info.dependents.getter(i))
+        .map(|edge| (edge.task_id.get_string(), edge.version.get()))
+        .collect(),
+    )
+  }
+
+  /// Returns `(license, commercial_use, redistribution, fine_tuning)`. Versions
+  /// published without a license read back as empty with nothing permitted.
+  pub fn license_of(&self, task_id: String, version: U64) -> Result<(String, bool, bool, bool), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    let flags = info.license_flags.get().to::<u8>();
+    Ok((
+      info.license.get_string(),
+      flags & LICENSE_COMMERCIAL_USE != 0,
+      flags & LICENSE_REDISTRIBUTION != 0,
+      flags & LICENSE_FINE_TUNING != 0,
+    ))
+  }
+
+  pub fn rating_deposit(&self) -> U256 {
+    self.rating_deposit.get()
+  }
+
+  pub fn set_rating_deposit(&mut self, rating_deposit: U256) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.rating_deposit.set(rating_deposit);
+
+    evm::log(RatingDepositChanged { rating_deposit });
+    Ok(())
+  }
+
+  /// Scores a version from 1 to 5, locking the attached value, which must be
+  /// at least `rating_deposit`, until the rating is withdrawn. One standing
+  /// rating per account and version.
+  #[payable]
+  pub fn rate_version(&mut self, task_id: String, version: U64, score: u8) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    if !(1..=5).contains(&score) {
+      return Err(RegistryError::InvalidRating(InvalidRating { score }));
+    }
+    let required = self.rating_deposit.get();
+    let deposit = msg::value();
+    if deposit < required {
+      return Err(RegistryError::InsufficientFee(InsufficientFee {
+        required,
+        provided: deposit,
+      }));
+    }
+    let rater = msg_sender();
+    let mut task = self.task_mut(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let mut info = task.versions.setter(version);
+    if info.ratings.get(rater).score.get() != U8::ZERO {
+      return Err(RegistryError::AlreadyRated(AlreadyRated {
+        task_id,
+        version: version.to::<u64>(),
+        rater,
+      }));
+    }
+    let mut rating = info.ratings.setter(rater);
+    rating.score.set(U8::from(score));
+    rating.deposit.set(deposit);
+    let rating_sum = info.rating_sum.get() + U64::from(score);
+    info.rating_sum.set(rating_sum);
+    let rating_count = info.rating_count.get() + U64::from(1);
+    info.rating_count.set(rating_count);
+
+    evm::log(VersionRated {
+      task_id,
+      version: version.to::<u64>(),
+      rater,
+      score,
+      deposit,
+    });
+    Ok(())
+  }
+
+  /// Takes back the caller's rating of a version and refunds its deposit,
+  /// also once the version has been pruned.
+  pub fn withdraw_rating(&mut self, task_id: String, version: U64) -> Result<(), RegistryError> {
+    // Not pausable: raters can always take their deposit back.
+    let rater = msg_sender();
+    let mut task = self.task_mut(&task_id)?;
+    if version == U64::ZERO || version > task.version_count.get() {
+      return Err(version_not_found(&task_id, version));
+    }
+    let pruned = !task.has_version(version);
+    let mut info = task.versions.setter(version);
+    let rating = info.ratings.get(rater);
+    let score = rating.score.get();
+    let deposit = rating.deposit.get();
+    if score == U8::ZERO {
+      return Err(RegistryError::NotRated(NotRated {
+        task_id,
+        version: version.to::<u64>(),
+        rater,
+      }));
+    }
+    info.ratings.delete(rater);
+    // Pruning already cleared the totals.
+    if !pruned {
+      let rating_sum = info.rating_sum.get() - U64::from(score);
+      info.rating_sum.set(rating_sum);
+      let rating_count = info.rating_count.get() - U64::from(1);
+      info.rating_count.set(rating_count);
+    }
+    if call::transfer_eth(rater, deposit).is_err() {
+      return Err(RegistryError::TransferFailed(TransferFailed { to: rater, amount: deposit }));
+    }
+
+    evm::log(RatingWithdrawn {
+      task_id,
+      version: version.to::<u64>(),
+      rater,
+      deposit,
+    });
+    Ok(())
+  }
+
+  /// Returns `(score_sum, rating_count)` over the version's standing ratings;
+  /// the average is `score_sum / rating_count`.
+  pub fn get_rating(&self, task_id: String, version: U64) -> Result<(U64, U64), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    Ok((info.rating_sum.get(), info.rating_count.get()))
+  }
+
+  /// Returns `(score, deposit)` for `rater`'s rating of a version; the score is
+  /// zero if there is none.
+  pub fn get_rating_of(&self, task_id: String, version: U64, rater: Address) -> Result<(u8, U256), RegistryError> {
+    let task = self.task(&task_id)?;
+    if !task.has_version(version) {
+      return Err(version_not_found(&task_id, version));
+    }
+    let info = task.versions.getter(version);
+    let rating = info.ratings.get(rater);
+    Ok((rating.score.get().to::<u8>(), rating.deposit.get()))
+  }
+
+  pub fn keeper_bounty(&self) -> U256 {
+    self.keeper_bounty.get()
+  }
+
+  pub fn set_keeper_bounty(&mut self, keeper_bounty: U256) -> Result<(), RegistryError> {
+    self.assert_owner()?;
+    self.keeper_bounty.set(keeper_bounty);
+
+    evm::log(KeeperBountyChanged { keeper_bounty });
+    Ok(())
+  }
+
+  /// `refund_inference` for keepers: refunds a timed-out job and pays the
+  /// caller the keeper bounty, scaled to the refunded escrow.
+  pub fn poke_inference(&mut self, job_id: U64) -> Result<U256, RegistryError> {
+    let payment = index(job_id)
+      .and_then(|i| self.jobs.getter(i))
+      .map(|job| job.payment.get())
+      .unwrap_or_default();
+    self.refund_inference(job_id)?;
+    self.pay_keeper(payment)
+  }
+
+  /// Clears a lapsed subscription from storage. Anyone may call it and collect
+  /// the keeper bounty, scaled to the ETH the subscription was paid with.
+  pub fn poke_subscription(&mut self, task_id: String, subscriber: Address) -> Result<U256, RegistryError> {
+    let expires_at = self.task(&task_id)?.subscriptions.get(subscriber);
+    if expires_at == U64::ZERO || U64::from(block::timestamp()) < expires_at {
+      return Err(RegistryError::SubscriptionNotExpired(SubscriptionNotExpired {
+        task_id,
+        subscriber,
+      }));
+    }
+    let task_id = self.canonical_id(&task_id);
+    let mut task = self.tasks.setter(task_key(&task_id));
+    let paid = task.subscription_paid.get(subscriber);
+    task.subscriptions.delete(subscriber);
+    task.subscription_paid.delete(subscriber);
+
+    evm::log(SubscriptionExpired { task_id, subscriber });
+    self.pay_keeper(paid)
+  }
+
+  /// Returns the task's hooks as `(target, gas_limit)`, in call order.
+  pub fn get_hooks(&self, task_id: String) -> Result<Vec<(Address, U64)>, RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok(
+      (0..task.hooks.len())
+        .filter_map(|i| task.hooks.getter(i))
+        .map(|hook| (hook.target.get(), hook.gas_limit.get()))
+        .collect(),
+    )
+  }
+
+  /// Registers a contract implementing `IPublishHook` to be called after every
+  /// publish to the task, with at most `gas_limit` gas. The hook cannot call
+  /// back into the registry.
+  pub fn add_hook(&mut self, task_id: String, hook: Address, gas_limit: U64) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    if gas_limit == U64::ZERO {
+      return Err(RegistryError::InvalidHookGas(InvalidHookGas { gas_limit: 0 }));
+    }
+    let mut task = self.task_mut(&task_id)?;
+    if task.hooks.len() >= MAX_HOOKS {
+      return Err(RegistryError::TooManyHooks(TooManyHooks { task_id }));
+    }
+    let mut entry = task.hooks.grow();
+    entry.target.set(hook);
+    entry.gas_limit.set(gas_limit);
+
+    evm::log(HookAdded {
+      task_id,
+      hook,
+      gas_limit: gas_limit.to::<u64>(),
+    });
+    Ok(())
+  }
+
+  /// Unregisters a hook. The last hook takes its place in the call order.
+  pub fn remove_hook(&mut self, task_id: String, hook: Address) -> Result<(), RegistryError> {
+    self.assert_not_paused()?;
+    self.assert_task_admin(&task_id)?;
+    let mut task = self.task_mut(&task_id)?;
+    let index = (0..task.hooks.len()).find(|&i| task.hooks.getter(i).map(|entry| entry.target.get()) == Some(hook));
+    let Some(index) = index else {
+      return Err(RegistryError::HookNotFound(HookNotFound { task_id, hook }));
+    };
+    let last = task.hooks.len() - 1;
+    if index != last {
+      let (target, gas_limit) = match task.hooks.getter(last) {
+        Some(entry) => (entry.target.get(), entry.gas_limit.get()),
+        None => (Address::ZERO, U64::ZERO),
+      };
+      if let Some(mut entry) = task.hooks.setter(index) {
+        entry.target.set(target);
+        entry.gas_limit.set(gas_limit);
+      }
+    }
+    task.hooks.erase_last();
+
+    evm::log(HookRemoved { task_id, hook });
+    Ok(())
+  }
+
+  /// Returns `(pruned_through, pruned_root)`: the highest deleted version
+  /// and the Merkle root over versions 1 to it, in `merkle`'s leaf layout.
+  pub fn get_pruned_history(&self, task_id: String) -> Result<(U64, FixedBytes<32>), RegistryError> {
+    let task = self.task(&task_id)?;
+    Ok((task.pruned_through.get(), task.pruned_root.get()))
+  }
+
+  /// Deletes the task's oldest versions, keeping the newest `keep_last_n` and
+  /// everything from the active version on, and stopping early at a version
+  /// that still holds funds or that a consumer contract has pinned. Each call deletes
+  /// at most `MAX_PRUNE_BATCH`; returns how many it did. Pruned versions read
+  /// as missing, but remain provable against both the version tree and
+  /// `get_pruned_history`; `export_state` carries only that history.
+  pub fn prune_versions(&mut self, task_id: String, keep_last_n: U64) -> Result<U64, RegistryError> {
+    self.assert_owner()?;
+    let mut task = self.task_mut(&task_id)?;
+    let start = task.pruned_through.get();
+    let count = task.version_count.get();
+    let keep = keep_last_n.max(U64::from(1));
+    let end = count
+      .saturating_sub(keep)
+      .min(task.active_version().saturating_sub(U64::from(1)))
+      .min(start + U64::from(MAX_PRUNE_BATCH));
+
+    let mut version = start;
+    while version < end {
+      let next = version + U64::from(1);
+      let mut info = task.versions.setter(next);
+      if info.holds_funds() || info.contract_pins.get() != U64::ZERO {
+        break;
+      }
+      let leaf = merkle::leaf(next.to::<u64>(), info.hash.get());
+      info.erase();
+      let (height, node) = merkle::append(
+        |height| task.pruned_tree.get(height).unwrap_or_default(),
+        version.to::<u64>(),
+        leaf,
+      );
+      if let Some(mut slot) = task.pruned_tree.setter(height) {
+        slot.set(node);
+      }
+      version = next;
+    }
+    if version == start {
+      return Ok(U64::ZERO);
+    }
+    let root = merkle::root(
+      |height| task.pruned_tree.get(height).unwrap_or_default(),
+      version.to::<u64>(),
+    );
+    task.pruned_through.set(version);
+    task.pruned_root.set(root);
+
+    evm::log(VersionsPruned {
+      task_id,
+      pruned_through: version.to::<u64>(),
+      pruned_root: root,
+    });
+    Ok(version - start)
+  }
+}
@@ -0,0 +1,111 @@
+//! Append-only keccak Merkle tree over a task's versions, kept as the
+//! deposit-contract style frontier: one node per level, so each append costs
+//! at most `DEPTH` hashes and storage never grows past `DEPTH` words.
+//!
+//! Leaf `i` is `keccak(abi.encode(uint64 version, bytes32 hash))` for version
+//! `i + 1`; empty leaves are zero and internal nodes are `keccak(left || right)`.
+//!
+//! `update` covers trees whose leaves change, such as the registry state tree,
+//! by keeping every node rather than just the frontier.
+
+use alloc::vec::Vec;
+use alloy_sol_types::SolValue;
+use stylus_sdk::alloy_primitives::B256;
+use stylus_sdk::crypto;
+use stylus_sdk::keccak_const::Keccak256;
+
+/// Tree height; enough for 2^32 versions per task.
+pub const DEPTH: usize = 32;
+
+/// Root of an empty subtree at each height.
+const ZERO_HASHES: [[u8; 32]; DEPTH] = {
+  let mut zeros = [[0u8; 32]; DEPTH];
+  let mut height = 1;
+  while height < DEPTH {
+    zeros[height] = Keccak256::new()
+      .update(&zeros[height - 1])
+      .update(&zeros[height - 1])
+      .finalize();
+    height += 1;
+  }
+  zeros
+};
+
+pub fn leaf(version: u64, hash: B256) -> B256 {
+  crypto::keccak((version, hash).abi_encode())
+}
+
+pub fn hash_pair(left: B256, right: B256) -> B256 {
+  let mut preimage = [0u8; 64];
+  preimage[..32].copy_from_slice(left.as_slice());
+  preimage[32..].copy_from_slice(right.as_slice());
+  crypto::keccak(preimage)
+}
+
+/// Folds `leaf` into `frontier` as leaf number `count` (zero-based). Returns
+/// the height whose frontier node changed, and that node's new value.
+pub fn append(frontier: impl Fn(usize) -> B256, count: u64, leaf: B256) -> (usize, B256) {
+  let mut node = leaf;
+  let mut size = count + 1;
+  let mut height = 0;
+  while size & 1 == 0 && height + 1 < DEPTH {
+    node = hash_pair(frontier(height), node);
+    size >>= 1;
+    height += 1;
+  }
+  (height, node)
+}
+
+/// Root of a tree holding `count` leaves, given its frontier.
+pub fn root(frontier: impl Fn(usize) -> B256, count: u64) -> B256 {
+  let mut node = B256::ZERO;
+  let mut size = count;
+  for (height, zero) in ZERO_HASHES.iter().enumerate() {
+    node = if size & 1 == 1 {
+      hash_pair(frontier(height), node)
+    } else {
+      hash_pair(node, B256::from(*zero))
+    };
+    size >>= 1;
+  }
+  node
+}
+
+/// Sets leaf `index` of a tree of `count` leaves kept node by node, where
+/// `node(height, i)` reads a stored node and an unset one reads zero. Returns
+/// the new nodes on the leaf's path, from the leaf up to the subtree holding
+/// all `count` leaves, and the new root. Nodes above that subtree only ever
+/// have empty right siblings, so they are not stored.
+pub fn update(node: impl Fn(usize, u64) -> B256, count: u64, index: u64, leaf: B256) -> (Vec<B256>, B256) {
+  let top = (u64::BITS - count.saturating_sub(1).leading_zeros()) as usize;
+  let mut path = Vec::with_capacity(top + 1);
+  let mut value = leaf;
+  let mut i = index;
+  for (height, zero) in ZERO_HASHES.iter().enumerate() {
+    if height <= top {
+      path.push(value);
+    }
+    let sibling = if height < top { node(height, i ^ 1) } else { B256::ZERO };
+    let sibling = if sibling == B256::ZERO { B256::from(*zero) } else { sibling };
+    value = if i & 1 == 1 { hash_pair(sibling, value) } else { hash_pair(value, sibling) };
+    i >>= 1;
+  }
+  (path, value)
+}
+
+/// Root implied by `leaf` sitting at `index` with sibling path `proof`, listed
+/// from the leaf level up. `None` unless the proof has exactly `DEPTH` nodes.
+pub fn root_from_proof(leaf: B256, index: u64, proof: &[B256]) -> Option<B256> {
+  if proof.len() != DEPTH {
+    return None;
+  }
+  let mut node = leaf;
+  for (height, sibling) in proof.iter().enumerate() {
+    node = if (index >> height) & 1 == 1 {
+      hash_pair(*sibling, node)
+    } else {
+      hash_pair(node, *sibling)
+    };
+  }
+  Some(node)
+}
@@ -0,0 +1,337 @@
+//! In-process stand-in for the Stylus `vm_hooks` imports, so `Registry` can be
+//! driven directly from unit tests.
+//!
+//! Each test thread gets its own host: storage, caller context, block clock and
+//! emitted logs. Of the precompiles only ecrecover is available; beyond plain
+//! ETH transfers every other external call fails, as there are no other
+//! contracts.
+
+use core::slice;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha3::{Digest, Keccak256};
+use stylus_sdk::alloy_primitives::{address, Address, B256, U256};
+use stylus_sdk::storage::StorageType;
+
+use crate::Registry;
+
+#[derive(Default)]
+struct State {
+  storage: HashMap<B256, B256>,
+  /// Slots written through `storage_cache_bytes32`, in order.
+  writes: Vec<B256>,
+  sender: Address,
+  origin: Address,
+  value: U256,
+  timestamp: u64,
+  block_number: u64,
+  logs: Vec<Log>,
+  return_data: Vec<u8>,
+  /// Accounts reported as having code.
+  contracts: Vec<Address>,
+}
+
+/// An event as emitted through `emit_log`.
+#[derive(Clone, Debug)]
+pub struct Log {
+  pub topics: Vec<B256>,
+  pub data: Vec<u8>,
+}
+
+thread_local! {
+  static STATE: RefCell<State> = RefCell::new(State::default());
+}
+
+fn with<R>(f: impl FnOnce(&mut State) -> R) -> R {
+  STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// The contract address reported by `contract_address`.
+pub const CONTRACT: Address = Address::repeat_byte(0xcc);
+
+/// Wipes the host and returns a registry at slot zero, with `owner` as the
+/// sender and origin of subsequent calls.
+pub fn deploy(owner: Address) -> Registry {
+  with(|state| {
+    *state = State {
+      timestamp: 1_700_000_000,
+      block_number: 1,
+      ..State::default()
+    }
+  });
+  act_as(owner);
+  unsafe { Registry::new(U256::ZERO, 0) }
+}
+
+/// Makes `account` both `msg::sender` and `tx::origin`, with no value attached.
+pub fn act_as(account: Address) {
+  with(|state| {
+    state.sender = account;
+    state.origin = account;
+    state.value = U256::ZERO;
+  });
+  // A fresh call, not relayed until a test says otherwise.
+  crate::forwarder::unwrap(Vec::new(), Address::ZERO);
+}
+
+/// Makes `account` report having code, as a deployed contract would.
+pub fn set_code(account: Address) {
+  with(|state| state.contracts.push(account));
+}
+
+pub fn set_value(value: U256) {
+  with(|state| state.value = value);
+}
+
+/// Moves the clock forward by `seconds` and mines one block.
+pub fn advance(seconds: u64) {
+  with(|state| {
+    state.timestamp += seconds;
+    state.block_number += 1;
+  });
+}
+
+pub fn timestamp() -> u64 {
+  with(|state| state.timestamp)
+}
+
+/// Runs `call` like a transaction: if it returns an error, storage writes and
+/// logs made during it are discarded, as a revert would.
+pub fn transact<T, E>(call: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+  let (storage, log_count) = with(|state| (state.storage.clone(), state.logs.len()));
+  let result = call();
+  if result.is_err() {
+    with(|state| {
+      state.storage = storage;
+      state.logs.truncate(log_count);
+    });
+  }
+  result
+}
+
+/// The word currently stored at `slot`.
+pub fn load(slot: B256) -> B256 {
+  with(|state| state.storage.get(&slot).copied().unwrap_or_default())
+}
+
+/// Takes the storage slots written since the last call, one entry per write.
+pub fn take_writes() -> Vec<B256> {
+  with(|state| core::mem::take(&mut state.writes))
+}
+
+/// Takes the logs emitted since the last call.
+pub fn take_logs() -> Vec<Log> {
+  with(|state| core::mem::take(&mut state.logs))
+}
+
+const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+
+/// The precompile's semantics: `(digest, v, r, s)` in, the signer left-padded
+/// to a word out, or nothing if recovery fails.
+fn ecrecover(input: &[u8]) -> Vec<u8> {
+  let recover = || {
+    let input = input.get(..128)?;
+    let v = U256::from_be_slice(&input[32..64]);
+    let recovery_id = RecoveryId::from_byte(u8::try_from(v).ok()?.checked_sub(27)?)?;
+    let signature = Signature::from_slice(&input[64..128]).ok()?;
+    let key = VerifyingKey::recover_from_prehash(&input[..32], &signature, recovery_id).ok()?;
+    let point = key.to_encoded_point(false);
+    let digest = Keccak256::digest(&point.as_bytes()[1..]);
+    let mut output = [0u8; 32].to_vec();
+    output[12..].copy_from_slice(&digest[12..]);
+    Some(output)
+  };
+  recover().unwrap_or_default()
+}
+
+/// Address whose key is `secret`, matching what `ecrecover` returns.
+pub fn address_of(secret: &k256::ecdsa::SigningKey) -> Address {
+  let point = secret.verifying_key().to_encoded_point(false);
+  Address::from_slice(&Keccak256::digest(&point.as_bytes()[1..])[12..])
+}
+
+/// 65-byte `r || s || v` signature over `digest`.
+pub fn sign(secret: &k256::ecdsa::SigningKey, digest: B256) -> Vec<u8> {
+  let (signature, recovery_id) = secret.sign_prehash_recoverable(digest.as_slice()).unwrap();
+  let mut bytes = signature.to_bytes().to_vec();
+  bytes.push(27 + recovery_id.to_byte());
+  bytes
+}
+
+unsafe fn write(dest: *mut u8, bytes: &[u8]) {
+  core::ptr::copy_nonoverlapping(bytes.as_ptr(), dest, bytes.len());
+}
+
+unsafe fn word(ptr: *const u8) -> B256 {
+  B256::from_slice(slice::from_raw_parts(ptr, 32))
+}
+
+#[no_mangle]
+unsafe extern "C" fn storage_load_bytes32(key: *const u8, dest: *mut u8) {
+  let value = with(|state| state.storage.get(&word(key)).copied().unwrap_or_default());
+  write(dest, value.as_slice());
+}
+
+// Writes land immediately, so there is nothing left to flush.
+#[no_mangle]
+unsafe extern "C" fn storage_cache_bytes32(key: *const u8, value: *const u8) {
+  let (key, value) = (word(key), word(value));
+  with(|state| {
+    state.writes.push(key);
+    state.storage.insert(key, value)
+  });
+}
+
+#[no_mangle]
+extern "C" fn storage_flush_cache(_clear: bool) {}
+
+#[no_mangle]
+unsafe extern "C" fn native_keccak256(bytes: *const u8, len: usize, output: *mut u8) {
+  let digest = Keccak256::digest(slice::from_raw_parts(bytes, len));
+  write(output, &digest);
+}
+
+#[no_mangle]
+unsafe extern "C" fn emit_log(data: *const u8, len: usize, topics: usize) {
+  let bytes = slice::from_raw_parts(data, len);
+  let (topic_bytes, data) = bytes.split_at(topics * 32);
+  let log = Log {
+    topics: topic_bytes.chunks(32).map(B256::from_slice).collect(),
+    data: data.to_vec(),
+  };
+  with(|state| state.logs.push(log));
+}
+
+#[no_mangle]
+unsafe extern "C" fn msg_sender(sender: *mut u8) {
+  write(sender, with(|state| state.sender).as_slice());
+}
+
+#[no_mangle]
+unsafe extern "C" fn tx_origin(origin: *mut u8) {
+  write(origin, with(|state| state.origin).as_slice());
+}
+
+#[no_mangle]
+unsafe extern "C" fn msg_value(value: *mut u8) {
+  write(value, &with(|state| state.value).to_be_bytes::<32>());
+}
+
+#[no_mangle]
+extern "C" fn msg_reentrant() -> bool {
+  false
+}
+
+#[no_mangle]
+extern "C" fn block_timestamp() -> u64 {
+  with(|state| state.timestamp)
+}
+
+#[no_mangle]
+extern "C" fn block_number() -> u64 {
+  with(|state| state.block_number)
+}
+
+#[no_mangle]
+extern "C" fn chainid() -> u64 {
+  412_346
+}
+
+#[no_mangle]
+unsafe extern "C" fn contract_address(address: *mut u8) {
+  write(address, CONTRACT.as_slice());
+}
+
+#[no_mangle]
+unsafe extern "C" fn account_balance(_address: *const u8, dest: *mut u8) {
+  write(dest, &[0; 32]);
+}
+
+#[no_mangle]
+unsafe extern "C" fn account_codehash(address: *const u8, dest: *mut u8) {
+  let address = Address::from_slice(slice::from_raw_parts(address, 20));
+  let hash = if with(|state| state.contracts.contains(&address)) { [0xc0; 32] } else { [0; 32] };
+  write(dest, &hash);
+}
+
+#[no_mangle]
+unsafe extern "C" fn account_code_size(address: *const u8) -> usize {
+  let address = Address::from_slice(slice::from_raw_parts(address, 20));
+  usize::from(with(|state| state.contracts.contains(&address)))
+}
+
+// Accounts marked with `set_code` only report having code; plain value
+// transfers go through and anything that expects a contract on the other end
+// fails.
+#[no_mangle]
+unsafe extern "C" fn call_contract(
+  _contract: *const u8,
+  _calldata: *const u8,
+  calldata_len: usize,
+  _value: *const u8,
+  _gas: u64,
+  return_data_len: *mut usize,
+) -> u8 {
+  with(|state| state.return_data.clear());
+  *return_data_len = 0;
+  u8::from(calldata_len != 0)
+}
+
+#[no_mangle]
+unsafe extern "C" fn static_call_contract(
+  contract: *const u8,
+  calldata: *const u8,
+  calldata_len: usize,
+  _gas: u64,
+  return_data_len: *mut usize,
+) -> u8 {
+  let contract = Address::from_slice(slice::from_raw_parts(contract, 20));
+  let (output, status) = if contract == ECRECOVER {
+    (ecrecover(slice::from_raw_parts(calldata, calldata_len)), 0)
+  } else {
+    (Vec::new(), 1)
+  };
+  *return_data_len = output.len();
+  with(|state| state.return_data = output);
+  status
+}
+
+#[no_mangle]
+unsafe extern "C" fn delegate_call_contract(
+  _contract: *const u8,
+  _calldata: *const u8,
+  _calldata_len: usize,
+  _gas: u64,
+  return_data_len: *mut usize,
+) -> u8 {
+  with(|state| state.return_data.clear());
+  *return_data_len = 0;
+  1
+}
+
+#[no_mangle]
+unsafe extern "C" fn read_return_data(dest: *mut u8, offset: usize, size: usize) -> usize {
+  let data = with(|state| state.return_data.clone());
+  let start = offset.min(data.len());
+  let end = offset.saturating_add(size).min(data.len());
+  write(dest, &data[start..end]);
+  end - start
+}
+
+#[no_mangle]
+extern "C" fn return_data_size() -> usize {
+  with(|state| state.return_data.len())
+}
+
+#[no_mangle]
+extern "C" fn evm_gas_left() -> u64 {
+  u64::MAX
+}
+
+#[no_mangle]
+extern "C" fn evm_ink_left() -> u64 {
+  u64::MAX
+}
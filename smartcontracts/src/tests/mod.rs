@@ -0,0 +1,1550 @@
+mod host;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloy_sol_types::SolEvent;
+use proptest::prelude::*;
+use stylus_sdk::alloy_primitives::{b256, Address, FixedBytes, U256, U64};
+
+use crate::*;
+
+const OWNER: Address = Address::repeat_byte(0x01);
+const ALICE: Address = Address::repeat_byte(0xa1);
+const TASK: &str = "mnist";
+
+fn hash(n: u8) -> FixedBytes<32> {
+  FixedBytes::repeat_byte(n)
+}
+
+/// A registry owned by `OWNER` with `TASK` registered and no versions.
+fn registry_with_task() -> Registry {
+  let mut registry = host::deploy(OWNER);
+  assert!(registry.init().is_ok());
+  assert!(registry.register_task(String::from(TASK)).is_ok());
+  host::take_logs();
+  registry
+}
+
+fn latest(registry: &Registry) -> Option<u64> {
+  registry.get_latest(String::from(TASK)).ok().map(|record| record.0.to())
+}
+
+#[test]
+fn init_claims_ownership_once() {
+  let mut registry = host::deploy(OWNER);
+  assert!(registry.init().is_ok());
+  assert_eq!(registry.owner(), OWNER);
+
+  host::act_as(ALICE);
+  assert!(matches!(registry.init(), Err(RegistryError::AlreadyInitialized(_))));
+  assert_eq!(registry.owner(), OWNER);
+}
+
+#[test]
+fn only_owner_registers_top_level_tasks() {
+  let mut registry = registry_with_task();
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.register_task(String::from("cifar")),
+    Err(RegistryError::NotOwner(_))
+  ));
+  assert!(matches!(
+    registry.publish_new_version(String::from(TASK), hash(1)),
+    Err(RegistryError::NotPublisher(_))
+  ));
+}
+
+#[test]
+fn publish_collects_fee_from_msg_value() {
+  let mut registry = registry_with_task();
+  assert!(registry.set_fee(U256::ZERO, U256::from(100)).is_ok());
+
+  host::set_value(U256::from(99));
+  assert!(matches!(
+    registry.publish_new_version(String::from(TASK), hash(1)),
+    Err(RegistryError::InsufficientFee(_))
+  ));
+
+  host::set_value(U256::from(100));
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert_eq!(registry.collected_fees(), U256::from(100));
+}
+
+#[test]
+fn fees_without_a_token_price_are_charged_in_eth() {
+  let mut registry = registry_with_task();
+  assert!(registry.set_fee(U256::ZERO, U256::from(100)).is_ok());
+  assert!(registry.set_payment_token(Address::repeat_byte(0x70), U256::ZERO, U256::ZERO).is_ok());
+
+  assert!(matches!(
+    registry.publish_new_version(String::from(TASK), hash(1)),
+    Err(RegistryError::InsufficientFee(InsufficientFee { provided: U256::ZERO, .. }))
+  ));
+  assert!(registry.set_gating(String::from(TASK), true, U256::from(2)).is_ok());
+  assert!(matches!(
+    registry.subscribe(String::from(TASK), U64::from(10)),
+    Err(RegistryError::InsufficientFee(InsufficientFee { provided: U256::ZERO, .. }))
+  ));
+}
+
+#[test]
+fn plain_publish_writes_three_version_slots() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  host::take_writes();
+  assert!(registry.publish_new_version(String::from(TASK), hash(0x77)).is_ok());
+  let writes = host::take_writes();
+
+  // The hash is the first field, so the slot holding it is the record's base.
+  let base = writes
+    .iter()
+    .find(|slot| host::load(**slot) == hash(0x77))
+    .map(|slot| U256::from_be_bytes(slot.0))
+    .unwrap();
+  let mut offsets: Vec<U256> = writes
+    .iter()
+    .map(|slot| U256::from_be_bytes(slot.0).wrapping_sub(base))
+    .filter(|offset| *offset < U256::from(16))
+    .collect();
+  offsets.sort();
+  offsets.dedup();
+  assert_eq!(offsets, Vec::from([U256::ZERO, U256::from(1), U256::from(2)]));
+
+  // Publisher, timestamp and hash algorithm share the second slot.
+  let packed = host::load(FixedBytes::from(base + U256::from(1)));
+  assert_eq!(Address::from_slice(&packed[12..]), OWNER);
+  assert_eq!(U256::from_be_slice(&packed[4..12]), U256::from(host::timestamp()));
+}
+
+#[test]
+fn only_get_latest_fresh_reverts_on_stale_versions() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.set_max_age(String::from(TASK), U64::from(60)).is_ok());
+  assert!(registry.set_strict_mode(String::from(TASK), true).is_ok());
+  assert!(registry.get_latest_fresh(String::from(TASK)).is_ok());
+
+  host::advance(61);
+  assert_eq!(latest(&registry), Some(1));
+  assert_eq!(registry.get_latest_with_staleness(String::from(TASK)).ok().map(|r| r.1), Some(true));
+  assert!(matches!(
+    registry.get_latest_fresh(String::from(TASK)),
+    Err(RegistryError::StaleVersion(_))
+  ));
+}
+
+#[test]
+fn publish_records_version_and_emits_event() {
+  let mut registry = registry_with_task();
+  host::advance(12);
+  assert!(registry.publish_new_version(String::from(TASK), hash(7)).is_ok());
+
+  let record = registry.get_latest(String::from(TASK)).ok().unwrap();
+  assert_eq!(record.0, U64::from(1));
+  assert_eq!(record.1, hash(7));
+  assert_eq!(record.2, U64::from(host::timestamp()));
+  assert_eq!(record.3, OWNER);
+
+  let logs = host::take_logs();
+  let log = logs
+    .iter()
+    .find(|log| log.topics[0] == VersionPublished::SIGNATURE_HASH)
+    .unwrap();
+  let event = VersionPublished::decode_raw_log(log.topics.iter().copied(), &log.data, true).unwrap();
+  assert_eq!(event.version, 1);
+  assert_eq!(event.hash, hash(7));
+  assert_eq!(event.publisher, OWNER);
+}
+
+#[test]
+fn activation_delay_follows_block_timestamp() {
+  let mut registry = registry_with_task();
+  assert!(registry.set_activation_delay(String::from(TASK), U64::from(60)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert_eq!(latest(&registry), None);
+
+  host::advance(59);
+  assert_eq!(latest(&registry), None);
+  host::advance(1);
+  assert_eq!(latest(&registry), Some(1));
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+  Publish(u8),
+  Rollback(u64),
+  Yank(u64),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+  prop_oneof![
+    3 => any::<u8>().prop_map(Op::Publish),
+    2 => (0u64..8).prop_map(Op::Rollback),
+    2 => (0u64..8).prop_map(Op::Yank),
+  ]
+}
+
+/// Reference model of a task's history: one yanked flag per version and the
+/// version `get_latest` serves.
+#[derive(Default)]
+struct Model {
+  yanked: Vec<bool>,
+  latest: u64,
+}
+
+impl Model {
+  fn exists(&self, version: u64) -> bool {
+    version >= 1 && version <= self.yanked.len() as u64
+  }
+
+  fn is_yanked(&self, version: u64) -> bool {
+    self.yanked[version as usize - 1]
+  }
+
+  fn latest_unyanked(&self) -> Option<u64> {
+    (1..=self.latest).rev().find(|version| !self.is_yanked(*version))
+  }
+}
+
+proptest! {
+  #[test]
+  fn publish_rollback_yank_match_model(ops in prop::collection::vec(op(), 1..40)) {
+    let mut registry = registry_with_task();
+    let mut model = Model::default();
+
+    for op in ops {
+      match op {
+        Op::Publish(n) => {
+          prop_assert!(registry.publish_new_version(String::from(TASK), hash(n)).is_ok());
+          model.yanked.push(false);
+          model.latest = model.yanked.len() as u64;
+        }
+        Op::Rollback(to) => {
+          let expected = model.exists(to) && to < model.latest && !model.is_yanked(to);
+          let result = registry.rollback_latest(String::from(TASK), U64::from(to));
+          prop_assert_eq!(result.is_ok(), expected);
+          if expected {
+            model.latest = to;
+          }
+        }
+        Op::Yank(version) => {
+          let expected = model.exists(version) && !model.is_yanked(version);
+          let result = registry.yank_version(String::from(TASK), U64::from(version), String::from("bad"), U64::ZERO);
+          prop_assert_eq!(result.is_ok(), expected);
+          if expected {
+            model.yanked[version as usize - 1] = true;
+          }
+        }
+      }
+
+      let count = registry.get_version_count(String::from(TASK)).ok().unwrap();
+      prop_assert_eq!(count.to::<u64>(), model.yanked.len() as u64);
+      prop_assert_eq!(latest(&registry), (model.latest != 0).then_some(model.latest));
+      let unyanked = registry.get_latest_unyanked(String::from(TASK)).ok().map(|record| record.0.to::<u64>());
+      prop_assert_eq!(unyanked, model.latest_unyanked());
+    }
+  }
+}
+
+#[test]
+fn poseidon_matches_circomlib() {
+  let digest = poseidon::hash(U256::from(1), U256::from(2)).unwrap();
+  assert_eq!(
+    FixedBytes::<32>::from(digest),
+    b256!("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a")
+  );
+  assert_eq!(poseidon::hash(groth16::SCALAR_FIELD, U256::ZERO), None);
+}
+
+#[test]
+fn poseidon_versions_must_be_field_elements() {
+  let mut registry = registry_with_task();
+  assert!(matches!(
+    registry.publish_new_version_with_algorithm(String::from(TASK), hash(0xff), HASH_POSEIDON, String::new()),
+    Err(RegistryError::InvalidFieldElement(_))
+  ));
+
+  let commitment = registry.poseidon_commitment(Vec::from([U256::from(1), U256::from(2)])).ok().unwrap();
+  assert!(registry
+    .publish_new_version_with_algorithm(String::from(TASK), commitment, HASH_POSEIDON, String::new())
+    .is_ok());
+}
+
+/// Sibling path for leaf `index` of a full tree over `leaves`, built level by level.
+fn naive_proof(leaves: &[FixedBytes<32>], index: usize) -> Vec<FixedBytes<32>> {
+  let mut level = leaves.to_vec();
+  let mut zero = FixedBytes::ZERO;
+  let mut proof = Vec::new();
+  let mut index = index;
+  for _ in 0..merkle::DEPTH {
+    if level.len() % 2 == 1 {
+      level.push(zero);
+    }
+    proof.push(level[index ^ 1]);
+    level = level.chunks(2).map(|pair| merkle::hash_pair(pair[0], pair[1])).collect();
+    zero = merkle::hash_pair(zero, zero);
+    index /= 2;
+  }
+  proof
+}
+
+#[test]
+fn version_proofs_verify_against_tree_root() {
+  let mut registry = registry_with_task();
+  let hashes: Vec<_> = (1..=5).map(hash).collect();
+  for h in &hashes {
+    assert!(registry.publish_new_version(String::from(TASK), *h).is_ok());
+  }
+  let leaves: Vec<_> = hashes
+    .iter()
+    .enumerate()
+    .map(|(i, h)| merkle::leaf(i as u64 + 1, *h))
+    .collect();
+
+  for (i, h) in hashes.iter().enumerate() {
+    let proof = naive_proof(&leaves, i);
+    let version = U64::from(i + 1);
+    assert_eq!(registry.verify_version_proof(String::from(TASK), version, *h, proof.clone()).ok(), Some(true));
+    assert_eq!(registry.verify_version_proof(String::from(TASK), version, hash(9), proof).ok(), Some(false));
+  }
+  assert_eq!(
+    registry.verify_version_proof(String::from(TASK), U64::from(6), hash(6), naive_proof(&leaves, 5)).ok(),
+    Some(false)
+  );
+}
+
+#[test]
+fn checkpoints_log_state_root_over_task_roots() {
+  let mut registry = registry_with_task();
+  assert!(registry.register_task(String::from("cifar")).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+
+  let leaf = |task_id: &str, registry: &Registry| {
+    let root = registry.get_version_tree_root(String::from(task_id)).ok().unwrap();
+    crypto::keccak((crypto::keccak(task_id.as_bytes()), root).abi_encode())
+  };
+  let leaves = [leaf(TASK, &registry), leaf("cifar", &registry)];
+  for (i, leaf) in leaves.iter().enumerate() {
+    let root = merkle::root_from_proof(*leaf, i as u64, &naive_proof(&leaves, i));
+    assert_eq!(root, Some(registry.state_root()));
+  }
+
+  // The stored root tracks the tree as it grows and its leaves change.
+  let mut task_ids = Vec::from([String::from(TASK), String::from("cifar")]);
+  for n in 0..5u8 {
+    let task_id = format!("task-{n}");
+    assert!(registry.register_task(task_id.clone()).is_ok());
+    task_ids.push(task_id);
+    let target = task_ids[usize::from(n) % task_ids.len()].clone();
+    assert!(registry.publish_new_version(target, hash(n + 2)).is_ok());
+    let leaves: Vec<_> = task_ids.iter().map(|task_id| leaf(task_id, &registry)).collect();
+    let root = merkle::root_from_proof(leaves[0], 0, &naive_proof(&leaves, 0));
+    assert_eq!(root, Some(registry.state_root()));
+  }
+
+  assert_eq!(registry.record_checkpoint().ok(), Some(U64::ZERO));
+  let (state_root, task_count, _, timestamp) = registry.get_checkpoint(U64::ZERO).ok().unwrap();
+  assert_eq!(state_root, registry.state_root());
+  assert_eq!(task_count, U64::from(7));
+  assert_eq!(timestamp, U64::from(host::timestamp()));
+
+  host::act_as(ALICE);
+  assert!(matches!(registry.record_checkpoint(), Err(RegistryError::NotOwner(_))));
+  host::act_as(OWNER);
+  assert!(registry.set_checkpoint_interval(U64::from(3600)).is_ok());
+  host::act_as(ALICE);
+  assert!(matches!(registry.record_checkpoint(), Err(RegistryError::CheckpointTooSoon(_))));
+  host::advance(3600);
+  assert_eq!(registry.record_checkpoint().ok(), Some(U64::from(1)));
+}
+
+#[test]
+fn publish_cooldown_spaces_publishes_except_for_owner() {
+  let mut registry = registry_with_task();
+  assert!(registry.grant_publisher(String::from(TASK), ALICE).is_ok());
+  assert!(registry.set_publish_cooldown(String::from(TASK), U64::from(60)).is_ok());
+
+  host::act_as(ALICE);
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(matches!(
+    registry.publish_new_version(String::from(TASK), hash(2)),
+    Err(RegistryError::PublishCooldown(_))
+  ));
+
+  host::act_as(OWNER);
+  assert!(registry.publish_new_version(String::from(TASK), hash(2)).is_ok());
+
+  host::act_as(ALICE);
+  host::advance(60);
+  assert!(registry.publish_new_version(String::from(TASK), hash(3)).is_ok());
+
+  // Relaying someone else's signed publish does not lend them the exemption.
+  let signer = k256::ecdsa::SigningKey::from_slice(&[0x42; 32]).unwrap();
+  host::act_as(OWNER);
+  assert!(registry.grant_publisher(String::from(TASK), host::address_of(&signer)).is_ok());
+  let deadline = U256::from(host::timestamp());
+  let struct_hash = crypto::keccak(
+    (B256::from(PUBLISH_TYPEHASH), crypto::keccak(TASK.as_bytes()), hash(4), U256::ZERO, deadline).abi_encode(),
+  );
+  let signature = host::sign(&signer, eip712_digest(struct_hash));
+  let (r, s) = (B256::from_slice(&signature[..32]), B256::from_slice(&signature[32..64]));
+  assert!(matches!(
+    registry.publish_with_sig(String::from(TASK), hash(4), deadline, signature[64], r, s),
+    Err(RegistryError::PublishCooldown(_))
+  ));
+}
+
+#[test]
+fn tasks_are_browsable_by_category() {
+  let mut registry = registry_with_task();
+  assert!(registry.add_category(String::from("vision")).is_ok());
+  assert!(registry.add_category(String::from("nlp")).is_ok());
+  let audio = Vec::from([String::from("audio")]);
+  assert!(matches!(
+    host::transact(|| registry.register_task_with_categories(String::from("cifar"), audio)),
+    Err(RegistryError::UnknownCategory(_))
+  ));
+
+  let vision = Vec::from([String::from("vision")]);
+  assert!(registry.register_task_with_categories(String::from("cifar"), vision.clone()).is_ok());
+  assert!(registry.assign_category(String::from(TASK), String::from("vision")).is_ok());
+  assert!(matches!(
+    registry.assign_category(String::from(TASK), String::from("vision")),
+    Err(RegistryError::CategoryAlreadyAssigned(_))
+  ));
+
+  let page = registry.tasks_by_category(String::from("vision"), U64::ZERO, U64::from(10));
+  assert_eq!(page, Vec::from([String::from("cifar"), String::from(TASK)]));
+  assert_eq!(registry.get_task_categories(String::from("cifar")).ok(), Some(vision));
+
+  assert!(registry.retire_category(String::from("nlp")).is_ok());
+  assert_eq!(registry.categories(), Vec::from([String::from("vision")]));
+}
+
+#[test]
+fn deprecation_records_reason_and_sunset() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+
+  let now = U64::from(host::timestamp());
+  assert!(matches!(
+    registry.deprecate_version(String::from(TASK), U64::from(1), hash(0xee), now),
+    Err(RegistryError::InvalidSunset(_))
+  ));
+  let sunset = now + U64::from(86_400);
+  assert!(registry.deprecate_version(String::from(TASK), U64::from(1), hash(0xee), sunset).is_ok());
+  assert_eq!(
+    registry.get_deprecation(String::from(TASK), U64::from(1)).ok(),
+    Some((true, hash(0xee), sunset))
+  );
+  assert_eq!(latest(&registry), Some(1));
+}
+
+#[test]
+fn author_signature_is_recovered_and_recorded() {
+  let mut registry = registry_with_task();
+  let author = k256::ecdsa::SigningKey::from_slice(&[0x42; 32]).unwrap();
+  let digest = registry.get_release_digest(String::from(TASK), hash(1));
+  let signature = host::sign(&author, digest);
+
+  assert!(registry
+    .publish_new_version_with_author_sig(String::from(TASK), hash(1), signature.clone().into())
+    .is_ok());
+  assert_eq!(registry.get_author(String::from(TASK), U64::from(1)).ok(), Some(host::address_of(&author)));
+
+  // The same signature does not vouch for a different hash.
+  assert!(registry
+    .publish_new_version_with_author_sig(String::from(TASK), hash(2), signature.into())
+    .is_ok());
+  assert_ne!(registry.get_author(String::from(TASK), U64::from(2)).ok(), Some(host::address_of(&author)));
+
+  assert!(matches!(
+    registry.publish_new_version_with_author_sig(String::from(TASK), hash(3), Vec::from([0u8; 65]).into()),
+    Err(RegistryError::InvalidSignature(_))
+  ));
+}
+
+#[test]
+fn publisher_profiles_resolve_from_versions() {
+  let mut registry = registry_with_task();
+  assert!(registry.grant_publisher(String::from(TASK), ALICE).is_ok());
+  host::act_as(ALICE);
+  let did = String::from("did:web:alice.example");
+  assert!(registry.set_profile(hash(0xa), did.clone(), hash(0xb)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+
+  assert_eq!(
+    registry.get_publisher_profile(String::from(TASK), U64::from(1)).ok(),
+    Some((ALICE, hash(0xa), did, hash(0xb)))
+  );
+
+  assert!(registry.clear_profile().is_ok());
+  assert_eq!(registry.get_profile(ALICE).3, U64::ZERO);
+}
+
+#[test]
+fn audit_log_records_mutations_in_order() {
+  let mut registry = registry_with_task();
+  assert!(registry.grant_publisher(String::from(TASK), ALICE).is_ok());
+  host::act_as(ALICE);
+  host::advance(12);
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(2)).is_ok());
+  host::act_as(OWNER);
+  assert!(registry.rollback_latest(String::from(TASK), U64::from(1)).is_ok());
+  assert!(registry.yank_version(String::from(TASK), U64::from(2), String::new(), U64::ZERO).is_ok());
+
+  let log = registry.get_audit_log(String::from(TASK), U64::ZERO, U64::from(10)).ok().unwrap();
+  let actions: Vec<u8> = log.iter().map(|entry| entry.0).collect();
+  assert_eq!(
+    actions,
+    Vec::from([ACTION_REGISTER, ACTION_PUBLISHER_GRANT, ACTION_PUBLISH, ACTION_PUBLISH, ACTION_ROLLBACK, ACTION_YANK])
+  );
+  assert_eq!(log[1], (ACTION_PUBLISHER_GRANT, OWNER, U64::ZERO, ALICE, U64::from(1)));
+  assert_eq!(log[3], (ACTION_PUBLISH, ALICE, U64::from(2), Address::ZERO, U64::from(2)));
+
+  let tail = registry.get_audit_log(String::from(TASK), U64::from(4), U64::from(10)).ok().unwrap();
+  assert_eq!(tail.len(), 2);
+  assert_eq!(registry.get_audit_log_length(String::from(TASK)).ok(), Some(U64::from(6)));
+}
+
+#[test]
+fn role_changes_are_audited_once() {
+  let mut registry = registry_with_task();
+  assert!(registry.grant_evaluator(String::from(TASK), ALICE).is_ok());
+  assert!(registry.grant_evaluator(String::from(TASK), ALICE).is_ok());
+  assert!(registry.revoke_evaluator(String::from(TASK), ALICE).is_ok());
+  assert!(registry.revoke_evaluator(String::from(TASK), ALICE).is_ok());
+  assert!(registry.grant_round_client(String::from(TASK), ALICE).is_ok());
+  assert!(registry.grant_round_client(String::from(TASK), ALICE).is_ok());
+  assert!(registry.revoke_round_client(String::from(TASK), ALICE).is_ok());
+
+  let log = registry.get_audit_log(String::from(TASK), U64::from(1), U64::from(10)).ok().unwrap();
+  let actions: Vec<(u8, Address)> = log.iter().map(|entry| (entry.0, entry.3)).collect();
+  assert_eq!(
+    actions,
+    Vec::from([
+      (ACTION_EVALUATOR_GRANT, ALICE),
+      (ACTION_EVALUATOR_REVOKE, ALICE),
+      (ACTION_ROUND_CLIENT_GRANT, ALICE),
+      (ACTION_ROUND_CLIENT_REVOKE, ALICE),
+    ])
+  );
+}
+
+#[test]
+fn delta_chain_leads_back_to_a_full_version() {
+  let mut registry = registry_with_task();
+  assert!(matches!(
+    registry.publish_new_version_with_delta(String::from(TASK), hash(1), hash(0xd1)),
+    Err(RegistryError::NoVersions(_))
+  ));
+
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.publish_new_version_with_delta(String::from(TASK), hash(2), hash(0xd2)).is_ok());
+  assert!(registry.publish_new_version_with_delta(String::from(TASK), hash(3), hash(0xd3)).is_ok());
+  assert_eq!(registry.get_delta_hash(String::from(TASK), U64::from(1)).ok(), Some(FixedBytes::ZERO));
+
+  assert_eq!(
+    registry.get_delta_chain(String::from(TASK), U64::from(3)).ok(),
+    Some((U64::from(1), Vec::from([hash(0xd2), hash(0xd3)])))
+  );
+  assert_eq!(
+    registry.get_delta_chain(String::from(TASK), U64::from(1)).ok(),
+    Some((U64::from(1), Vec::new()))
+  );
+}
+
+#[test]
+fn federated_rounds_collect_updates_until_finalized() {
+  let mut registry = registry_with_task();
+  assert!(matches!(
+    registry.finalize_round(String::from(TASK), hash(0xa0), hash(0xb0)),
+    Err(RegistryError::RoundClosed(_))
+  ));
+  assert_eq!(registry.open_round(String::from(TASK)).ok(), Some(U64::from(1)));
+  assert!(matches!(registry.open_round(String::from(TASK)), Err(RegistryError::RoundStillOpen(_))));
+
+  let round = U64::from(1);
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.submit_update_commitment(String::from(TASK), round, hash(2)),
+    Err(RegistryError::NotRoundClient(_))
+  ));
+  host::act_as(OWNER);
+  assert!(registry.grant_round_client(String::from(TASK), OWNER).is_ok());
+  assert!(registry.grant_round_client(String::from(TASK), ALICE).is_ok());
+  assert!(registry.submit_update_commitment(String::from(TASK), round, hash(1)).is_ok());
+  host::act_as(ALICE);
+  assert!(registry.submit_update_commitment(String::from(TASK), round, hash(2)).is_ok());
+  assert!(matches!(
+    registry.submit_update_commitment(String::from(TASK), round, hash(3)),
+    Err(RegistryError::UpdateAlreadySubmitted(_))
+  ));
+  assert!(matches!(
+    registry.finalize_round(String::from(TASK), hash(0xa0), hash(0xb0)),
+    Err(RegistryError::NotTaskAdmin(_))
+  ));
+
+  host::act_as(OWNER);
+  assert_eq!(registry.finalize_round(String::from(TASK), hash(0xa0), hash(0xb0)).ok(), Some(round));
+  let (_, finalized_at, participants, aggregate, proof) = registry.get_round(String::from(TASK), round).ok().unwrap();
+  assert_eq!(finalized_at, U64::from(host::timestamp()));
+  assert_eq!((participants, aggregate, proof), (U64::from(2), hash(0xa0), hash(0xb0)));
+  assert_eq!(registry.get_update_commitment(String::from(TASK), round, ALICE).ok(), Some(hash(2)));
+
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.submit_update_commitment(String::from(TASK), round, hash(4)),
+    Err(RegistryError::RoundClosed(_))
+  ));
+  host::act_as(OWNER);
+  assert_eq!(registry.open_round(String::from(TASK)).ok(), Some(U64::from(2)));
+}
+
+#[test]
+fn inference_requests_must_cover_the_version_price() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  let version = U64::from(1);
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.set_inference_price(String::from(TASK), version, U256::from(500)),
+    Err(RegistryError::NotTaskAdmin(_))
+  ));
+  host::act_as(OWNER);
+  assert!(registry.set_inference_price(String::from(TASK), version, U256::from(500)).is_ok());
+  assert_eq!(registry.get_inference_price(String::from(TASK), version).ok(), Some(U256::from(500)));
+
+  host::act_as(ALICE);
+  host::set_value(U256::from(499));
+  assert!(matches!(
+    registry.request_inference(String::from(TASK), version, hash(0xf)),
+    Err(RegistryError::InsufficientFee(_))
+  ));
+  host::set_value(U256::from(500));
+  assert_eq!(registry.request_inference(String::from(TASK), version, hash(0xf)).ok(), Some(U64::ZERO));
+
+  // The proof must name its prover, here OWNER rather than the caller.
+  let inputs = |prover: Address| {
+    let commitment = |n: u8| U256::from_be_bytes(hash(n).0);
+    Vec::from([commitment(0xf), commitment(0xe), U256::from_be_slice(prover.as_slice())])
+  };
+  assert!(matches!(
+    registry.fulfill_inference(U64::ZERO, hash(0xe), Vec::new().into(), inputs(OWNER)),
+    Err(RegistryError::InvalidJobFulfillment(_))
+  ));
+  assert!(matches!(
+    registry.fulfill_inference(U64::ZERO, hash(0xe), Vec::new().into(), inputs(ALICE)),
+    Err(RegistryError::NoVerificationKey(_))
+  ));
+}
+
+#[test]
+fn pins_are_counted_per_version() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(2)).is_ok());
+  let count = |registry: &Registry, version: u64| registry.get_pin_count(String::from(TASK), U64::from(version)).ok();
+
+  assert!(registry.pin(String::from(TASK), U64::from(1)).is_ok());
+  assert!(registry.pin(String::from(TASK), U64::from(1)).is_ok());
+  host::act_as(ALICE);
+  assert!(registry.pin(String::from(TASK), U64::from(1)).is_ok());
+  assert_eq!(count(&registry, 1), Some(U64::from(2)));
+  assert!(registry.pin(String::from(TASK), U64::from(2)).is_ok());
+  assert_eq!((count(&registry, 1), count(&registry, 2)), (Some(U64::from(1)), Some(U64::from(1))));
+  assert!(registry.unpin(String::from(TASK)).is_ok());
+  assert!(registry.unpin(String::from(TASK)).is_ok());
+  assert_eq!(count(&registry, 2), Some(U64::ZERO));
+}
+
+#[test]
+fn aliases_follow_registration_rights() {
+  let mut registry = registry_with_task();
+  assert!(registry.set_task_admin(String::from(TASK), ALICE).is_ok());
+  assert!(registry.set_namespace_admin(String::from("alice"), ALICE).is_ok());
+
+  // Admin of the task alone is not enough to claim a top-level name.
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.add_alias(String::from("digits"), String::from(TASK)),
+    Err(RegistryError::NotOwner(_))
+  ));
+  assert!(registry.add_alias(String::from("alice/digits"), String::from(TASK)).is_ok());
+  assert_eq!(registry.resolve_task_id(String::from("alice/digits")), TASK);
+}
+
+#[test]
+fn requests_through_an_alias_record_the_canonical_id() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.add_alias(String::from("digits"), String::from(TASK)).is_ok());
+
+  host::set_value(U256::from(1));
+  let job_id = registry.request_inference(String::from("digits"), U64::from(1), hash(0xf)).ok().unwrap();
+  host::set_value(U256::ZERO);
+  assert_eq!(registry.get_job(job_id).ok().unwrap().0, TASK);
+  let request_id = registry.request_evaluation(String::from("digits"), U64::from(1), hash(0xe)).ok().unwrap();
+  assert_eq!(registry.get_evaluation(request_id).ok().unwrap().0, TASK);
+}
+
+#[test]
+fn pinners_are_listed_and_told_of_yanks() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  let consumers = [Address::repeat_byte(0xc1), Address::repeat_byte(0xc2), Address::repeat_byte(0xc3)];
+  for consumer in consumers {
+    host::act_as(consumer);
+    assert!(registry.pin(String::from(TASK), U64::from(1)).is_ok());
+  }
+  let pinners = |registry: &Registry| registry.get_pinners(String::from(TASK), U64::from(1), U64::ZERO, U64::from(10)).ok();
+  assert_eq!(pinners(&registry), Some(consumers.to_vec()));
+
+  // The last pinner moves into the slot the first one left.
+  host::act_as(consumers[0]);
+  assert!(registry.unpin(String::from(TASK)).is_ok());
+  assert_eq!(pinners(&registry), Some(vec![consumers[2], consumers[1]]));
+  host::act_as(consumers[1]);
+  assert!(registry.unpin(String::from(TASK)).is_ok());
+  assert_eq!(pinners(&registry), Some(vec![consumers[2]]));
+
+  host::act_as(OWNER);
+  host::take_logs();
+  assert!(registry.yank_version(String::from(TASK), U64::from(1), String::from("bad"), U64::ZERO).is_ok());
+  let logs = host::take_logs();
+  let log = logs.iter().find(|log| log.topics[0] == PinnedVersionYanked::SIGNATURE_HASH).unwrap();
+  let event = PinnedVersionYanked::decode_raw_log(log.topics.iter().copied(), &log.data, true).unwrap();
+  assert_eq!((event.version, event.pin_count), (1, 1));
+}
+
+#[test]
+fn pause_blocks_writes_but_not_withdrawals() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.pin(String::from(TASK), U64::from(1)).is_ok());
+  assert!(registry.pause().is_ok());
+
+  assert!(matches!(registry.unpin(String::from(TASK)), Err(RegistryError::EnforcedPause(_))));
+  assert!(matches!(
+    registry.set_approval_for_all(ALICE, true),
+    Err(RegistryError::EnforcedPause(_))
+  ));
+  assert!(registry.withdraw_fees(OWNER).is_ok());
+}
+
+#[test]
+fn multi_step_calls_advance_the_nonce_once() {
+  let mut registry = registry_with_task();
+  let nonce = |registry: &Registry, account: Address| registry.nonce_of(String::from(TASK), account).ok().unwrap();
+
+  let mut preimage = [0u8; 64];
+  preimage[..32].copy_from_slice(hash(1).as_slice());
+  preimage[32..].copy_from_slice(hash(2).as_slice());
+  assert!(registry.commit_version(String::from(TASK), crypto::keccak(preimage)).is_ok());
+  host::advance(1);
+  let before = nonce(&registry, OWNER);
+  assert!(registry.reveal_version(String::from(TASK), hash(1), hash(2)).is_ok());
+  assert_eq!(nonce(&registry, OWNER), before + U64::from(1));
+
+  assert!(registry.grant_approver(String::from(TASK), OWNER).is_ok());
+  assert!(registry.grant_approver(String::from(TASK), ALICE).is_ok());
+  assert!(registry.set_approval_policy(String::from(TASK), 2, U64::ZERO).is_ok());
+  let proposal_id = registry.propose_version(String::from(TASK), hash(3), String::new()).ok().unwrap();
+  let before = nonce(&registry, OWNER);
+  assert!(registry.approve_version(String::from(TASK), proposal_id).is_ok());
+  assert_eq!(nonce(&registry, OWNER), before + U64::from(1));
+  host::act_as(ALICE);
+  assert!(registry.approve_version(String::from(TASK), proposal_id).is_ok());
+  assert_eq!(latest(&registry), Some(2));
+  assert_eq!(nonce(&registry, ALICE), U64::from(1));
+}
+
+#[test]
+fn royalties_are_offered_by_the_child_and_accepted_by_the_parent() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.register_task(String::from("fine-tune")).is_ok());
+  assert!(registry.publish_new_version(String::from("fine-tune"), hash(2)).is_ok());
+  assert!(registry
+    .publish_new_version_with_metadata(
+      String::from("fine-tune"),
+      hash(3),
+      FixedBytes::ZERO,
+      String::from(TASK),
+      U64::from(1),
+      Vec::new(),
+    )
+    .is_ok());
+  const PARENT_ADMIN: Address = Address::repeat_byte(0xb0);
+  assert!(registry.set_task_admin(String::from(TASK), PARENT_ADMIN).is_ok());
+  assert!(registry.set_task_admin(String::from("fine-tune"), ALICE).is_ok());
+  assert!(registry.set_max_royalty_bps(1000).is_ok());
+  let royalty = |registry: &Registry| registry.get_royalty(String::from("fine-tune")).ok();
+
+  // Only the child's admin offers, within the cap, and version 1 has no parent to pay.
+  host::act_as(PARENT_ADMIN);
+  assert!(matches!(
+    registry.set_royalty(String::from("fine-tune"), U64::from(2), 500),
+    Err(RegistryError::NotTaskAdmin(_))
+  ));
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.set_royalty(String::from("fine-tune"), U64::from(1), 500),
+    Err(RegistryError::InvalidRoyalty(_))
+  ));
+  assert!(matches!(
+    registry.set_royalty(String::from("fine-tune"), U64::from(2), 1001),
+    Err(RegistryError::InvalidRoyalty(_))
+  ));
+  assert!(registry.set_royalty(String::from("fine-tune"), U64::from(2), 500).is_ok());
+  assert_eq!(royalty(&registry), Some((String::new(), 0)));
+
+  // The parent's admin accepts exactly what was offered.
+  assert!(matches!(
+    registry.accept_royalty(String::from("fine-tune"), 500),
+    Err(RegistryError::NotTaskAdmin(_))
+  ));
+  host::act_as(PARENT_ADMIN);
+  assert!(matches!(
+    registry.accept_royalty(String::from("fine-tune"), 1000),
+    Err(RegistryError::InvalidRoyalty(_))
+  ));
+  assert!(registry.accept_royalty(String::from("fine-tune"), 500).is_ok());
+  assert_eq!(royalty(&registry), Some((String::from(TASK), 500)));
+
+  // The child's admin can always drop it.
+  host::act_as(ALICE);
+  assert!(registry.set_royalty(String::from("fine-tune"), U64::from(2), 0).is_ok());
+  assert_eq!(royalty(&registry), Some((String::new(), 0)));
+}
+
+#[test]
+fn batch_members_cannot_be_forged_from_inner_nodes() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  let version = U64::from(1);
+  let leaf = |input: u8, output: u8| {
+    crypto::keccak([[0x00].as_slice(), hash(input).as_slice(), hash(output).as_slice()].concat())
+  };
+  let (a, b) = (leaf(1, 2), leaf(3, 4));
+  let (low, high) = if a <= b { (a, b) } else { (b, a) };
+  let root = crypto::keccak([[0x01].as_slice(), low.as_slice(), high.as_slice()].concat());
+  registry.tasks.setter(task_key(TASK)).versions.setter(version).batch_roots.insert(root, U64::from(2));
+
+  let member = |input: FixedBytes<32>, output: FixedBytes<32>, proof: Vec<FixedBytes<32>>| {
+    registry.is_batch_member(String::from(TASK), version, root, input, output, proof).ok()
+  };
+  assert_eq!(member(hash(1), hash(2), Vec::from([b])), Some(true));
+  assert_eq!(member(hash(3), hash(4), Vec::from([a])), Some(true));
+  // The two children of the root, passed off as an inference.
+  assert_eq!(member(low, high, Vec::new()), Some(false));
+}
+
+#[test]
+fn stakes_stay_locked_while_a_challenge_is_open() {
+  let mut registry = registry_with_task();
+  assert!(registry.set_staking(U256::from(1000), U64::from(60)).is_ok());
+  assert!(registry.set_challenge_period(String::from(TASK), U64::from(86_400)).is_ok());
+  assert!(registry.grant_publisher(String::from(TASK), ALICE).is_ok());
+  host::act_as(ALICE);
+  host::set_value(U256::from(1000));
+  assert!(registry.stake().is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.request_unstake().is_ok());
+
+  host::act_as(OWNER);
+  assert!(registry.challenge_version(String::from(TASK), U64::from(1), hash(0xe)).is_ok());
+  host::advance(61);
+  host::act_as(ALICE);
+  assert!(matches!(registry.unstake(), Err(RegistryError::StakeUnderChallenge(_))));
+
+  host::act_as(OWNER);
+  assert!(registry.resolve_challenge(String::from(TASK), U64::from(1), false).is_ok());
+  host::act_as(ALICE);
+  assert!(registry.unstake().is_ok());
+}
+
+#[test]
+fn an_unbounded_challenge_period_saturates() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.set_challenge_period(String::from(TASK), U64::MAX).is_ok());
+  host::set_value(U256::from(1000));
+  assert!(registry.challenge_version(String::from(TASK), U64::from(1), hash(0xe)).is_ok());
+  host::set_value(U256::ZERO);
+  assert_eq!(registry.get_challenge(String::from(TASK), U64::from(1)).ok().map(|challenge| challenge.4), Some(U64::MAX));
+}
+
+#[test]
+fn upheld_challenge_refunds_paying_consumers_from_slashed_stake() {
+  let mut registry = registry_with_task();
+  assert!(registry.set_staking(U256::from(1000), U64::ZERO).is_ok());
+  host::set_value(U256::from(1000));
+  assert!(registry.stake().is_ok());
+  assert!(registry.set_challenge_period(String::from(TASK), U64::from(86_400)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  let version = U64::from(1);
+
+  // Fulfilment needs the pairing precompile, so seed what two fulfilled jobs
+  // would have recorded.
+  {
+    let mut task = registry.tasks.setter(task_key(TASK));
+    let mut info = task.versions.setter(version);
+    info.consumer_payments.insert(ALICE, U256::from(300));
+    info.consumer_payments.insert(OWNER, U256::from(100));
+    info.total_payments.set(U256::from(400));
+  }
+
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.claim_refund(String::from(TASK), version),
+    Err(RegistryError::NoRefundDue(_))
+  ));
+  host::set_value(U256::from(10));
+  assert!(registry.challenge_version(String::from(TASK), version, hash(0xe)).is_ok());
+  host::act_as(OWNER);
+  assert!(registry.resolve_challenge(String::from(TASK), version, true).is_ok());
+
+  host::act_as(ALICE);
+  assert_eq!(
+    registry.get_refund(String::from(TASK), version, ALICE).ok(),
+    Some((U256::from(300), U256::from(300)))
+  );
+  assert_eq!(registry.claim_refund(String::from(TASK), version).ok(), Some(U256::from(300)));
+  assert!(matches!(
+    registry.claim_refund(String::from(TASK), version),
+    Err(RegistryError::NoRefundDue(_))
+  ));
+}
+
+#[test]
+fn auditors_are_credited_by_how_their_versions_fare_under_challenge() {
+  let mut registry = registry_with_task();
+  assert!(registry.set_challenge_period(String::from(TASK), U64::from(86_400)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(2)).is_ok());
+  assert!(registry.grant_auditor(ALICE).is_ok());
+  host::act_as(ALICE);
+  for version in [1, 2] {
+    assert!(registry.attest(String::from(TASK), U64::from(version), String::from("security"), hash(9)).is_ok());
+  }
+
+  for (version, upheld) in [(1, false), (2, true)] {
+    host::act_as(OWNER);
+    host::set_value(U256::ZERO);
+    assert!(registry.challenge_version(String::from(TASK), U64::from(version), hash(0xe)).is_ok());
+    assert!(registry.resolve_challenge(String::from(TASK), U64::from(version), upheld).is_ok());
+  }
+  let (_, _, _, upheld, overturned) = registry.reputation_of(ALICE);
+  assert_eq!((upheld, overturned), (U64::from(1), U64::from(1)));
+}
+
+#[test]
+fn verification_key_hashes_must_match_the_bound_groth16_key() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(2)).is_ok());
+  let vk = Vec::from([0u8; groth16::VK_FIXED_LEN + 64]);
+  let digest = stylus_sdk::crypto::keccak(&vk);
+  let set_hash = |registry: &mut Registry, version: u64, vk_hash| {
+    registry.set_verification_key(String::from(TASK), U64::from(version), vk_hash, String::from("ipfs://vk"))
+  };
+  let set_key = |registry: &mut Registry, version: u64| {
+    registry.set_groth16_key(String::from(TASK), U64::from(version), vk.clone().into())
+  };
+
+  // Key first: the hash has to commit to it.
+  assert!(set_key(&mut registry, 1).is_ok());
+  assert!(matches!(set_hash(&mut registry, 1, hash(9)), Err(RegistryError::InvalidVerificationKey(_))));
+  assert!(set_hash(&mut registry, 1, digest).is_ok());
+
+  // Hash first: only the key it commits to can be bound.
+  assert!(set_hash(&mut registry, 2, hash(9)).is_ok());
+  assert!(matches!(set_key(&mut registry, 2), Err(RegistryError::InvalidVerificationKey(_))));
+}
+
+#[test]
+fn repeated_proof_failures_trip_the_circuit_breaker() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  let version = U64::from(1);
+  let vk = Vec::from([0u8; groth16::VK_FIXED_LEN + 64]);
+  assert!(registry.set_groth16_key(String::from(TASK), version, vk.into()).is_ok());
+  assert!(matches!(
+    registry.set_circuit_breaker(String::from(TASK), 2, U64::ZERO, true),
+    Err(RegistryError::InvalidBreakerWindow(_))
+  ));
+  assert!(registry.set_circuit_breaker(String::from(TASK), 2, U64::from(600), true).is_ok());
+
+  let verify = |registry: &mut Registry| {
+    registry.verify_inference(String::from(TASK), version, Vec::new().into(), Vec::new())
+  };
+  // Unbonded outsiders cannot run up the count.
+  host::act_as(ALICE);
+  for _ in 0..3 {
+    assert_eq!(verify(&mut registry).ok(), Some(false));
+  }
+  assert_eq!(registry.get_breaker_state(String::from(TASK), version).ok().map(|s| s.0), Some(0));
+  host::act_as(OWNER);
+  assert_eq!(verify(&mut registry).ok(), Some(false));
+  // The window lapses, so the count starts over.
+  host::advance(600);
+  assert_eq!(verify(&mut registry).ok(), Some(false));
+  assert_eq!(
+    registry.get_breaker_state(String::from(TASK), version).ok(),
+    Some((1, U64::from(host::timestamp()), false, false))
+  );
+  assert_eq!(verify(&mut registry).ok(), Some(false));
+  assert!(matches!(verify(&mut registry), Err(RegistryError::CircuitBreakerOpen(_))));
+  assert_eq!(registry.get_breaker_state(String::from(TASK), version).ok().map(|s| s.3), Some(true));
+
+  assert!(registry.reset_circuit_breaker(String::from(TASK), version).is_ok());
+  assert_eq!(verify(&mut registry).ok(), Some(false));
+}
+
+#[test]
+fn external_verifier_replaces_the_groth16_key() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  let version = U64::from(1);
+  let verify = |registry: &mut Registry| {
+    registry.verify_inference(String::from(TASK), version, Vec::new().into(), Vec::from([U256::from(1)]))
+  };
+  assert!(matches!(verify(&mut registry), Err(RegistryError::NoVerificationKey(_))));
+
+  let verifier = Address::repeat_byte(0x77);
+  assert!(registry.set_external_verifier(String::from(TASK), verifier).is_ok());
+  assert_eq!(registry.get_external_verifier(String::from(TASK)).ok(), Some(verifier));
+  // There is no contract at the verifier in the mock host, so the call fails
+  // and counts as a rejected proof.
+  assert_eq!(verify(&mut registry).ok(), Some(false));
+
+  assert!(registry.set_external_verifier(String::from(TASK), Address::ZERO).is_ok());
+  assert!(matches!(verify(&mut registry), Err(RegistryError::NoVerificationKey(_))));
+}
+
+#[test]
+fn training_proofs_need_the_task_training_key() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.commit_dataset(String::from("corpus"), hash(0xd)).is_ok());
+  let datasets = Vec::from([(String::from("corpus"), U64::from(1))]);
+  assert!(registry
+    .publish_new_version_with_metadata(String::from(TASK), hash(2), hash(0xc), String::new(), U64::ZERO, datasets)
+    .is_ok());
+  assert_eq!(registry.get_lineage(String::from(TASK), U64::from(2)).ok(), Some(Vec::new()));
+  let submit = |registry: &mut Registry, version: u64| {
+    registry.submit_training_proof(String::from(TASK), U64::from(version), Vec::new().into(), Vec::new())
+  };
+  // Proofs are bound to the declared training data, so there must be some.
+  assert!(matches!(submit(&mut registry, 1), Err(RegistryError::NoTrainingData(_))));
+  assert_eq!(
+    registry.get_training_commitment(String::from(TASK), U64::from(2)).ok(),
+    Some(crypto::keccak(hash(0xd)))
+  );
+  let version = U64::from(2);
+  assert!(matches!(submit(&mut registry, 2), Err(RegistryError::NoVerificationKey(_))));
+
+  assert!(matches!(
+    registry.set_training_key(String::from(TASK), Vec::from([0u8; 10]).into()),
+    Err(RegistryError::InvalidVerificationKey(_))
+  ));
+  // A key without room for the version and data commitments is rejected.
+  let vk = Vec::from([0u8; groth16::VK_FIXED_LEN + 4 * 64]);
+  assert!(matches!(
+    registry.set_training_key(String::from(TASK), vk.into()),
+    Err(RegistryError::InvalidVerificationKey(_))
+  ));
+  let vk = Vec::from([0u8; groth16::VK_FIXED_LEN + 5 * 64]);
+  assert!(registry.set_training_key(String::from(TASK), vk.into()).is_ok());
+  assert_eq!(submit(&mut registry, 2).ok(), Some(false));
+  assert_eq!(
+    registry.get_training_status(String::from(TASK), version).ok(),
+    Some((false, FixedBytes::ZERO))
+  );
+}
+
+#[test]
+fn dependency_edges_are_readable_from_both_ends() {
+  let mut registry = registry_with_task();
+  assert!(registry.register_task(String::from("tokenizer")).is_ok());
+  assert!(registry.publish_new_version(String::from("tokenizer"), hash(1)).is_ok());
+  let missing = Vec::from([(String::from("tokenizer"), U64::from(2))]);
+  assert!(matches!(
+    registry.publish_new_version_with_dependencies(String::from(TASK), hash(2), missing),
+    Err(RegistryError::VersionNotFound(_))
+  ));
+
+  let edges = Vec::from([(String::from("tokenizer"), U64::from(1))]);
+  assert!(registry.publish_new_version_with_dependencies(String::from(TASK), hash(2), edges.clone()).is_ok());
+  assert_eq!(registry.get_dependencies(String::from(TASK), U64::from(1)).ok(), Some(edges));
+  assert_eq!(
+    registry.get_dependents(String::from("tokenizer"), U64::from(1), U64::ZERO, U64::from(10)).ok(),
+    Some(Vec::from([(String::from(TASK), U64::from(1))]))
+  );
+}
+
+#[test]
+fn license_flags_round_trip() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry
+    .publish_new_version_with_license(String::from(TASK), hash(2), String::from("Apache-2.0"), true, false, true)
+    .is_ok());
+
+  assert_eq!(
+    registry.license_of(String::from(TASK), U64::from(1)).ok(),
+    Some((String::new(), false, false, false))
+  );
+  assert_eq!(
+    registry.license_of(String::from(TASK), U64::from(2)).ok(),
+    Some((String::from("Apache-2.0"), true, false, true))
+  );
+}
+
+#[test]
+fn ratings_lock_a_deposit_until_withdrawn() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.set_rating_deposit(U256::from(50)).is_ok());
+  let version = U64::from(1);
+
+  host::act_as(ALICE);
+  host::set_value(U256::from(50));
+  assert!(matches!(
+    registry.rate_version(String::from(TASK), version, 6),
+    Err(RegistryError::InvalidRating(_))
+  ));
+  host::set_value(U256::from(49));
+  assert!(matches!(
+    registry.rate_version(String::from(TASK), version, 4),
+    Err(RegistryError::InsufficientFee(_))
+  ));
+  host::set_value(U256::from(50));
+  assert!(registry.rate_version(String::from(TASK), version, 4).is_ok());
+  assert!(matches!(
+    registry.rate_version(String::from(TASK), version, 5),
+    Err(RegistryError::AlreadyRated(_))
+  ));
+  host::act_as(OWNER);
+  host::set_value(U256::from(50));
+  assert!(registry.rate_version(String::from(TASK), version, 1).is_ok());
+  assert_eq!(registry.get_rating(String::from(TASK), version).ok(), Some((U64::from(5), U64::from(2))));
+
+  host::act_as(ALICE);
+  assert!(registry.withdraw_rating(String::from(TASK), version).is_ok());
+  assert_eq!(registry.get_rating(String::from(TASK), version).ok(), Some((U64::from(1), U64::from(1))));
+  assert_eq!(registry.get_rating_of(String::from(TASK), version, ALICE).ok(), Some((0, U256::ZERO)));
+  assert!(matches!(
+    registry.withdraw_rating(String::from(TASK), version),
+    Err(RegistryError::NotRated(_))
+  ));
+}
+
+#[test]
+fn latest_cache_follows_rollback_and_cancellation() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(2)).is_ok());
+  assert_eq!(registry.tasks.getter(task_key(TASK)).latest.version.get(), U64::from(2));
+  assert_eq!(registry.get_latest(String::from(TASK)).ok().map(|record| record.1), Some(hash(2)));
+
+  assert!(registry.rollback_latest(String::from(TASK), U64::from(1)).is_ok());
+  assert_eq!(registry.get_latest(String::from(TASK)).ok().map(|record| record.1), Some(hash(1)));
+
+  assert!(registry.set_activation_delay(String::from(TASK), U64::from(60)).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(3)).is_ok());
+  // While 3 is pending the lookup falls back to walking down from it.
+  assert_eq!(latest(&registry), Some(2));
+  assert!(registry.cancel_pending_version(String::from(TASK), U64::from(3)).is_ok());
+  assert_eq!(registry.tasks.getter(task_key(TASK)).latest.version.get(), U64::ZERO);
+  host::advance(60);
+  assert_eq!(latest(&registry), Some(2));
+}
+
+#[test]
+fn record_hashes_chain_back_from_the_head() {
+  let mut registry = registry_with_task();
+  for n in 1..=3 {
+    assert!(registry.publish_new_version(String::from(TASK), hash(n)).is_ok());
+    host::advance(10);
+  }
+  assert_eq!(
+    registry.get_prev_record_hash(String::from(TASK), U64::from(1)).ok(),
+    Some(FixedBytes::ZERO)
+  );
+
+  // Walk down from the head, recomputing each link from the version's own fields.
+  let mut expected = registry.get_record_hash(String::from(TASK), U64::from(3)).ok().unwrap();
+  for version in (1..=3u64).rev() {
+    let (_, hash, timestamp, ..) = registry.get_version(String::from(TASK), U64::from(version)).ok().unwrap();
+    let prev = registry.get_prev_record_hash(String::from(TASK), U64::from(version)).ok().unwrap();
+    let preimage = [hash.as_slice(), &timestamp.to_be_bytes::<8>(), &version.to_be_bytes(), prev.as_slice()].concat();
+    assert_eq!(stylus_sdk::crypto::keccak(preimage), expected);
+    expected = prev;
+  }
+}
+
+#[test]
+fn capabilities_expire_and_can_be_revoked() {
+  let mut registry = registry_with_task();
+  let expires_at = U64::from(host::timestamp() + 100);
+  assert!(matches!(
+    registry.issue_capability(String::from(TASK), ALICE, U64::from(host::timestamp())),
+    Err(RegistryError::InvalidExpiry(_))
+  ));
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.issue_capability(String::from(TASK), ALICE, expires_at),
+    Err(RegistryError::NotTaskAdmin(_))
+  ));
+
+  host::act_as(OWNER);
+  assert!(registry.issue_capability(String::from(TASK), ALICE, expires_at).is_ok());
+  assert_eq!(registry.has_capability(String::from(TASK), ALICE).ok(), Some(true));
+  assert_eq!(registry.capability_expiry(String::from(TASK), ALICE).ok(), Some(expires_at));
+  host::advance(100);
+  assert_eq!(registry.has_capability(String::from(TASK), ALICE).ok(), Some(false));
+
+  assert!(registry.issue_capability(String::from(TASK), ALICE, U64::from(host::timestamp() + 100)).is_ok());
+  assert!(registry.revoke_capability(String::from(TASK), ALICE).is_ok());
+  assert_eq!(registry.has_capability(String::from(TASK), ALICE).ok(), Some(false));
+  assert_eq!(registry.capability_expiry(String::from(TASK), ALICE).ok(), Some(U64::ZERO));
+}
+
+#[test]
+fn pokes_pay_a_capped_share_of_the_value_freed() {
+  let mut registry = registry_with_task();
+  registry.collected_fees.set(U256::from(15));
+  assert!(registry.set_keeper_bounty(U256::from(10)).is_ok());
+  assert!(matches!(
+    registry.subscribe(String::from(TASK), U64::ZERO),
+    Err(RegistryError::InvalidDuration(_))
+  ));
+
+  let mut task = registry.tasks.setter(task_key(TASK));
+  task.subscriptions.insert(OWNER, U64::from(host::timestamp() + 10));
+  task.subscription_paid.insert(OWNER, U256::from(700));
+  task.subscriptions.insert(ALICE, U64::from(host::timestamp() + 10));
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.poke_subscription(String::from(TASK), OWNER),
+    Err(RegistryError::SubscriptionNotExpired(_))
+  ));
+  host::advance(10);
+  // 1% of the 700 wei paid, under the bounty.
+  assert_eq!(registry.poke_subscription(String::from(TASK), OWNER).ok(), Some(U256::from(7)));
+  assert_eq!(registry.subscription_expiry(String::from(TASK), OWNER).ok(), Some(U64::ZERO));
+  // A subscription nobody paid ETH for frees nothing worth paying for.
+  assert_eq!(registry.poke_subscription(String::from(TASK), ALICE).ok(), Some(U256::ZERO));
+  assert_eq!(registry.collected_fees(), U256::from(8));
+}
+
+#[test]
+fn trusted_forwarder_calls_act_for_the_appended_sender() {
+  const FORWARDER: Address = Address::repeat_byte(0xf0);
+  let relayed = |sender: Address| [[0; 4].as_slice(), sender.as_slice()].concat();
+  let mut registry = registry_with_task();
+  assert!(registry.set_trusted_forwarder(FORWARDER).is_ok());
+  assert!(registry.is_trusted_forwarder(FORWARDER));
+
+  // Anyone else appending an address is just sending longer calldata.
+  host::act_as(ALICE);
+  assert_eq!(forwarder::unwrap(relayed(OWNER), FORWARDER).len(), 24);
+  assert_eq!(forwarder::msg_sender(), ALICE);
+
+  host::act_as(FORWARDER);
+  assert_eq!(forwarder::unwrap(relayed(ALICE), FORWARDER), [0; 4].to_vec());
+  assert!(matches!(
+    registry.register_task(String::from("relayed")),
+    Err(RegistryError::NotOwner(NotOwner { account: ALICE }))
+  ));
+  forwarder::unwrap(relayed(OWNER), FORWARDER);
+  assert!(registry.register_task(String::from("relayed")).is_ok());
+  assert_eq!(registry.task_admin(String::from("relayed")).ok(), Some(OWNER));
+}
+
+#[test]
+fn bounties_stay_open_until_an_example_is_proven() {
+  let mut registry = registry_with_task();
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  let version = U64::from(1);
+  let submit = |registry: &mut Registry, label: u8| {
+    let (label, prediction) = (U256::from(label), U256::from(7));
+    registry.submit_adversarial_example(String::from(TASK), version, hash(9), label, prediction, Vec::new().into())
+  };
+  assert!(matches!(submit(&mut registry, 7), Err(RegistryError::NotMisclassified(_))));
+  assert!(matches!(submit(&mut registry, 3), Err(RegistryError::NoVerificationKey(_))));
+
+  // The circuit has exactly seven public inputs.
+  let vk = |inputs: usize| Vec::from_iter(core::iter::repeat_n(0u8, groth16::VK_FIXED_LEN + 64 * (inputs + 1)));
+  assert!(matches!(
+    registry.set_adversarial_key(String::from(TASK), vk(6).into()),
+    Err(RegistryError::InvalidVerificationKey(_))
+  ));
+  assert!(registry.set_adversarial_key(String::from(TASK), vk(7).into()).is_ok());
+
+  let deadline = U64::from(host::timestamp() + 100);
+  assert!(matches!(
+    registry.fund_bounty(String::from(TASK), version, U64::from(host::timestamp())),
+    Err(RegistryError::InvalidExpiry(_))
+  ));
+  host::set_value(U256::from(100));
+  assert!(registry.fund_bounty(String::from(TASK), version, deadline).is_ok());
+  host::set_value(U256::from(50));
+  assert!(registry.fund_bounty(String::from(TASK), version, deadline).is_ok());
+  host::set_value(U256::ZERO);
+  // Claims are judged by the key the funders saw.
+  assert!(matches!(
+    registry.set_adversarial_key(String::from(TASK), vk(7).into()),
+    Err(RegistryError::BountyKeyFrozen(_))
+  ));
+  host::act_as(ALICE);
+  assert_eq!(submit(&mut registry, 3).ok(), Some(false));
+  assert_eq!(
+    registry.get_bounty(String::from(TASK), version).ok(),
+    Some((U256::from(150), deadline, Address::ZERO, FixedBytes::ZERO))
+  );
+
+  host::act_as(OWNER);
+  assert!(matches!(
+    registry.refund_bounty(String::from(TASK), version),
+    Err(RegistryError::BountyNotExpired(_))
+  ));
+  host::advance(100);
+  assert!(matches!(submit(&mut registry, 3), Err(RegistryError::BountyClosed(_))));
+  assert_eq!(registry.refund_bounty(String::from(TASK), version).ok(), Some(U256::from(150)));
+  assert_eq!(registry.refund_bounty(String::from(TASK), version).ok(), Some(U256::ZERO));
+  assert!(registry.set_adversarial_key(String::from(TASK), vk(7).into()).is_ok());
+}
+
+#[test]
+fn failing_hooks_do_not_block_publishes() {
+  const HOOK: Address = Address::repeat_byte(0x4b);
+  let mut registry = registry_with_task();
+  assert!(matches!(
+    registry.add_hook(String::from(TASK), HOOK, U64::ZERO),
+    Err(RegistryError::InvalidHookGas(_))
+  ));
+  for n in 0..MAX_HOOKS {
+    assert!(registry.add_hook(String::from(TASK), Address::repeat_byte(n as u8), U64::from(50_000)).is_ok());
+  }
+  assert!(matches!(
+    registry.add_hook(String::from(TASK), HOOK, U64::from(50_000)),
+    Err(RegistryError::TooManyHooks(_))
+  ));
+  assert!(registry.remove_hook(String::from(TASK), Address::repeat_byte(0)).is_ok());
+  assert!(registry.add_hook(String::from(TASK), HOOK, U64::from(50_000)).is_ok());
+  let hooks = registry.get_hooks(String::from(TASK)).ok().unwrap();
+  assert_eq!(hooks.len(), MAX_HOOKS);
+  assert_eq!(hooks[0].0, Address::repeat_byte(MAX_HOOKS as u8 - 1));
+  host::take_logs();
+
+  // The mock host has no hook contracts, so every call fails.
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  let failures = host::take_logs()
+    .iter()
+    .filter(|log| log.topics.first() == Some(&HookFailed::SIGNATURE_HASH))
+    .count();
+  assert_eq!(failures, MAX_HOOKS);
+  assert_eq!(latest(&registry), Some(1));
+}
+
+#[test]
+fn pruning_keeps_recent_versions_and_a_root_of_the_rest() {
+  let mut registry = registry_with_task();
+  for n in 1..=5 {
+    assert!(registry.publish_new_version(String::from(TASK), hash(n)).is_ok());
+  }
+  registry.tasks.setter(task_key(TASK)).versions.setter(U64::from(2)).refund_pool.set(U256::from(1));
+
+  host::act_as(ALICE);
+  assert!(matches!(
+    registry.prune_versions(String::from(TASK), U64::from(2)),
+    Err(RegistryError::NotOwner(_))
+  ));
+  host::act_as(OWNER);
+  // Version 2 still holds refunds, so pruning stops in front of it.
+  assert_eq!(registry.prune_versions(String::from(TASK), U64::from(2)).ok(), Some(U64::from(1)));
+  registry.tasks.setter(task_key(TASK)).versions.setter(U64::from(2)).refund_pool.set(U256::ZERO);
+  assert_eq!(registry.prune_versions(String::from(TASK), U64::from(2)).ok(), Some(U64::from(2)));
+  assert_eq!(registry.prune_versions(String::from(TASK), U64::from(2)).ok(), Some(U64::ZERO));
+
+  assert!(matches!(
+    registry.get_version(String::from(TASK), U64::from(3)),
+    Err(RegistryError::VersionNotFound(_))
+  ));
+  assert_eq!(registry.get_version(String::from(TASK), U64::from(4)).ok().map(|record| record.1), Some(hash(4)));
+  assert_eq!(latest(&registry), Some(5));
+
+  let leaves: Vec<_> = (1..=3u8).map(|n| merkle::leaf(u64::from(n), hash(n))).collect();
+  let mut frontier = [FixedBytes::ZERO; merkle::DEPTH];
+  for (i, leaf) in leaves.iter().enumerate() {
+    let (height, node) = merkle::append(|height| frontier[height], i as u64, *leaf);
+    frontier[height] = node;
+  }
+  let root = merkle::root(|height| frontier[height], 3);
+  assert_eq!(registry.get_pruned_history(String::from(TASK)).ok(), Some((U64::from(3), root)));
+
+  // A version a contract pinned is never pruned.
+  assert!(registry.publish_new_version(String::from(TASK), hash(6)).is_ok());
+  host::set_code(ALICE);
+  host::act_as(ALICE);
+  assert!(registry.pin(String::from(TASK), U64::from(4)).is_ok());
+  host::act_as(OWNER);
+  assert_eq!(registry.prune_versions(String::from(TASK), U64::from(1)).ok(), Some(U64::ZERO));
+}
+
+#[test]
+fn free_pins_and_ratings_do_not_hold_up_pruning() {
+  const STRANGER: Address = Address::repeat_byte(0x5e);
+  let mut registry = registry_with_task();
+  for n in 1..=3 {
+    assert!(registry.publish_new_version(String::from(TASK), hash(n)).is_ok());
+  }
+  host::act_as(STRANGER);
+  assert!(registry.pin(String::from(TASK), U64::from(1)).is_ok());
+  host::set_value(U256::from(7));
+  assert!(registry.rate_version(String::from(TASK), U64::from(1), 5).is_ok());
+
+  host::act_as(OWNER);
+  assert_eq!(registry.prune_versions(String::from(TASK), U64::from(1)).ok(), Some(U64::from(2)));
+  assert!(matches!(
+    registry.get_latest_unyanked(String::from(TASK)).map(|record| record.0),
+    Ok(version) if version == U64::from(3)
+  ));
+  // The stranger can still let go of both, and gets the deposit back.
+  host::act_as(STRANGER);
+  assert!(registry.unpin(String::from(TASK)).is_ok());
+  assert!(registry.withdraw_rating(String::from(TASK), U64::from(1)).is_ok());
+}
+
+#[test]
+fn reads_never_serve_pruned_versions() {
+  let mut registry = registry_with_task();
+  for n in 1..=3 {
+    assert!(registry.publish_new_version(String::from(TASK), hash(n)).is_ok());
+    host::advance(10);
+  }
+  assert!(registry.set_tag(String::from(TASK), String::from("old"), U64::from(1)).is_ok());
+  assert!(registry.yank_version(String::from(TASK), U64::from(3), String::from("bad"), U64::ZERO).is_ok());
+  assert_eq!(registry.prune_versions(String::from(TASK), U64::from(1)).ok(), Some(U64::from(2)));
+
+  assert!(matches!(
+    registry.get_latest_unyanked(String::from(TASK)),
+    Err(RegistryError::NoVersions(_))
+  ));
+  assert!(matches!(
+    registry.get_by_tag(String::from(TASK), String::from("old")),
+    Err(RegistryError::VersionNotFound(_))
+  ));
+  assert!(matches!(
+    registry.get_version_at(String::from(TASK), U64::from(host::timestamp() - 25)),
+    Err(RegistryError::NoVersionAt(_))
+  ));
+}
+
+#[test]
+fn pruned_tasks_migrate_with_their_history() {
+  let mut registry = registry_with_task();
+  for n in 1..=5 {
+    assert!(registry.publish_new_version(String::from(TASK), hash(n)).is_ok());
+  }
+  assert_eq!(registry.prune_versions(String::from(TASK), U64::from(2)).ok(), Some(U64::from(3)));
+  let history = registry.get_pruned_history(String::from(TASK)).ok();
+  let tree_root = registry.get_version_tree_root(String::from(TASK)).ok();
+  let record_hash = registry.get_record_hash(String::from(TASK), U64::from(5)).ok();
+  let (chunk, _) = registry.export_state(U64::ZERO, U64::from(10));
+
+  let mut registry = host::deploy(OWNER);
+  assert!(registry.init().is_ok());
+  assert!(registry.import_state(chunk).is_ok());
+  assert_eq!(registry.get_pruned_history(String::from(TASK)).ok(), history);
+  assert_eq!(registry.get_version_tree_root(String::from(TASK)).ok(), tree_root);
+  assert_eq!(registry.get_record_hash(String::from(TASK), U64::from(5)).ok(), record_hash);
+  assert!(matches!(
+    registry.get_version(String::from(TASK), U64::from(3)),
+    Err(RegistryError::VersionNotFound(_))
+  ));
+  assert_eq!(latest(&registry), Some(5));
+  // Publishing carries on from the migrated tree.
+  assert!(registry.publish_new_version(String::from(TASK), hash(6)).is_ok());
+  assert_eq!(latest(&registry), Some(6));
+}
+
+#[test]
+fn migrations_carry_task_settings_and_categories() {
+  let mut registry = registry_with_task();
+  assert!(registry.add_category(String::from("vision")).is_ok());
+  assert!(registry.register_task_with_categories(String::from("cifar"), Vec::from([String::from("vision")])).is_ok());
+  assert!(registry.publish_new_version(String::from(TASK), hash(1)).is_ok());
+  assert!(registry.set_strict_mode(String::from(TASK), true).is_ok());
+  assert!(registry.set_max_age(String::from(TASK), U64::from(600)).is_ok());
+  assert!(registry.set_gating(String::from(TASK), true, U256::from(5)).is_ok());
+  assert!(registry.set_circuit_breaker(String::from(TASK), 3, U64::from(60), true).is_ok());
+  assert!(registry.archive_task(String::from("cifar"), String::from("superseded")).is_ok());
+  let (chunk, _) = registry.export_state(U64::ZERO, U64::from(10));
+
+  let mut registry = host::deploy(OWNER);
+  assert!(registry.init().is_ok());
+  assert!(matches!(
+    host::transact(|| registry.import_state(chunk.clone())),
+    Err(RegistryError::UnknownCategory(_))
+  ));
+  assert!(registry.add_category(String::from("vision")).is_ok());
+  assert!(registry.import_state(chunk.clone()).is_ok());
+  // Everything exported comes back: a second export is byte for byte the same.
+  assert_eq!(registry.export_state(U64::ZERO, U64::from(10)).0, chunk);
+  assert_eq!(
+    registry.get_task_categories(String::from("cifar")).ok(),
+    Some(Vec::from([String::from("vision")]))
+  );
+  assert_eq!(
+    registry.get_archive_status(String::from("cifar")).ok(),
+    Some((true, String::from("superseded")))
+  );
+  assert!(matches!(
+    registry.publish_new_version(String::from(TASK), hash(1)),
+    Err(RegistryError::DuplicateHash(_))
+  ));
+}
+
+#[test]
+fn guardians_recover_ownership_after_the_delay() {
+  const GUARDIANS: [Address; 3] = [Address::repeat_byte(0x91), Address::repeat_byte(0x92), Address::repeat_byte(0x93)];
+  const NEW_OWNER: Address = Address::repeat_byte(0x0e);
+  let mut registry = host::deploy(OWNER);
+  assert!(registry.init().is_ok());
+  assert!(matches!(
+    registry.set_recovery_policy(2, U64::from(100)),
+    Err(RegistryError::InvalidRecoveryThreshold(_))
+  ));
+  for guardian in GUARDIANS {
+    assert!(registry.set_recovery_guardian(guardian, true).is_ok());
+  }
+  assert!(registry.set_recovery_policy(2, U64::from(100)).is_ok());
+
+  // The owner can abort a proposal; approvals given to it do not carry over.
+  host::act_as(GUARDIANS[0]);
+  assert!(registry.propose_recovery(ALICE).is_ok());
+  host::act_as(OWNER);
+  assert!(registry.cancel_recovery().is_ok());
+
+  host::act_as(GUARDIANS[1]);
+  assert!(registry.propose_recovery(NEW_OWNER).is_ok());
+  assert!(matches!(registry.approve_recovery(), Err(RegistryError::RecoveryAlreadyApproved(_))));
+  assert!(matches!(registry.execute_recovery(), Err(RegistryError::RecoveryNotReady(_))));
+  host::act_as(ALICE);
+  assert!(matches!(registry.approve_recovery(), Err(RegistryError::NotRecoveryGuardian(_))));
+  host::act_as(GUARDIANS[2]);
+  assert!(registry.approve_recovery().is_ok());
+  let executable_at = U64::from(host::timestamp() + 100);
+  assert_eq!(registry.get_recovery(), (NEW_OWNER, 2, executable_at));
+
+  assert!(matches!(registry.execute_recovery(), Err(RegistryError::RecoveryNotReady(_))));
+  host::advance(100);
+  assert!(registry.execute_recovery().is_ok());
+  assert_eq!(registry.owner(), NEW_OWNER);
+  assert_eq!(registry.get_recovery().0, Address::ZERO);
+}